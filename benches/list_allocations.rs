@@ -0,0 +1,103 @@
+//! Counts the allocations of rendering one 10,000-ticket JSON page two ways:
+//! cloning every ticket into a response and a [`serde_json::Value`] first,
+//! as `GET /tickets` used to, and serializing each ticket in place with
+//! `TicketStore::write_page_json`.
+//!
+//! Run with `cargo bench --bench list_allocations`.
+
+use serde_json::json;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use ticket_api::{TicketDraft, TicketFilter, TicketResponse, TicketStore};
+
+/// Tickets in the store, all on the one page.
+const TICKETS: usize = 10_000;
+
+/// Allocations made so far.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated so far, never decremented.
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// The allocation and byte counters right now.
+fn counters() -> (usize, usize) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    )
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let store = TicketStore::new();
+        let drafts = (0..TICKETS)
+            .map(|n| {
+                TicketDraft::builder()
+                    .title(&format!("Ticket number {}", n))
+                    .description("A description long enough to be worth not copying")
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        store.add_tickets(drafts).await.unwrap();
+
+        let start = counters();
+        let (tickets, total) = store.list_tickets_paginated(0, TICKETS, false).await;
+        let responses: Vec<TicketResponse> =
+            tickets.into_iter().map(TicketResponse::from).collect();
+        let cloned = serde_json::to_vec(&json!({
+            "tickets": responses.iter().map(|response| json!(response)).collect::<Vec<_>>(),
+            "total": total,
+            "limit": TICKETS,
+            "offset": 0
+        }))
+        .unwrap();
+        let after_cloning = counters();
+
+        let mut borrowed = Vec::new();
+        store
+            .write_page_json(&TicketFilter::default(), 0, TICKETS, &mut borrowed)
+            .await
+            .unwrap();
+        let after_borrowing = counters();
+
+        let cloning = (after_cloning.0 - start.0, after_cloning.1 - start.1);
+        let borrowing = (
+            after_borrowing.0 - after_cloning.0,
+            after_borrowing.1 - after_cloning.1,
+        );
+        println!("{} tickets, {} bytes of JSON", TICKETS, cloned.len());
+        println!(
+            "cloning:   {:>9} allocations, {:>11} bytes",
+            cloning.0, cloning.1
+        );
+        println!(
+            "borrowing: {:>9} allocations, {:>11} bytes",
+            borrowing.0, borrowing.1
+        );
+        assert!(
+            borrowing.0 * 10 < cloning.0,
+            "serializing in place should allocate far less"
+        );
+    });
+}
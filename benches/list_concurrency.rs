@@ -0,0 +1,75 @@
+//! Times `TicketStore::list_tickets` on a large store, reading the tickets
+//! one after another and with `list_read_concurrency` fanning the reads out.
+//!
+//! Run with `cargo bench --bench list_concurrency`.
+
+use std::time::{Duration, Instant};
+use ticket_api::{ServerConfig, TicketDraft, TicketStore};
+
+/// Tickets in the store.
+const TICKETS: usize = 20_000;
+
+/// Timed listings per configuration, after one warm-up run.
+const RUNS: usize = 20;
+
+/// The read concurrencies timed; `None` is the sequential path.
+const CONCURRENCIES: [Option<usize>; 4] = [None, Some(4), Some(16), Some(64)];
+
+/// Times `RUNS` listings of `store`.
+///
+/// # Returns
+/// The fastest and the median run
+async fn time_listings(store: &TicketStore) -> (Duration, Duration) {
+    assert_eq!(store.list_tickets().await.len(), TICKETS);
+    let mut runs = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let tickets = store.list_tickets().await;
+        runs.push(start.elapsed());
+        assert_eq!(tickets.len(), TICKETS);
+    }
+    runs.sort();
+    (runs[0], runs[RUNS / 2])
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let source = TicketStore::new();
+        let drafts = (0..TICKETS)
+            .map(|n| {
+                TicketDraft::builder()
+                    .title(&format!("Ticket number {}", n))
+                    .description("Listed over and over")
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        source.add_tickets(drafts).await.unwrap();
+        let snapshot = source.export_snapshot().await;
+
+        println!("list_tickets over {} tickets, {} runs each", TICKETS, RUNS);
+        for concurrency in CONCURRENCIES {
+            let store = TicketStore::with_config(ServerConfig {
+                list_read_concurrency: concurrency,
+                ..ServerConfig::default()
+            });
+            store.import_snapshot(snapshot.clone()).await.unwrap();
+
+            let (fastest, median) = time_listings(&store).await;
+            let label = match concurrency {
+                Some(n) => format!("concurrent ({})", n),
+                None => "sequential".to_string(),
+            };
+            println!(
+                "{:<16} fastest {:>8.2} ms, median {:>8.2} ms",
+                label,
+                fastest.as_secs_f64() * 1000.0,
+                median.as_secs_f64() * 1000.0
+            );
+        }
+    });
+}
@@ -0,0 +1,663 @@
+//! Runtime configuration for the ticket API.
+//!
+//! This module groups the tunable policies of the service:
+//! - The address the server binds to
+//! - Title and description length limits
+//! - The allowed range of story point estimates
+//! - Ticket creation throttling per reporter
+//! - The most tickets the store holds
+//! - Default description templates per category
+//! - Default assignees per category
+//! - Read concurrency when listing tickets
+//! - Size, depth and node count limits on custom fields
+//! - Custom fields required per ticket kind
+//! - The CORS policy, permissive or an origin allowlist
+//! - Legacy response field names
+//! - How long reads wait for a requested write version
+//! - The token guarding admin endpoints
+//! - The API key guarding ticket endpoints
+//! - SLA targets per status
+//! - Guarding descriptions against accidental deletion
+//! - How many changes the change log retains
+//! - How long and how many idempotency keys are remembered
+//! - Whether every request is logged
+//! - The largest request body accepted by ticket endpoints
+//! - How long a request may take
+//! - Whether demo tickets are seeded at startup
+//! - Webhooks notified of ticket events
+//! - Purging tickets that have long been done
+//! - How long, and how many, deleted tickets stay in the trash
+//! - Request rate limits per client, and the proxies trusted to name clients
+//! - The most clients streaming events at once
+//! - Whether error bodies include the underlying error
+//!
+//! Every option defaults to the permissive behavior so an unconfigured
+//! server behaves exactly like before. [`ServerConfig::from_env`] overrides
+//! the address, length limits, ticket capacity, API key, request logging,
+//! body size limit, request timeout, demo seeding, webhook URLs, purge age,
+//! trash retention, rate limit, trusted proxies, event subscriber cap, error verbosity and
+//! CORS allowlist from `TICKET_API_*` environment variables.
+
+use axum::http::{HeaderValue, Method};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::data::Status;
+
+/// Configuration shared by the server and the ticket store.
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    /// Host the server binds to
+    pub host: String,
+    /// Port the server listens on
+    pub port: u16,
+    /// Maximum length of a ticket title, in characters
+    pub max_title_len: usize,
+    /// Maximum length of a ticket description, in characters
+    pub max_description_len: usize,
+    /// Rejects blank descriptions on creation and patches, instead of
+    /// accepting them as empty
+    pub require_description: bool,
+    /// Lets only an assigned ticket's assignee, named by the `X-User`
+    /// header, change its status, whichever endpoint makes the change.
+    /// Anyone may still edit the other fields.
+    pub status_owner_only: bool,
+    /// Story point estimates a ticket may carry, inclusive
+    pub estimate_range: RangeInclusive<u16>,
+    /// Optional per-reporter limit on ticket creation. Disabled when `None`.
+    pub creation_throttle: Option<CreationThrottle>,
+    /// Most tickets the store holds, soft-deleted ones included. Unbounded
+    /// when `None`.
+    pub max_tickets: Option<usize>,
+    /// Description templates keyed by category, used when a ticket is
+    /// created in that category with an empty description.
+    pub description_templates: HashMap<String, String>,
+    /// Assignees keyed by category, used when a ticket is created in that
+    /// category without an assignee.
+    pub default_assignees: HashMap<String, String>,
+    /// Maximum number of per-ticket locks read concurrently when listing
+    /// tickets. Reads are sequential when `None`.
+    pub list_read_concurrency: Option<usize>,
+    /// Limits applied to each ticket's custom fields
+    pub custom_field_limits: CustomFieldLimits,
+    /// Custom field keys a ticket must carry, keyed by ticket kind, e.g.
+    /// `"bug"` requiring `"steps_to_reproduce"`. Kinds without an entry
+    /// require nothing.
+    pub required_custom_fields: HashMap<String, Vec<String>>,
+    /// CORS policy applied to every route
+    pub cors: CorsPolicy,
+    /// Renaming of response fields for legacy clients
+    pub legacy_fields: LegacyFields,
+    /// Read-your-writes waiting behavior
+    pub read_your_writes: ReadYourWrites,
+    /// Bearer token required by the `/admin` endpoints. Admin endpoints are
+    /// refused when `None`.
+    pub admin_token: Option<String>,
+    /// Key every `/tickets` request must send in `x-api-key`. Ticket
+    /// endpoints are open when `None`.
+    pub api_key: Option<String>,
+    /// SLA targets reported on single-ticket reads. SLA tracking is off when `None`.
+    pub sla: Option<SlaPolicy>,
+    /// Rejects patches that cut most of a description unless forced.
+    /// Disabled when `None`.
+    pub description_shrink_guard: Option<DescriptionShrinkGuard>,
+    /// Number of most recent changes kept for `GET /tickets/changes`
+    pub change_log_retention: usize,
+    /// Retention of `Idempotency-Key`s sent to `POST /tickets`
+    pub idempotency: IdempotencyPolicy,
+    /// Logs method, path, status and latency of every request, tagged with
+    /// its `x-request-id`
+    pub request_logging: bool,
+    /// Largest request body, in bytes, accepted by `/tickets` routes
+    pub max_body_bytes: usize,
+    /// Longest a request may take before it is answered with
+    /// `408 Request Timeout`. The event stream is exempt.
+    pub request_timeout: Duration,
+    /// Longest reading or patching a single ticket waits for its lock before
+    /// giving up with `503 Service Unavailable`
+    pub lock_timeout: Duration,
+    /// Fills an empty store with demo tickets when the server starts
+    pub seed_demo_data: bool,
+    /// Webhooks notified of every ticket event. Off when no URL is set.
+    pub webhooks: WebhookConfig,
+    /// Periodic removal of tickets that have long been `Done`. Off when `None`.
+    pub purge: Option<PurgePolicy>,
+    /// How long, and how many, soft-deleted tickets are kept. Kept until an
+    /// admin purges them when `None`.
+    pub trash: Option<TrashPolicy>,
+    /// Requests each client IP may make, `/health` aside. Unlimited when `None`.
+    pub rate_limit: Option<RateLimit>,
+    /// Most clients streaming `/tickets/events` at once. Unlimited when `None`.
+    pub max_event_subscribers: Option<usize>,
+    /// Whether error bodies include the underlying store error and its debug
+    /// form, for development. Compact (`false`) by default.
+    pub verbose_errors: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 3000,
+            max_title_len: 100,
+            max_description_len: 1000,
+            require_description: false,
+            status_owner_only: false,
+            estimate_range: 0..=100,
+            creation_throttle: None,
+            max_tickets: None,
+            description_templates: HashMap::new(),
+            default_assignees: HashMap::new(),
+            list_read_concurrency: None,
+            custom_field_limits: CustomFieldLimits::default(),
+            required_custom_fields: HashMap::new(),
+            cors: CorsPolicy::default(),
+            legacy_fields: LegacyFields::default(),
+            read_your_writes: ReadYourWrites::default(),
+            admin_token: None,
+            api_key: None,
+            sla: None,
+            description_shrink_guard: None,
+            change_log_retention: 1000,
+            idempotency: IdempotencyPolicy::default(),
+            request_logging: true,
+            max_body_bytes: 64 * 1024,
+            request_timeout: Duration::from_secs(30),
+            lock_timeout: Duration::from_secs(5),
+            seed_demo_data: false,
+            webhooks: WebhookConfig::default(),
+            purge: None,
+            trash: None,
+            rate_limit: None,
+            max_event_subscribers: None,
+            verbose_errors: false,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Create the default configuration, overridden by environment variables.
+    ///
+    /// Reads `TICKET_API_HOST`, `TICKET_API_PORT`, `TICKET_API_MAX_TITLE_LEN`,
+    /// `TICKET_API_MAX_DESCRIPTION_LEN`, `TICKET_API_REQUIRE_DESCRIPTION`,
+    /// `TICKET_API_STATUS_OWNER_ONLY`, `TICKET_API_MAX_TICKETS`,
+    /// `TICKET_API_KEY`, `TICKET_API_REQUEST_LOG`, `TICKET_API_MAX_BODY_BYTES`,
+    /// `TICKET_API_REQUEST_TIMEOUT_SECS`, `TICKET_API_LOCK_TIMEOUT_MS`,
+    /// `TICKET_API_SEED`,
+    /// `TICKET_API_WEBHOOK_URLS`, a comma-separated list,
+    /// `TICKET_API_PURGE_AFTER`, in seconds, which turns purging on,
+    /// `TICKET_API_TRASH_RETENTION`, in seconds, which turns the trash limits on,
+    /// `TICKET_API_RATE_LIMIT`, in requests per minute, which turns rate
+    /// limiting on, with `TICKET_API_TRUSTED_PROXIES`, a comma-separated list
+    /// of proxy IPs, `TICKET_API_MAX_EVENT_SUBSCRIBERS`,
+    /// `TICKET_API_VERBOSE_ERRORS`, and `TICKET_API_CORS_ORIGINS`, a comma-separated list,
+    /// which replaces the permissive CORS policy with an allowlist. The
+    /// allowlist's methods and credentials come from `TICKET_API_CORS_METHODS`,
+    /// also comma-separated, and the `TICKET_API_CORS_CREDENTIALS` flag.
+    /// Unset or, for the key, empty variables keep their defaults.
+    ///
+    /// # Returns
+    /// * `Ok(ServerConfig)` - The configuration
+    /// * `Err(ConfigError)` - If a variable doesn't hold a valid number, flag,
+    ///   origin, method or IP address
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        if let Ok(host) = std::env::var("TICKET_API_HOST") {
+            config.host = host;
+        }
+        if let Some(port) = env_number("TICKET_API_PORT")? {
+            config.port = port;
+        }
+        if let Some(max_title_len) = env_number("TICKET_API_MAX_TITLE_LEN")? {
+            config.max_title_len = max_title_len;
+        }
+        if let Some(max_description_len) = env_number("TICKET_API_MAX_DESCRIPTION_LEN")? {
+            config.max_description_len = max_description_len;
+        }
+        if let Some(require_description) = env_flag("TICKET_API_REQUIRE_DESCRIPTION")? {
+            config.require_description = require_description;
+        }
+        if let Some(status_owner_only) = env_flag("TICKET_API_STATUS_OWNER_ONLY")? {
+            config.status_owner_only = status_owner_only;
+        }
+        if let Some(max_tickets) = env_number("TICKET_API_MAX_TICKETS")? {
+            config.max_tickets = Some(max_tickets);
+        }
+        if let Ok(api_key) = std::env::var("TICKET_API_KEY") {
+            if !api_key.is_empty() {
+                config.api_key = Some(api_key);
+            }
+        }
+        if let Some(request_logging) = env_flag("TICKET_API_REQUEST_LOG")? {
+            config.request_logging = request_logging;
+        }
+        if let Some(max_body_bytes) = env_number("TICKET_API_MAX_BODY_BYTES")? {
+            config.max_body_bytes = max_body_bytes;
+        }
+        if let Some(seconds) = env_number("TICKET_API_REQUEST_TIMEOUT_SECS")? {
+            config.request_timeout = Duration::from_secs(seconds);
+        }
+        if let Some(millis) = env_number("TICKET_API_LOCK_TIMEOUT_MS")? {
+            config.lock_timeout = Duration::from_millis(millis);
+        }
+        if let Some(seed_demo_data) = env_flag("TICKET_API_SEED")? {
+            config.seed_demo_data = seed_demo_data;
+        }
+        if let Ok(urls) = std::env::var("TICKET_API_WEBHOOK_URLS") {
+            config.webhooks.urls = urls
+                .split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(seconds) = env_number("TICKET_API_PURGE_AFTER")? {
+            let older_than = Duration::from_secs(seconds);
+            let default = PurgePolicy::default();
+            config.purge = Some(PurgePolicy {
+                older_than,
+                interval: default.interval.min(older_than).max(Duration::from_secs(1)),
+            });
+        }
+        if let Some(seconds) = env_number("TICKET_API_TRASH_RETENTION")? {
+            let retention = Duration::from_secs(seconds);
+            let default = TrashPolicy::default();
+            config.trash = Some(TrashPolicy {
+                retention,
+                interval: default.interval.min(retention).max(Duration::from_secs(1)),
+                ..default
+            });
+        }
+        if let Some(per_minute) = env_number("TICKET_API_RATE_LIMIT")? {
+            let mut rate_limit = RateLimit::per_minute(per_minute);
+            if let Some(proxies) = env_list("TICKET_API_TRUSTED_PROXIES")? {
+                rate_limit.trusted_proxies = proxies;
+            }
+            config.rate_limit = Some(rate_limit);
+        }
+        if let Some(max) = env_number("TICKET_API_MAX_EVENT_SUBSCRIBERS")? {
+            config.max_event_subscribers = Some(max);
+        }
+        if let Some(verbose_errors) = env_flag("TICKET_API_VERBOSE_ERRORS")? {
+            config.verbose_errors = verbose_errors;
+        }
+        if let Some(origins) = env_list("TICKET_API_CORS_ORIGINS")? {
+            let mut allow_list = CorsAllowList {
+                origins,
+                ..CorsAllowList::default()
+            };
+            if let Some(methods) = env_list("TICKET_API_CORS_METHODS")? {
+                allow_list.methods = methods;
+            }
+            if let Some(allow_credentials) = env_flag("TICKET_API_CORS_CREDENTIALS")? {
+                allow_list.allow_credentials = allow_credentials;
+            }
+            config.cors = CorsPolicy::AllowList(allow_list);
+        }
+        Ok(config)
+    }
+}
+
+/// Reads a numeric environment variable, `None` when it is unset.
+fn env_number<T: std::str::FromStr>(name: &'static str) -> Result<Option<T>, ConfigError> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| ConfigError::InvalidNumber { name, value }),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Reads a boolean environment variable, `None` when it is unset.
+///
+/// Accepts `true`/`false`, `1`/`0`, `yes`/`no` and `on`/`off`, ignoring case.
+fn env_flag(name: &'static str) -> Result<Option<bool>, ConfigError> {
+    match std::env::var(name) {
+        Ok(value) => match value.trim().to_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(Some(true)),
+            "false" | "0" | "no" | "off" => Ok(Some(false)),
+            _ => Err(ConfigError::InvalidFlag { name, value }),
+        },
+        Err(_) => Ok(None),
+    }
+}
+
+/// Reads a comma-separated environment variable, `None` when it is unset.
+///
+/// Blank entries are skipped and every other one must parse as a `T`.
+fn env_list<T: std::str::FromStr>(name: &'static str) -> Result<Option<Vec<T>>, ConfigError> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry.parse().map_err(|_| ConfigError::InvalidEntry {
+                    name,
+                    value: entry.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Errors that can occur while loading a [`ServerConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// Returned when a numeric environment variable holds something else.
+    #[error("{name} must be a valid number, got {value:?}")]
+    InvalidNumber { name: &'static str, value: String },
+
+    /// Returned when a boolean environment variable holds something else.
+    #[error("{name} must be true or false, got {value:?}")]
+    InvalidFlag { name: &'static str, value: String },
+
+    /// Returned when an entry of a comma-separated environment variable
+    /// can't be parsed, e.g. an origin that isn't a valid header value.
+    #[error("{name} holds an invalid entry {value:?}")]
+    InvalidEntry { name: &'static str, value: String },
+}
+
+/// How long, and how many, idempotency keys of ticket creations are kept.
+///
+/// A key is forgotten once `ttl` has passed since its ticket was created, or
+/// earlier when more than `max_keys` newer keys have arrived since.
+#[derive(Clone, Debug)]
+pub struct IdempotencyPolicy {
+    /// How long a key is remembered
+    pub ttl: Duration,
+    /// Maximum number of keys remembered at once
+    pub max_keys: usize,
+}
+
+impl Default for IdempotencyPolicy {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(24 * 60 * 60),
+            max_keys: 10_000,
+        }
+    }
+}
+
+/// URLs notified of ticket events, and how hard delivery is tried.
+///
+/// Each event is POSTed to every URL. A delivery failing with an error or a
+/// non-2xx status is retried, waiting `initial_backoff` and then twice as
+/// long each time, until `max_attempts` attempts have been made.
+#[derive(Clone, Debug)]
+pub struct WebhookConfig {
+    /// URLs to POST events to; webhooks are off when empty
+    pub urls: Vec<String>,
+    /// Attempts per delivery, the first one included
+    pub max_attempts: u32,
+    /// Wait before the first retry
+    pub initial_backoff: Duration,
+    /// Longest time a single attempt may take
+    pub timeout: Duration,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// How long, and how many, soft-deleted tickets stay in the trash.
+///
+/// Every `interval`, tickets deleted more than `retention` ago are removed
+/// for good. Deleting a ticket while the trash already holds `max_tickets`
+/// removes the longest-deleted ones to make room.
+#[derive(Clone, Debug)]
+pub struct TrashPolicy {
+    /// How long a deleted ticket can still be restored
+    pub retention: Duration,
+    /// Most tickets the trash holds
+    pub max_tickets: usize,
+    /// How often the trash is checked for expired tickets
+    pub interval: Duration,
+}
+
+impl Default for TrashPolicy {
+    fn default() -> Self {
+        Self {
+            retention: Duration::from_secs(7 * 24 * 60 * 60),
+            max_tickets: 1000,
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// When tickets that have been `Done` for a while are removed for good.
+///
+/// Every `interval`, tickets that are `Done` and haven't been updated for
+/// `older_than` are removed from the store. Tickets in any other status are
+/// kept, however old.
+#[derive(Clone, Debug)]
+pub struct PurgePolicy {
+    /// How long a ticket must have gone without updates while `Done`
+    pub older_than: Duration,
+    /// How often the store is checked
+    pub interval: Duration,
+}
+
+impl Default for PurgePolicy {
+    fn default() -> Self {
+        Self {
+            older_than: Duration::from_secs(30 * 24 * 60 * 60),
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Limits how much a single patch may shorten a ticket's description.
+///
+/// A patch reducing the description's length by more than
+/// `max_shrink_percent` is rejected unless the client passes `force=true`.
+#[derive(Clone, Debug)]
+pub struct DescriptionShrinkGuard {
+    /// Largest allowed reduction of the description length, in percent
+    pub max_shrink_percent: u8,
+}
+
+impl Default for DescriptionShrinkGuard {
+    fn default() -> Self {
+        Self {
+            max_shrink_percent: 80,
+        }
+    }
+}
+
+/// How long a ticket may stay in each status before breaching its SLA.
+///
+/// The clock starts when the ticket enters the status. Statuses without a
+/// target, typically `Done`, are not tracked.
+#[derive(Clone, Debug, Default)]
+pub struct SlaPolicy {
+    /// Longest time a ticket may spend in each status
+    pub targets: HashMap<Status, Duration>,
+}
+
+/// Bounds how long a read waits for the store to reach a client's write version.
+///
+/// Reads asking for a `min_version` the store hasn't reached wait up to
+/// `max_wait`, then are served anyway and flagged as stale.
+#[derive(Clone, Debug)]
+pub struct ReadYourWrites {
+    /// Longest time a read waits for the requested version
+    pub max_wait: Duration,
+}
+
+impl Default for ReadYourWrites {
+    fn default() -> Self {
+        Self {
+            max_wait: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Compatibility mode renaming response fields to the legacy scheme.
+///
+/// Legacy names are used when `always` is set, or per request when the
+/// client sends `X-Response-Compat: legacy`.
+#[derive(Clone, Debug)]
+pub struct LegacyFields {
+    /// Apply the legacy names to every response, not just opted-in requests
+    pub always: bool,
+    /// Modern field name to legacy field name
+    pub mapping: HashMap<String, String>,
+}
+
+impl Default for LegacyFields {
+    fn default() -> Self {
+        Self {
+            always: false,
+            mapping: HashMap::from([
+                ("id".to_string(), "ticket_id".to_string()),
+                ("title".to_string(), "ticket_title".to_string()),
+            ]),
+        }
+    }
+}
+
+/// CORS behavior of the router.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CorsPolicy {
+    /// Allow any origin, method and header. Convenient for development, but
+    /// unsafe in production.
+    #[default]
+    Permissive,
+    /// Send no CORS headers, so browsers block cross-origin requests
+    Disabled,
+    /// Allow only the listed origins
+    AllowList(CorsAllowList),
+}
+
+/// The origins, methods and credentials allowed by [`CorsPolicy::AllowList`].
+///
+/// Requests from an origin not on the list get no
+/// `Access-Control-Allow-Origin` header, so browsers block them. Preflight
+/// `OPTIONS` requests are answered by the CORS layer itself, with the request
+/// headers the browser asked for mirrored back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorsAllowList {
+    /// Origins allowed to make cross-origin requests, e.g.
+    /// `https://app.example.com`
+    pub origins: Vec<HeaderValue>,
+    /// Methods allowed in cross-origin requests
+    pub methods: Vec<Method>,
+    /// Whether cookies and `Authorization` headers may be sent along
+    pub allow_credentials: bool,
+}
+
+impl Default for CorsAllowList {
+    fn default() -> Self {
+        Self {
+            origins: Vec::new(),
+            methods: vec![
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+            ],
+            allow_credentials: false,
+        }
+    }
+}
+
+/// Bounds on the custom fields a single ticket may carry.
+#[derive(Clone, Debug)]
+pub struct CustomFieldLimits {
+    /// Maximum number of custom field keys per ticket
+    pub max_keys: usize,
+    /// Maximum serialized size of a single custom field value, in bytes
+    pub max_value_bytes: usize,
+    /// Maximum nesting depth of a custom field value; a scalar has depth 1
+    pub max_depth: usize,
+    /// Maximum number of JSON nodes across all of a ticket's custom field values
+    pub max_nodes: usize,
+}
+
+impl Default for CustomFieldLimits {
+    fn default() -> Self {
+        Self {
+            max_keys: 50,
+            max_value_bytes: 4096,
+            max_depth: 8,
+            max_nodes: 1000,
+        }
+    }
+}
+
+/// Limits how many requests a single client may make.
+///
+/// Each client IP gets a token bucket holding up to `requests` tokens, which
+/// refills at `requests` per `per`. A request takes one token; a client with
+/// none left is answered with `429 Too Many Requests` until one refills. A
+/// client can so burst up to `requests` at once, but not sustain more than
+/// `requests` per `per`.
+///
+/// A client is the connection's peer address. Only when that peer is one of
+/// `trusted_proxies` is the client taken from `X-Forwarded-For` instead, so
+/// other clients can't dodge the limit by sending the header.
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    /// Size of each bucket, and how many tokens refill per `per`
+    pub requests: u32,
+    /// How long a whole bucket takes to refill
+    pub per: Duration,
+    /// Addresses of the reverse proxies whose `X-Forwarded-For` is believed.
+    /// Empty by default, which ignores the header.
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimit {
+    /// Create a limit of `requests` per `per`, trusting no proxy.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        Self {
+            requests,
+            per,
+            trusted_proxies: Vec::new(),
+        }
+    }
+
+    /// Create a limit of `requests` per minute.
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+}
+
+/// Limits how many tickets a single reporter may create within a time window.
+#[derive(Clone, Debug)]
+pub struct CreationThrottle {
+    /// Maximum number of tickets a reporter may create per window
+    pub max_per_window: usize,
+    /// Length of the sliding window
+    pub window: Duration,
+}
+
+impl CreationThrottle {
+    /// Create a throttle allowing `max_per_window` creations per `window`.
+    pub fn new(max_per_window: usize, window: Duration) -> Self {
+        Self {
+            max_per_window,
+            window,
+        }
+    }
+
+    /// Create a throttle allowing `max_per_hour` creations per hour.
+    pub fn per_hour(max_per_hour: usize) -> Self {
+        Self::new(max_per_hour, Duration::from_secs(60 * 60))
+    }
+}
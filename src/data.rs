@@ -1,17 +1,27 @@
 //! Core data types and validation for the ticket management system.
 //!
 //! This module defines the fundamental data structures and their validation rules:
-//! - Ticket components (ID, title, description, status)
+//! - Ticket components (ID, title, description, status, custom fields)
 //! - Request/response DTOs for the API
 //! - Input validation logic
 //! - Serialization/deserialization support
 
-use serde::{Deserialize, Serialize};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::config::{CustomFieldLimits, ServerConfig};
+
 /// Unique identifier for a ticket.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
 pub struct TicketId(pub Uuid);
 
 impl TicketId {
@@ -19,6 +29,72 @@ impl TicketId {
     pub fn new() -> Self {
         Self(Uuid::new_v4())
     }
+
+    /// Parses a ticket ID from a UUID string.
+    ///
+    /// Any UUID version is accepted, since the store's
+    /// [`IdGenerator`](crate::id::IdGenerator) may hand out time-ordered or
+    /// sequential IDs. The nil UUID is rejected: no generator produces it,
+    /// so it can never identify a ticket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ticket_api::TicketId;
+    ///
+    /// let id = TicketId::parse("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    /// assert_eq!(id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    ///
+    /// assert!(TicketId::parse("00000000-0000-0000-0000-000000000000").is_err());
+    /// assert!(TicketId::parse("not-a-uuid").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let uuid = Uuid::parse_str(s).map_err(|_| "Invalid ticket ID format".to_string())?;
+        if uuid.is_nil() {
+            return Err("The nil UUID is not a valid ticket ID".to_string());
+        }
+        Ok(Self(uuid))
+    }
+
+    /// Parses a ticket ID like [`TicketId::parse`], additionally requiring a
+    /// version 4 (random) UUID.
+    ///
+    /// For backends that only ever use [`UuidV4Generator`](crate::id::UuidV4Generator).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ticket_api::TicketId;
+    ///
+    /// assert!(TicketId::parse_v4("67e55044-10b1-426f-9247-bb680e5fe0c8").is_ok());
+    /// // A version 7 UUID
+    /// assert!(TicketId::parse_v4("01890a5d-ac96-774b-bcce-b302099a8057").is_err());
+    /// ```
+    pub fn parse_v4(s: &str) -> Result<Self, String> {
+        let id = Self::parse(s)?;
+        match id.0.get_version_num() {
+            4 => Ok(id),
+            version => Err(format!(
+                "Ticket IDs must be version 4 UUIDs, got version {}",
+                version
+            )),
+        }
+    }
+}
+
+impl FromStr for TicketId {
+    type Err = String;
+
+    /// Parses a ticket ID with [`TicketId::parse`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Default for TicketId {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Display implementation for TicketId.
@@ -28,44 +104,714 @@ impl fmt::Display for TicketId {
     }
 }
 
-/// Title of a ticket. Must be non-empty and <= 100 chars.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Title of a ticket. Must be non-empty and at most
+/// [`ServerConfig::max_title_len`] characters (not bytes), not counting
+/// surrounding whitespace, which is trimmed off.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct TicketTitle(pub String);
 
 impl TicketTitle {
-    /// Validate and create a new TicketTitle.
-    pub fn new(title: String) -> Result<Self, String> {
-        if title.trim().is_empty() {
+    /// Validate and create a new TicketTitle of at most `max_len` characters.
+    ///
+    /// Leading and trailing whitespace is trimmed before the title is
+    /// checked and stored; whitespace inside the title is kept as is.
+    pub fn new(title: String, max_len: usize) -> Result<Self, String> {
+        let title = Self::trim(&title);
+        if title.is_empty() {
             return Err("Title cannot be empty".to_string());
         }
-        if title.len() > 100 {
-            return Err("Title cannot be longer than 100 characters".to_string());
+        if title.chars().count() > max_len {
+            return Err(format!(
+                "Title cannot be longer than {} characters",
+                max_len
+            ));
         }
-        Ok(Self(title))
+        Ok(Self(title.to_string()))
+    }
+
+    /// Trims a title the way [`TicketTitle::new`] does before storing it.
+    fn trim(title: &str) -> &str {
+        title.trim()
+    }
+
+    /// Normalizes `title` for comparing titles: trimmed like stored titles,
+    /// and lowercased, so `" Fix Login "` and `"fix login"` compare equal.
+    pub fn normalize(title: &str) -> String {
+        Self::trim(title).to_lowercase()
+    }
+
+    /// Suggests a valid replacement for a title that is too long.
+    ///
+    /// # Returns
+    /// The title truncated to `max_len` characters, or `None` if the title
+    /// isn't too long or truncation wouldn't make it valid.
+    pub fn suggestion(title: &str, max_len: usize) -> Option<String> {
+        let title = Self::trim(title);
+        if title.chars().count() <= max_len {
+            return None;
+        }
+        let truncated: String = title.chars().take(max_len).collect();
+        let truncated = truncated.trim_end().to_string();
+        Self::new(truncated.clone(), max_len)
+            .ok()
+            .map(|_| truncated)
     }
 }
 
-/// Description of a ticket. Must be <= 1000 chars.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Description of a ticket. Must be at most
+/// [`ServerConfig::max_description_len`] characters (not bytes), and
+/// non-blank when [`ServerConfig::require_description`] is set.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct TicketDescription(pub String);
 
 impl TicketDescription {
-    /// Validate and create a new TicketDescription.
-    pub fn new(description: String) -> Result<Self, String> {
-        if description.len() > 1000 {
-            return Err("Description cannot be longer than 1000 characters".to_string());
+    /// Validate and create a new TicketDescription of at most `max_len`
+    /// characters, which must not be blank if `required` is set.
+    ///
+    /// ```
+    /// use ticket_api::TicketDescription;
+    ///
+    /// assert!(TicketDescription::new("  ".to_string(), 1000, false).is_ok());
+    /// assert_eq!(
+    ///     TicketDescription::new("  ".to_string(), 1000, true),
+    ///     Err("Description cannot be empty".to_string())
+    /// );
+    /// ```
+    pub fn new(description: String, max_len: usize, required: bool) -> Result<Self, String> {
+        if required && description.trim().is_empty() {
+            return Err("Description cannot be empty".to_string());
+        }
+        if description.chars().count() > max_len {
+            return Err(format!(
+                "Description cannot be longer than {} characters",
+                max_len
+            ));
         }
         Ok(Self(description))
     }
 }
 
-/// Represents a ticket in the system.
+/// Name of the person a ticket is assigned to. Must be non-empty and <= 80 characters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TicketAssignee(pub String);
+
+impl TicketAssignee {
+    /// Validate and create a new TicketAssignee.
+    pub fn new(assignee: String) -> Result<Self, String> {
+        if assignee.trim().is_empty() {
+            return Err("Assignee cannot be empty".to_string());
+        }
+        if assignee.chars().count() > 80 {
+            return Err("Assignee cannot be longer than 80 characters".to_string());
+        }
+        Ok(Self(assignee))
+    }
+}
+
+/// The text of a comment. Must be non-empty and <= 500 characters.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommentBody(pub String);
+
+impl CommentBody {
+    /// Validate and create a new CommentBody.
+    pub fn new(body: String) -> Result<Self, String> {
+        if body.trim().is_empty() {
+            return Err("Comment cannot be empty".to_string());
+        }
+        if body.chars().count() > 500 {
+            return Err("Comment cannot be longer than 500 characters".to_string());
+        }
+        Ok(Self(body))
+    }
+}
+
+/// A label on a ticket. Must be non-empty and <= 30 characters; stored lowercased.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TicketTag(pub String);
+
+impl TicketTag {
+    /// Validate and create a new TicketTag, lowercasing it.
+    pub fn new(tag: String) -> Result<Self, String> {
+        let tag = Self::normalize(&tag);
+        if tag.is_empty() {
+            return Err("Tag cannot be empty".to_string());
+        }
+        if tag.chars().count() > 30 {
+            return Err("Tag cannot be longer than 30 characters".to_string());
+        }
+        Ok(Self(tag))
+    }
+
+    /// Normalizes `tag` the way tags are stored, so lookups match regardless of case.
+    pub fn normalize(tag: &str) -> String {
+        tag.trim().to_lowercase()
+    }
+}
+
+/// Deserializes a field that distinguishes an explicit `null` from absence.
+///
+/// Serde calls a field's `deserialize_with` function only when the key is
+/// present, so wrapping whatever is there in `Some` turns `null` into
+/// `Some(None)` and a value into `Some(Some(value))`. Paired with
+/// `#[serde(default)]`, an absent key falls back to `None`.
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Free-form metadata attached to a ticket, keyed by field name.
+pub type CustomFields = BTreeMap<String, Value>;
+
+/// Validates a ticket's custom fields against the configured limits.
+///
+/// Keys must be non-empty, the number of keys may not exceed
+/// `limits.max_keys`, and each value's JSON encoding may not exceed
+/// `limits.max_value_bytes`. Values may nest at most `limits.max_depth`
+/// levels deep and hold at most `limits.max_nodes` JSON nodes in total.
+pub fn validate_custom_fields(
+    fields: &CustomFields,
+    limits: &CustomFieldLimits,
+) -> Result<(), String> {
+    if fields.len() > limits.max_keys {
+        return Err(format!(
+            "Cannot have more than {} custom fields",
+            limits.max_keys
+        ));
+    }
+    let mut nodes = 0;
+    for (key, value) in fields {
+        if key.trim().is_empty() {
+            return Err("Custom field names cannot be empty".to_string());
+        }
+        check_value_shape(key, value, 1, &mut nodes, limits)?;
+        let size = serde_json::to_vec(value).map(|v| v.len()).unwrap_or(0);
+        if size > limits.max_value_bytes {
+            return Err(format!(
+                "Custom field '{}' cannot be larger than {} bytes",
+                key, limits.max_value_bytes
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Walks a custom field value, counting its nodes into `nodes`.
+///
+/// Stops at the first node deeper than `limits.max_depth` or past
+/// `limits.max_nodes`, so hostile input costs no more than the limits allow.
+fn check_value_shape(
+    key: &str,
+    value: &Value,
+    depth: usize,
+    nodes: &mut usize,
+    limits: &CustomFieldLimits,
+) -> Result<(), String> {
+    *nodes += 1;
+    if depth > limits.max_depth {
+        return Err(format!(
+            "Custom field '{}' cannot be nested deeper than {} levels",
+            key, limits.max_depth
+        ));
+    }
+    if *nodes > limits.max_nodes {
+        return Err(format!(
+            "Custom fields cannot hold more than {} values in total",
+            limits.max_nodes
+        ));
+    }
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .try_for_each(|item| check_value_shape(key, item, depth + 1, nodes, limits)),
+        Value::Object(fields) => fields
+            .values()
+            .try_for_each(|field| check_value_shape(key, field, depth + 1, nodes, limits)),
+        _ => Ok(()),
+    }
+}
+
+/// Represents a ticket in the system.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Ticket {
     pub id: TicketId,
     pub title: TicketTitle,
     pub description: TicketDescription,
     pub status: Status,
+    pub priority: Priority,
+    pub sync_state: SyncState,
+    pub reporter: Option<String>,
+    pub category: Option<String>,
+    pub kind: Option<String>,
+    pub assignee: Option<String>,
+    #[schema(value_type = Object)]
+    pub custom_fields: CustomFields,
+    /// Lowercased labels, without duplicates, in the order they were added
+    pub tags: Vec<String>,
+    /// Notes left by collaborators, oldest first; never edited or removed
+    pub comments: Vec<Comment>,
+    /// Links to other tickets
+    pub relationships: Relationships,
+    pub external_ref: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// When the ticket last changed; equal to `created_at` until then
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the ticket entered its current status
+    pub status_changed_at: DateTime<Utc>,
+    /// When the ticket is due, if it has a deadline
+    pub due_date: Option<DateTime<Utc>>,
+    /// Story points, if estimated
+    #[serde(default)]
+    pub estimate: Option<u16>,
+    /// Position in the backlog; lower ranks come first. New tickets are
+    /// ranked by creation time, so they join the end.
+    #[serde(default)]
+    pub rank: f64,
+    /// Starts at 1 and increases by one with every change to the ticket
+    pub version: u64,
+    /// When the ticket was soft-deleted; deleted tickets are hidden from
+    /// reads until restored
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl Ticket {
+    /// Returns true if the ticket's due date has passed before `now` while
+    /// the ticket is still open, that is neither `Done` nor `Cancelled`.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.due_date.is_some_and(|due_date| due_date < now)
+            && !matches!(self.status, Status::Done | Status::Cancelled)
+    }
+
+    /// Returns true if the ticket is soft-deleted.
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Marks the ticket as changed: bumps the version and stamps `updated_at`.
+    pub fn touch(&mut self) {
+        self.version += 1;
+        self.updated_at = Utc::now();
+    }
+
+    /// Moves the ticket to `status`, keeping `completed_at` and
+    /// `status_changed_at` in step.
+    ///
+    /// Entering `Done` stamps the completion time, leaving it clears the
+    /// stamp, and staying in `Done` keeps the original one.
+    pub fn set_status(&mut self, status: Status) {
+        if status != self.status {
+            self.status_changed_at = Utc::now();
+        }
+        if status == Status::Done {
+            if self.status != Status::Done || self.completed_at.is_none() {
+                self.completed_at = Some(Utc::now());
+            }
+        } else {
+            self.completed_at = None;
+        }
+        self.status = status;
+    }
+
+    /// Adds `tag` unless the ticket already has it.
+    ///
+    /// # Returns
+    /// `true` if the tag was added, `false` if it was already present.
+    pub fn add_tag(&mut self, tag: TicketTag) -> bool {
+        if self.tags.contains(&tag.0) {
+            return false;
+        }
+        self.tags.push(tag.0);
+        true
+    }
+
+    /// Compares two tickets field by field, ignoring their IDs.
+    ///
+    /// # Returns
+    /// One [`FieldDiff`] per field whose value differs, ordered by field name.
+    pub fn diff(&self, other: &Ticket) -> Vec<FieldDiff> {
+        let a = serde_json::to_value(self).unwrap_or(Value::Null);
+        let b = serde_json::to_value(other).unwrap_or(Value::Null);
+        let (Value::Object(a), Value::Object(b)) = (a, b) else {
+            return Vec::new();
+        };
+
+        a.into_iter()
+            .filter(|(field, _)| field != "id")
+            .filter_map(|(field, a_value)| {
+                let b_value = b.get(&field).cloned().unwrap_or(Value::Null);
+                (a_value != b_value).then_some(FieldDiff {
+                    field,
+                    a: a_value,
+                    b: b_value,
+                })
+            })
+            .collect()
+    }
+}
+
+/// How a ticket is linked to other tickets.
+///
+/// Only the forward links are stored: a ticket lists the tickets it blocks,
+/// and "blocked by" is the same link seen from the other side.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Relationships {
+    /// Tickets that can't proceed until this one is done, in the order
+    /// they were linked
+    pub blocks: Vec<TicketId>,
+    /// The ticket this one is part of, if any
+    pub parent: Option<TicketId>,
+}
+
+/// A note left on a ticket.
+///
+/// Comments are append-only: once added they are never edited or removed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Comment {
+    /// Unique identifier of the comment
+    pub id: Uuid,
+    /// Who wrote the comment
+    pub author: String,
+    /// The comment's text
+    pub body: String,
+    /// When the comment was added
+    pub created_at: DateTime<Utc>,
+}
+
+/// A validated comment, ready to be added to a ticket.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommentDraft {
+    pub author: String,
+    pub body: CommentBody,
+    /// When the comment was written, `None` to stamp it with the current time
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Characters of context a [`CommentMatch`] snippet keeps on each side of
+/// the match.
+pub const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// A comment matching a search, see `GET /tickets/search-all`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CommentMatch {
+    /// ID of the matching comment
+    pub comment_id: Uuid,
+    /// Who wrote the comment
+    pub author: String,
+    /// When the comment was added
+    pub created_at: DateTime<Utc>,
+    /// The text around the first match, the match wrapped in `<mark>` and
+    /// `…` where the body was cut
+    pub snippet: String,
+}
+
+impl CommentMatch {
+    /// Matches `comment` against `query`, ignoring case.
+    ///
+    /// ```
+    /// use ticket_api::{Comment, CommentMatch};
+    ///
+    /// let comment = Comment {
+    ///     id: uuid::Uuid::new_v4(),
+    ///     author: "ana".to_string(),
+    ///     body: "Seen again after the Login change".to_string(),
+    ///     created_at: chrono::Utc::now(),
+    /// };
+    /// let found = CommentMatch::find(&comment, "login").unwrap();
+    /// assert_eq!(found.snippet, "Seen again after the <mark>Login</mark> change");
+    /// assert!(CommentMatch::find(&comment, "logout").is_none());
+    /// ```
+    ///
+    /// # Returns
+    /// The match, with a snippet around the first occurrence of `query`, or
+    /// `None` if the body doesn't contain it
+    pub fn find(comment: &Comment, query: &str) -> Option<Self> {
+        let body: Vec<char> = comment.body.chars().collect();
+        let (start, end) = find_ignoring_case(&body, query)?;
+        let from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+        let to = (end + SNIPPET_CONTEXT_CHARS).min(body.len());
+        let text = |range: std::ops::Range<usize>| body[range].iter().collect::<String>();
+        let snippet = format!(
+            "{}{}<mark>{}</mark>{}{}",
+            if from > 0 { "…" } else { "" },
+            text(from..start),
+            text(start..end),
+            text(end..to),
+            if to < body.len() { "…" } else { "" }
+        );
+
+        Some(Self {
+            comment_id: comment.id,
+            author: comment.author.clone(),
+            created_at: comment.created_at,
+            snippet,
+        })
+    }
+}
+
+/// Finds `query` in `text`, comparing characters by their lowercase forms.
+///
+/// # Returns
+/// The range of characters of the first match, or `None` if there is none
+/// or `query` is empty.
+fn find_ignoring_case(text: &[char], query: &str) -> Option<(usize, usize)> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return None;
+    }
+    (0..text.len()).find_map(|start| {
+        let mut matched = 0;
+        for (offset, c) in text[start..].iter().enumerate() {
+            for lower in c.to_lowercase() {
+                if query.get(matched) != Some(&lower) {
+                    return None;
+                }
+                matched += 1;
+            }
+            if matched == query.len() {
+                return Some((start, start + offset + 1));
+            }
+        }
+        None
+    })
+}
+
+/// A change to the store, published to event stream subscribers.
+///
+/// Serialized with a `type` tag, e.g. `{"type":"Created","id":"..."}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type")]
+pub enum TicketEvent {
+    /// A ticket was created
+    Created { id: TicketId },
+    /// A ticket's fields changed
+    Updated { id: TicketId },
+    /// A ticket was removed
+    Deleted { id: TicketId },
+    /// A soft-deleted ticket was brought back
+    Restored { id: TicketId },
+}
+
+impl TicketEvent {
+    /// The ticket the event is about.
+    pub fn id(&self) -> &TicketId {
+        match self {
+            TicketEvent::Created { id }
+            | TicketEvent::Updated { id }
+            | TicketEvent::Deleted { id }
+            | TicketEvent::Restored { id } => id,
+        }
+    }
+}
+
+/// Ticket counts for one assignee, see `GET /tickets/assignees`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AssigneeSummary {
+    /// The assignee, `null` for the bucket of unassigned tickets
+    pub assignee: Option<String>,
+    /// Number of live tickets in the bucket
+    pub total: usize,
+    /// Count per status; every status is present
+    #[schema(value_type = Object)]
+    pub by_status: BTreeMap<Status, usize>,
+}
+
+impl AssigneeSummary {
+    /// Starts the summary of `assignee`, with every status at zero.
+    pub fn new(assignee: Option<String>) -> Self {
+        Self {
+            assignee,
+            total: 0,
+            by_status: Status::ALL.into_iter().map(|status| (status, 0)).collect(),
+        }
+    }
+
+    /// Counts one more ticket in `status`.
+    pub fn count(&mut self, status: Status) {
+        self.total += 1;
+        *self.by_status.entry(status).or_insert(0) += 1;
+    }
+}
+
+/// Lead time statistics over the tickets completed in a window.
+///
+/// Lead time is the time from a ticket's creation to its completion. All
+/// figures are in seconds and `None` when no ticket was completed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LeadTimeStats {
+    /// Number of tickets completed in the window
+    pub count: usize,
+    /// Mean lead time
+    pub average_seconds: Option<f64>,
+    /// Median lead time
+    pub median_seconds: Option<f64>,
+    /// 90th percentile lead time, by nearest rank
+    pub p90_seconds: Option<f64>,
+}
+
+impl LeadTimeStats {
+    /// Computes the statistics from individual lead times, in seconds.
+    pub fn from_seconds(mut lead_times: Vec<f64>) -> Self {
+        lead_times.sort_by(f64::total_cmp);
+        let count = lead_times.len();
+        if count == 0 {
+            return Self {
+                count,
+                average_seconds: None,
+                median_seconds: None,
+                p90_seconds: None,
+            };
+        }
+
+        let average = lead_times.iter().sum::<f64>() / count as f64;
+        let median = if count.is_multiple_of(2) {
+            (lead_times[count / 2 - 1] + lead_times[count / 2]) / 2.0
+        } else {
+            lead_times[count / 2]
+        };
+        let p90_rank = (count * 9).div_ceil(10);
+        Self {
+            count,
+            average_seconds: Some(average),
+            median_seconds: Some(median),
+            p90_seconds: Some(lead_times[p90_rank - 1]),
+        }
+    }
+}
+
+/// Time a ticket has spent in one status, see `GET /tickets/:id/time-in-status`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TimeInStatus {
+    /// The status
+    pub status: Status,
+    /// Time spent in the status over every stay, in seconds
+    pub seconds: f64,
+}
+
+/// One field change in a ticket's history.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    /// Name of the changed field
+    pub field: String,
+    /// The field's value before the change
+    #[schema(value_type = Value)]
+    pub old: Value,
+    /// The field's value after the change
+    #[schema(value_type = Value)]
+    pub new: Value,
+    /// When the change happened
+    pub at: DateTime<Utc>,
+    /// Why the change was made, when the client said so
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// One entry of the store's change log.
+///
+/// Serializes flat, e.g.
+/// `{"sequence": 7, "type": "Updated", "id": "...", "fields": ["title"], "at": "..."}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChangeRecord {
+    /// The store's write version right after the change
+    pub sequence: u64,
+    /// What happened to which ticket
+    #[serde(flatten)]
+    pub event: TicketEvent,
+    /// Names of the fields an update changed; empty for creates and deletes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// When the change happened
+    pub at: DateTime<Utc>,
+}
+
+/// Where a ticket stands against the SLA target of its current status.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SlaStatus {
+    /// When the ticket must leave its current status
+    pub deadline: DateTime<Utc>,
+    /// Seconds left until the deadline, negative once it has passed
+    pub remaining_seconds: i64,
+    /// Whether the deadline has passed
+    pub breached: bool,
+}
+
+impl SlaStatus {
+    /// Computes the SLA status of a ticket that entered its status at
+    /// `entered_at` and must leave it within `target`.
+    pub fn compute(entered_at: DateTime<Utc>, target: Duration, now: DateTime<Utc>) -> Self {
+        let deadline = entered_at + target;
+        let remaining_seconds = (deadline - now).num_seconds();
+        Self {
+            deadline,
+            remaining_seconds,
+            breached: now > deadline,
+        }
+    }
+}
+
+/// A single field that differs between two tickets.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldDiff {
+    /// Name of the differing field
+    pub field: String,
+    /// The field's value on the first ticket
+    #[schema(value_type = Value)]
+    pub a: Value,
+    /// The field's value on the second ticket
+    #[schema(value_type = Value)]
+    pub b: Value,
+}
+
+/// A self-contained capture of the store's state.
+///
+/// Besides the tickets it carries the store's write version, so a store
+/// loaded from a fixture continues numbering writes where the captured
+/// store left off, along with the state derived from past writes: every
+/// ticket's history, the change log and the idempotency keys. Fixtures
+/// captured before those were added still load, with that state empty.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Fixture {
+    /// The store's write version at capture time
+    pub store_version: u64,
+    /// Every ticket, ordered by creation time, then ID
+    pub tickets: Vec<Ticket>,
+    /// Each ticket's audit history, oldest entry first
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub history: BTreeMap<TicketId, Vec<AuditEntry>>,
+    /// The retained change log, oldest first
+    #[serde(default)]
+    pub changes: Vec<ChangeRecord>,
+    /// Sequence of the newest change no longer in `changes`; the store
+    /// version when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes_floor: Option<u64>,
+    /// Ticket creations remembered by idempotency key, oldest first
+    #[serde(default)]
+    pub idempotency_keys: Vec<FixtureIdempotencyKey>,
+}
+
+/// A ticket creation remembered by idempotency key, as captured in a [`Fixture`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FixtureIdempotencyKey {
+    /// The `Idempotency-Key` of the creation
+    pub key: String,
+    /// A hash of the creation request, to tell retries from reuse
+    pub fingerprint: u64,
+    /// How long ago the key was first used, in seconds
+    pub age_seconds: u64,
+    /// The ticket as created
+    pub ticket: Ticket,
+}
+
+/// A backup of every ticket, as served by `GET /admin/snapshot` and accepted
+/// by `POST /admin/restore`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Snapshot {
+    /// Every ticket, soft-deleted ones included
+    pub tickets: Vec<Ticket>,
 }
 
 /// Draft for creating a new ticket.
@@ -73,6 +819,196 @@ pub struct Ticket {
 pub struct TicketDraft {
     pub title: TicketTitle,
     pub description: TicketDescription,
+    pub priority: Priority,
+    pub reporter: Option<String>,
+    pub category: Option<String>,
+    pub kind: Option<String>,
+    pub assignee: Option<TicketAssignee>,
+    pub custom_fields: CustomFields,
+    pub tags: Vec<TicketTag>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub estimate: Option<u16>,
+}
+
+impl TicketDraft {
+    /// Starts building a draft, validated in one step by
+    /// [`TicketDraftBuilder::build`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ticket_api::{Priority, TicketDraft};
+    ///
+    /// let draft = TicketDraft::builder()
+    ///     .title("  Fix login timeout ")
+    ///     .description("The login form spins forever on slow networks")
+    ///     .priority(Priority::High)
+    ///     .tag("auth")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(draft.title.0, "Fix login timeout");
+    /// assert_eq!(draft.priority, Priority::High);
+    /// ```
+    ///
+    /// Every invalid field is reported, not just the first:
+    ///
+    /// ```
+    /// use ticket_api::TicketDraft;
+    ///
+    /// let errors = TicketDraft::builder()
+    ///     .title("   ")
+    ///     .description(&"x".repeat(2000))
+    ///     .tag("")
+    ///     .build()
+    ///     .unwrap_err();
+    /// assert_eq!(errors.len(), 3);
+    /// assert!(errors[0].starts_with("title: "));
+    /// assert!(errors[1].starts_with("description: "));
+    /// assert!(errors[2].starts_with("tags: "));
+    /// ```
+    pub fn builder() -> TicketDraftBuilder {
+        TicketDraftBuilder::default()
+    }
+}
+
+/// Builds a [`TicketDraft`] from plain values, see [`TicketDraft::builder`].
+///
+/// Validation is the same as for `POST /tickets` and uses the default
+/// length limits unless [`limits`](Self::limits) says otherwise.
+#[derive(Debug)]
+pub struct TicketDraftBuilder {
+    request: CreateTicketRequest,
+    max_title_len: usize,
+    max_description_len: usize,
+    require_description: bool,
+    estimate_range: RangeInclusive<u16>,
+}
+
+impl Default for TicketDraftBuilder {
+    fn default() -> Self {
+        let config = ServerConfig::default();
+        Self {
+            request: CreateTicketRequest {
+                title: String::new(),
+                description: String::new(),
+                priority: None,
+                reporter: None,
+                category: None,
+                kind: None,
+                assignee: None,
+                custom_fields: CustomFields::new(),
+                tags: Vec::new(),
+                due_date: None,
+                estimate: None,
+            },
+            max_title_len: config.max_title_len,
+            max_description_len: config.max_description_len,
+            require_description: config.require_description,
+            estimate_range: config.estimate_range,
+        }
+    }
+}
+
+impl TicketDraftBuilder {
+    /// Sets the title; required.
+    pub fn title(mut self, title: &str) -> Self {
+        self.request.title = title.to_string();
+        self
+    }
+
+    /// Sets the description, empty by default.
+    pub fn description(mut self, description: &str) -> Self {
+        self.request.description = description.to_string();
+        self
+    }
+
+    /// Sets the priority, `Medium` by default.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.request.priority = Some(priority);
+        self
+    }
+
+    /// Sets who files the ticket.
+    pub fn reporter(mut self, reporter: &str) -> Self {
+        self.request.reporter = Some(reporter.to_string());
+        self
+    }
+
+    /// Sets the category, e.g. "bug".
+    pub fn category(mut self, category: &str) -> Self {
+        self.request.category = Some(category.to_string());
+        self
+    }
+
+    /// Sets the ticket kind, which decides the required custom fields.
+    pub fn kind(mut self, kind: &str) -> Self {
+        self.request.kind = Some(kind.to_string());
+        self
+    }
+
+    /// Sets who the ticket is assigned to.
+    pub fn assignee(mut self, assignee: &str) -> Self {
+        self.request.assignee = Some(assignee.to_string());
+        self
+    }
+
+    /// Sets one custom field.
+    pub fn custom_field(mut self, key: &str, value: Value) -> Self {
+        self.request.custom_fields.insert(key.to_string(), value);
+        self
+    }
+
+    /// Adds a tag; duplicates are dropped.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.request.tags.push(tag.to_string());
+        self
+    }
+
+    /// Sets the deadline, which must lie in the future.
+    pub fn due_date(mut self, due_date: DateTime<Utc>) -> Self {
+        self.request.due_date = Some(due_date);
+        self
+    }
+
+    /// Sets the story point estimate.
+    pub fn estimate(mut self, estimate: u16) -> Self {
+        self.request.estimate = Some(estimate);
+        self
+    }
+
+    /// Validates against the title and description limits, whether a
+    /// description is required, and the estimate range of `config`.
+    pub fn limits(mut self, config: &ServerConfig) -> Self {
+        self.max_title_len = config.max_title_len;
+        self.max_description_len = config.max_description_len;
+        self.require_description = config.require_description;
+        self.estimate_range = config.estimate_range.clone();
+        self
+    }
+
+    /// Validates every field and builds the draft.
+    ///
+    /// Store policies, such as custom field limits and required fields, are
+    /// checked when the draft is added to a store.
+    ///
+    /// # Returns
+    /// * `Ok(TicketDraft)` - The validated draft
+    /// * `Err(Vec<String>)` - One `"field: message"` entry per invalid field
+    pub fn build(self) -> Result<TicketDraft, Vec<String>> {
+        let config = ServerConfig {
+            max_title_len: self.max_title_len,
+            max_description_len: self.max_description_len,
+            require_description: self.require_description,
+            estimate_range: self.estimate_range,
+            ..ServerConfig::default()
+        };
+        self.request.validate(&config).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|error| format!("{}: {}", error.field, error.message))
+                .collect()
+        })
+    }
 }
 
 /// Status of a ticket.
@@ -82,7 +1018,12 @@ pub struct TicketDraft {
 /// - `ToDo`: Work hasn't started
 /// - `InProgress`: Work is currently being done
 /// - `Done`: Work is completed
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// - `Cancelled`: The ticket was dropped without being done
+///
+/// Serializes as the variant name, e.g. `"InProgress"`, but deserializes
+/// leniently via [`FromStr`], so `"in_progress"` and `"IN-PROGRESS"` work too.
+/// Statuses order by workflow: `ToDo < InProgress < Done < Cancelled`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ToSchema)]
 pub enum Status {
     /// Initial state for new tickets
     ToDo,
@@ -90,6 +1031,8 @@ pub enum Status {
     InProgress,
     /// Work is completed
     Done,
+    /// The ticket was dropped without being done
+    Cancelled,
 }
 
 /// Display implementation for Status.
@@ -99,40 +1042,1062 @@ impl fmt::Display for Status {
             Status::ToDo => write!(f, "To Do"),
             Status::InProgress => write!(f, "In Progress"),
             Status::Done => write!(f, "Done"),
+            Status::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
 
+impl Status {
+    /// Every status, in workflow order.
+    pub const ALL: [Status; 4] = [
+        Status::ToDo,
+        Status::InProgress,
+        Status::Done,
+        Status::Cancelled,
+    ];
+
+    /// Returns the serialized name of this status, e.g. `"InProgress"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::ToDo => "ToDo",
+            Status::InProgress => "InProgress",
+            Status::Done => "Done",
+            Status::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Returns true if a ticket may move from this status to `next`.
+    ///
+    /// The allowed transitions are `ToDo -> InProgress`, `InProgress -> Done`,
+    /// `InProgress -> ToDo` and `Done -> InProgress` (reopening). Any status
+    /// but `Done` may move to `Cancelled`, which only leads back to `ToDo`.
+    pub fn can_transition_to(&self, next: Status) -> bool {
+        matches!(
+            (self, next),
+            (Status::ToDo, Status::InProgress)
+                | (Status::InProgress, Status::Done)
+                | (Status::InProgress, Status::ToDo)
+                | (Status::Done, Status::InProgress)
+                | (Status::ToDo, Status::Cancelled)
+                | (Status::InProgress, Status::Cancelled)
+                | (Status::Cancelled, Status::ToDo)
+        )
+    }
+
+    /// Returns every status a ticket may move to from this one, in workflow order.
+    pub fn allowed_transitions(&self) -> Vec<Status> {
+        Status::ALL
+            .into_iter()
+            .filter(|next| self.can_transition_to(*next))
+            .collect()
+    }
+
+    /// Finds the status whose name is closest to `input` by edit distance.
+    ///
+    /// Used to suggest a fix when a client sends an unknown status.
+    pub fn closest(input: &str) -> Status {
+        let input = input.to_lowercase();
+        Status::ALL
+            .into_iter()
+            .min_by_key(|status| edit_distance(&input, &status.name().to_lowercase()))
+            .unwrap_or(Status::ToDo)
+    }
+}
+
+/// Parses a status from its name, ignoring case and separators.
+///
+/// `"InProgress"`, `"in_progress"`, `"in-progress"`, `"In Progress"` and
+/// `"inprogress"` all parse as [`Status::InProgress`].
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = status_key(s);
+        Status::ALL
+            .into_iter()
+            .find(|status| status_key(status.name()) == key)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Status::ALL.iter().map(Status::name).collect();
+                format!(
+                    "Unknown status '{}', expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for Status {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Lowercases a status name and drops `_`, `-` and spaces, so spellings of
+/// the same status compare equal.
+fn status_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Priority of a ticket.
+///
+/// New tickets default to `Medium` when no priority is given.
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Priority {
+    /// Can wait
+    Low,
+    /// Normal priority
+    #[default]
+    Medium,
+    /// Should be picked up soon
+    High,
+    /// Needs immediate attention
+    Critical,
+}
+
+/// Display implementation for Priority.
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Priority::Low => write!(f, "Low"),
+            Priority::Medium => write!(f, "Medium"),
+            Priority::High => write!(f, "High"),
+            Priority::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+impl Priority {
+    /// Every priority, from lowest to highest.
+    pub const ALL: [Priority; 4] = [
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+        Priority::Critical,
+    ];
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    /// Parses a priority name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Priority::ALL
+            .into_iter()
+            .find(|priority| priority.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown priority '{}', expected one of Low, Medium, High, Critical",
+                    s
+                )
+            })
+    }
+}
+
+/// Synchronization state of a ticket with respect to offline clients.
+///
+/// Every mutation marks a ticket as `Pending`; clients flip it back to
+/// `Synced` once they have pulled the change.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum SyncState {
+    /// The ticket has been pulled by the sync client
+    Synced,
+    /// The ticket has changes the sync client hasn't seen yet
+    Pending,
+}
+
+/// A field tickets can be listed by.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum SortField {
+    CreatedAt,
+    UpdatedAt,
+    /// Compared ignoring case
+    Title,
+    /// Compared in workflow order, see [`Status`]
+    Status,
+    /// Backlog position, see [`Ticket::rank`]
+    Rank,
+}
+
+impl FromStr for SortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "created_at" => Ok(SortField::CreatedAt),
+            "updated_at" => Ok(SortField::UpdatedAt),
+            "title" => Ok(SortField::Title),
+            "status" => Ok(SortField::Status),
+            "rank" => Ok(SortField::Rank),
+            _ => Err(format!(
+                "Unknown sort field '{}', expected one of created_at, updated_at, title, status, rank",
+                s
+            )),
+        }
+    }
+}
+
+/// Direction of a sorted listing.
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            _ => Err(format!("Unknown sort order '{}', expected asc or desc", s)),
+        }
+    }
+}
+
+/// How to order a listing of tickets.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct SortSpec {
+    pub field: SortField,
+    pub order: SortOrder,
+}
+
+impl SortSpec {
+    /// Sorts `tickets` by the spec's field and order.
+    ///
+    /// The sort is stable, so tickets that compare equal keep their
+    /// relative order, in either direction.
+    pub fn sort(&self, tickets: &mut [Ticket]) {
+        tickets.sort_by(|a, b| {
+            let ordering = match self.field {
+                SortField::CreatedAt => a.created_at.cmp(&b.created_at),
+                SortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+                SortField::Title => a.title.0.to_lowercase().cmp(&b.title.0.to_lowercase()),
+                SortField::Status => a.status.cmp(&b.status),
+                SortField::Rank => a.rank.total_cmp(&b.rank),
+            };
+            match self.order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+    }
+}
+
+/// The predicates a ticket listing is narrowed by.
+///
+/// Every predicate that is set must hold for a ticket to match; unset ones
+/// match everything, so the default filter matches every live ticket.
+///
+/// ```
+/// use ticket_api::{Priority, Status, TicketDraft, TicketFilter, TicketStore};
+///
+/// # tokio_test();
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn tokio_test() {
+/// let store = TicketStore::new();
+/// let tickets = [
+///     ("alice", Priority::High, Status::InProgress),
+///     ("alice", Priority::High, Status::ToDo),
+///     ("alice", Priority::Low, Status::InProgress),
+///     ("bob", Priority::High, Status::InProgress),
+/// ];
+/// for (assignee, priority, status) in tickets {
+///     let draft = TicketDraft::builder()
+///         .title("Fix login")
+///         .assignee(assignee)
+///         .priority(priority)
+///         .build()
+///         .unwrap();
+///     let ticket = store.add_ticket(draft).await.unwrap();
+///     store.transition_status(&ticket.id, status, None).await.unwrap();
+/// }
+///
+/// let filter = TicketFilter {
+///     status: Some(Status::InProgress),
+///     priority: Some(Priority::High),
+///     assignee: Some("alice".to_string()),
+///     ..TicketFilter::default()
+/// };
+/// let matching = store.query(&filter).await;
+/// assert_eq!(matching.len(), 1);
+/// assert_eq!(matching[0].assignee.as_deref(), Some("alice"));
+/// assert_eq!(matching[0].priority, Priority::High);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TicketFilter {
+    /// Only tickets in this status
+    pub status: Option<Status>,
+    /// Only tickets with this priority
+    pub priority: Option<Priority>,
+    /// Only tickets assigned to exactly this assignee
+    pub assignee: Option<String>,
+    /// Only tickets with this tag, matched ignoring case
+    pub tag: Option<String>,
+    /// Only tickets overdue at this point in time, see [`Ticket::is_overdue`]
+    pub overdue_at: Option<DateTime<Utc>>,
+    /// Whether soft-deleted tickets match too
+    pub include_deleted: bool,
+}
+
+impl TicketFilter {
+    /// Returns true if `ticket` satisfies every predicate of the filter.
+    pub fn matches(&self, ticket: &Ticket) -> bool {
+        (self.include_deleted || !ticket.is_deleted())
+            && self.status.is_none_or(|status| ticket.status == status)
+            && self
+                .priority
+                .is_none_or(|priority| ticket.priority == priority)
+            && self
+                .assignee
+                .as_deref()
+                .is_none_or(|assignee| ticket.assignee.as_deref() == Some(assignee))
+            && self
+                .tag
+                .as_deref()
+                .is_none_or(|tag| ticket.tags.contains(&TicketTag::normalize(tag)))
+            && self.overdue_at.is_none_or(|now| ticket.is_overdue(now))
+    }
+
+    /// Returns true if the filter only decides whether soft-deleted tickets
+    /// are included, and narrows the listing by nothing else.
+    pub fn is_unfiltered(&self) -> bool {
+        *self
+            == TicketFilter {
+                include_deleted: self.include_deleted,
+                ..TicketFilter::default()
+            }
+    }
+}
+
+/// Position in a cursor-paginated ticket listing: the last ticket seen.
+///
+/// Listings run in creation order, then ID, so new tickets always land
+/// after every existing cursor and paging never skips or repeats a ticket.
+/// Clients see the cursor only as an opaque URL-safe base64 string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    /// Creation time of the last ticket seen
+    pub created_at: DateTime<Utc>,
+    /// ID of the last ticket seen
+    pub id: TicketId,
+}
+
+impl Cursor {
+    /// The cursor positioned right after `ticket`.
+    pub fn after(ticket: &Ticket) -> Self {
+        Self {
+            created_at: ticket.created_at,
+            id: ticket.id.clone(),
+        }
+    }
+
+    /// Returns true if `ticket` comes after the cursor.
+    pub fn precedes(&self, ticket: &Ticket) -> bool {
+        (ticket.created_at, &ticket.id) > (self.created_at, &self.id)
+    }
+
+    /// Encodes the cursor for a response.
+    pub fn encode(&self) -> String {
+        let raw = format!("{}|{}", self.created_at.to_rfc3339(), self.id);
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = String;
+
+    /// Decodes a cursor made by [`Cursor::encode`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || "Invalid cursor".to_string();
+        let raw = URL_SAFE_NO_PAD.decode(s).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let (created_at, id) = raw.split_once('|').ok_or_else(invalid)?;
+        Ok(Self {
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            id: TicketId::parse(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
 /// Request payload for creating a ticket.
 /// Request payload for creating a new ticket.
 ///
-/// All fields are required and will be validated:
-/// - `title`: Must be non-empty and <= 100 characters
-/// - `description`: Must be <= 1000 characters
-#[derive(Debug, Serialize, Deserialize)]
+/// Title and description are required and will be validated:
+/// - `title`: Must be non-empty and at most `max_title_len` characters (100 by default)
+/// - `description`: Must be at most `max_description_len` characters (1000 by default),
+///   and non-blank when `require_description` is set, unless the category
+///   has a description template to fill it
+/// - `assignee`: When present, must be non-empty and <= 80 characters
+/// - `tags`: Each must be non-empty and <= 30 characters
+/// - `estimate`: When present, must lie within `estimate_range` (0-100 by default)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateTicketRequest {
     /// The ticket's title
     pub title: String,
     /// The ticket's description
     pub description: String,
+    /// Optional priority, `Medium` when omitted
+    pub priority: Option<Priority>,
+    /// Optional name of the person filing the ticket
+    pub reporter: Option<String>,
+    /// Optional category, e.g. "bug" or "feature"
+    pub category: Option<String>,
+    /// Optional ticket kind, which decides the required custom fields
+    pub kind: Option<String>,
+    /// Optional person the ticket is assigned to
+    pub assignee: Option<String>,
+    /// Initial custom fields, empty when omitted
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub custom_fields: CustomFields,
+    /// Initial tags, lowercased and deduplicated, empty when omitted
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Optional deadline (RFC 3339), which must lie in the future
+    pub due_date: Option<DateTime<Utc>>,
+    /// Optional story point estimate
+    pub estimate: Option<u16>,
+}
+
+impl CreateTicketRequest {
+    /// Validates the payload into a [`TicketDraft`].
+    ///
+    /// Every field is checked, so all problems are reported at once rather
+    /// than one per request.
+    ///
+    /// # Arguments
+    /// * `config` - Supplies the title and description length limits and the
+    ///   estimate range
+    ///
+    /// # Returns
+    /// * `Ok(TicketDraft)` - The validated draft
+    /// * `Err(Vec<FieldError>)` - One error per invalid field, in field order
+    pub fn validate(self, config: &ServerConfig) -> Result<TicketDraft, Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        let title_suggestion = TicketTitle::suggestion(&self.title, config.max_title_len);
+        let title = TicketTitle::new(self.title, config.max_title_len)
+            .map_err(|message| {
+                errors.push(FieldError {
+                    field: "title".to_string(),
+                    message,
+                    suggestion: title_suggestion,
+                })
+            })
+            .ok();
+
+        // An empty description is filled from the category's template, if any
+        let templated = self
+            .category
+            .as_ref()
+            .is_some_and(|category| config.description_templates.contains_key(category));
+        let description = TicketDescription::new(
+            self.description,
+            config.max_description_len,
+            config.require_description && !templated,
+        )
+        .map_err(|message| errors.push(FieldError::new("description", message)))
+        .ok();
+
+        let assignee = match self.assignee.map(TicketAssignee::new).transpose() {
+            Ok(assignee) => assignee,
+            Err(message) => {
+                errors.push(FieldError::new("assignee", message));
+                None
+            }
+        };
+
+        if self.due_date.is_some_and(|due_date| due_date <= Utc::now()) {
+            errors.push(FieldError::new(
+                "due_date",
+                "Due date must be in the future".to_string(),
+            ));
+        }
+
+        if let Some(message) = self
+            .estimate
+            .and_then(|estimate| estimate_error(estimate, &config.estimate_range))
+        {
+            errors.push(FieldError::new("estimate", message));
+        }
+
+        let mut tags = Vec::new();
+        for tag in self.tags {
+            match TicketTag::new(tag) {
+                Ok(tag) if !tags.contains(&tag) => tags.push(tag),
+                Ok(_) => {}
+                Err(message) => errors.push(FieldError::new("tags", message)),
+            }
+        }
+
+        match (title, description) {
+            (Some(title), Some(description)) if errors.is_empty() => Ok(TicketDraft {
+                title,
+                description,
+                priority: self.priority.unwrap_or_default(),
+                reporter: self.reporter,
+                category: self.category,
+                kind: self.kind,
+                assignee,
+                custom_fields: self.custom_fields,
+                tags,
+                due_date: self.due_date,
+                estimate: self.estimate,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+/// Checks a story point estimate against the allowed range.
+///
+/// # Returns
+/// `None` if `estimate` is within `range`, else why it isn't.
+pub fn estimate_error(estimate: u16, range: &RangeInclusive<u16>) -> Option<String> {
+    (!range.contains(&estimate)).then(|| {
+        format!(
+            "Estimate must be between {} and {}",
+            range.start(),
+            range.end()
+        )
+    })
+}
+
+/// A validation failure on a single request field.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    /// Name of the invalid field
+    pub field: String,
+    /// Why the value was rejected
+    pub message: String,
+    /// A valid replacement value, when one can be derived
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl FieldError {
+    /// Create an error for `field` without a suggestion.
+    pub fn new(field: &str, message: String) -> Self {
+        Self {
+            field: field.to_string(),
+            message,
+            suggestion: None,
+        }
+    }
+}
+
+/// Request payload for creating a ticket mirrored from an external system.
+///
+/// Carries the same fields as [`CreateTicketRequest`] plus the external
+/// reference used to detect an existing ticket.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpsertTicketRequest {
+    /// Identifier of the record in the external system
+    pub external_ref: String,
+    /// The ticket to create if the reference is new
+    #[serde(flatten)]
+    pub ticket: CreateTicketRequest,
+}
+
+/// Request payload for replacing a ticket wholesale.
+///
+/// Carries the same fields as [`CreateTicketRequest`] plus an optional
+/// status. Omitted fields reset to their defaults, and status resets to `ToDo`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReplaceTicketRequest {
+    /// The ticket's new contents
+    #[serde(flatten)]
+    pub ticket: CreateTicketRequest,
+    /// Optional status, `ToDo` when omitted
+    pub status: Option<Status>,
 }
 
-/// Request payload for patching a ticket.
 /// Request payload for updating an existing ticket.
 ///
-/// All fields are optional. Only provided fields will be updated.
-/// When provided, fields will be validated:
-/// - `title`: Must be non-empty and <= 100 characters
-/// - `description`: Must be <= 1000 characters
+/// The body is a JSON Merge Patch (RFC 7386), so each field can be in one of
+/// three states, kept apart as `Option<Option<T>>`:
+/// - omitted (`None`): the field is left as it is
+/// - `null` (`Some(None)`): the field is removed, which clears `description`,
+///   `assignee`, `due_date` and `estimate`. `title`, `status` and `priority`
+///   can't be removed, so a `null` for them is rejected.
+/// - a value (`Some(Some(value))`): the field is set to it
+///
+/// Values are validated:
+/// - `title`: Must be non-empty and at most `max_title_len` characters (100 by default)
+/// - `description`: Must be at most `max_description_len` characters (1000 by default),
+///   and can't be blank or cleared when `require_description` is set
 /// - `status`: Must be a valid Status enum value
-#[derive(Debug, Serialize, Deserialize)]
+/// - `priority`: Must be a valid Priority enum value
+/// - `assignee`: Must be non-empty and <= 80 characters
+/// - `due_date`: Any RFC 3339 time, past ones included
+/// - `estimate`: Within `estimate_range` (0-100 by default)
+///
+/// ```
+/// use ticket_api::data::PatchTicketRequest;
+///
+/// let patch: PatchTicketRequest =
+///     serde_json::from_str(r#"{"title": "Fix login", "description": null}"#).unwrap();
+/// assert_eq!(patch.title, Some(Some("Fix login".to_string())));
+/// assert_eq!(patch.description, Some(None));
+/// assert_eq!(patch.assignee, None);
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 pub struct PatchTicketRequest {
-    /// Optional new title
-    pub title: Option<String>,
-    /// Optional new description
-    pub description: Option<String>,
-    /// Optional new status
+    /// Optional new title, never `null`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub title: Option<Option<String>>,
+    /// Optional new description, `null` to clear it
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Option<String>>,
+    /// Optional new status, never `null`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status: Option<Option<Status>>,
+    /// Optional new priority, never `null`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority: Option<Option<Priority>>,
+    /// Optional new assignee
+    ///
+    /// `None` when the key is omitted (leave the assignee as is),
+    /// `Some(None)` for an explicit `null` (unassign), and `Some(Some(name))`
+    /// to assign. A plain `Option<String>` can't tell the first two apart,
+    /// since serde maps both to `None`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub assignee: Option<Option<String>>,
+    /// Optional new due date, `null` to clear it
+    ///
+    /// Unlike on creation, a date in the past is accepted, since deadlines slip.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub due_date: Option<Option<DateTime<Utc>>>,
+    /// Optional new story point estimate, `null` to clear it
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimate: Option<Option<u16>>,
+}
+
+impl PatchTicketRequest {
+    /// Validates the patch's values into a [`TicketPatch`].
+    ///
+    /// Every field is checked, so all problems are reported at once. Only
+    /// the checks that need no ticket are made here: whether the status may
+    /// change and whether the description shrinks too much depend on the
+    /// patched ticket, and are checked when the patch is applied.
+    ///
+    /// # Arguments
+    /// * `config` - Supplies the title and description length limits and the
+    ///   estimate range
+    ///
+    /// # Returns
+    /// * `Ok(TicketPatch)` - The validated patch
+    /// * `Err(Vec<FieldError>)` - One error per invalid field, in field order
+    pub fn validate(self, config: &ServerConfig) -> Result<TicketPatch, Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        let title = not_null("title", self.title, &mut errors).and_then(|title| {
+            let suggestion = TicketTitle::suggestion(&title, config.max_title_len);
+            TicketTitle::new(title, config.max_title_len)
+                .map_err(|message| {
+                    errors.push(FieldError {
+                        field: "title".to_string(),
+                        message,
+                        suggestion,
+                    })
+                })
+                .ok()
+        });
+
+        // A null description clears it
+        let description = self.description.and_then(|description| {
+            TicketDescription::new(
+                description.unwrap_or_default(),
+                config.max_description_len,
+                config.require_description,
+            )
+            .map_err(|message| errors.push(FieldError::new("description", message)))
+            .ok()
+        });
+
+        let status = not_null("status", self.status, &mut errors);
+        let priority = not_null("priority", self.priority, &mut errors);
+
+        let assignee = match self.assignee {
+            Some(Some(name)) => match TicketAssignee::new(name) {
+                Ok(assignee) => Some(Some(assignee.0)),
+                Err(message) => {
+                    errors.push(FieldError::new("assignee", message));
+                    None
+                }
+            },
+            assignee => assignee.map(|_| None),
+        };
+
+        if let Some(message) = self
+            .estimate
+            .flatten()
+            .and_then(|estimate| estimate_error(estimate, &config.estimate_range))
+        {
+            errors.push(FieldError::new("estimate", message));
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(TicketPatch {
+            title,
+            description,
+            status,
+            priority,
+            assignee,
+            due_date: self.due_date,
+            estimate: self.estimate,
+        })
+    }
+}
+
+/// Unwraps a patched field that can't be removed, recording an error for a
+/// `null`.
+fn not_null<T>(field: &str, value: Option<Option<T>>, errors: &mut Vec<FieldError>) -> Option<T> {
+    match value {
+        Some(None) => {
+            errors.push(FieldError::new(field, "cannot be null".to_string()));
+            None
+        }
+        value => value.flatten(),
+    }
+}
+
+/// A validated [`PatchTicketRequest`], ready to be applied to a ticket.
+///
+/// `None` leaves a field as it is; for the optional fields, `Some(None)`
+/// clears it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TicketPatch {
+    pub title: Option<TicketTitle>,
+    pub description: Option<TicketDescription>,
     pub status: Option<Status>,
+    pub priority: Option<Priority>,
+    pub assignee: Option<Option<String>>,
+    pub due_date: Option<Option<DateTime<Utc>>>,
+    pub estimate: Option<Option<u16>>,
+}
+
+/// Request payload for reopening a `Done` ticket.
+///
+/// Both fields are optional, so `{}` reopens the ticket as `InProgress`.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReopenTicketRequest {
+    /// Why the ticket is reopened, recorded in its history
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Move the ticket back to `ToDo` instead of `InProgress`
+    #[serde(default)]
+    pub backlog: bool,
+}
+
+/// Request payload for moving a ticket to another status.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransitionRequest {
+    /// The status to move the ticket to
+    pub to: Status,
+}
+
+/// Request payload for moving a ticket to another status only if it is
+/// still in the one the client last saw.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CasStatusRequest {
+    /// The status the ticket must be in
+    pub expected: Status,
+    /// The status to move the ticket to
+    pub new: Status,
+}
+
+/// Request payload for moving a ticket in the backlog.
+///
+/// Exactly one field must be set.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReorderRequest {
+    /// UUID of the ticket to move right after
+    pub after: Option<String>,
+    /// UUID of the ticket to move right before
+    pub before: Option<String>,
+    /// `start` or `end` to move to either end of the backlog
+    pub to: Option<BacklogEnd>,
+}
+
+/// One end of the backlog.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BacklogEnd {
+    Start,
+    End,
+}
+
+/// Where [`TicketStore::reorder`](crate::store::TicketStore::reorder) moves
+/// a ticket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReorderTarget {
+    /// Right after the given ticket
+    After(TicketId),
+    /// Right before the given ticket
+    Before(TicketId),
+    /// Ahead of every other ticket
+    Start,
+    /// Behind every other ticket
+    End,
+}
+
+/// The outcome of a status transition.
+#[derive(Debug, Clone)]
+pub struct StatusTransition {
+    /// The status the ticket was in
+    pub from: Status,
+    /// The status the ticket is in now
+    pub to: Status,
+    /// The ticket after the transition
+    pub ticket: Ticket,
+}
+
+/// Request payload for commenting on a ticket.
+///
+/// The author must be non-empty and <= 80 characters, the body non-empty and
+/// <= 500 characters.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddCommentRequest {
+    /// Who writes the comment
+    pub author: String,
+    /// The comment's text
+    pub body: String,
+}
+
+impl AddCommentRequest {
+    /// Validates the payload into a [`CommentDraft`].
+    ///
+    /// # Returns
+    /// * `Ok(CommentDraft)` - The validated comment
+    /// * `Err(Vec<FieldError>)` - One error per invalid field
+    pub fn validate(self) -> Result<CommentDraft, Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        if self.author.trim().is_empty() {
+            errors.push(FieldError::new(
+                "author",
+                "Author cannot be empty".to_string(),
+            ));
+        } else if self.author.chars().count() > 80 {
+            errors.push(FieldError::new(
+                "author",
+                "Author cannot be longer than 80 characters".to_string(),
+            ));
+        }
+
+        let body = CommentBody::new(self.body)
+            .map_err(|message| errors.push(FieldError::new("body", message)))
+            .ok();
+
+        match body {
+            Some(body) if errors.is_empty() => Ok(CommentDraft {
+                author: self.author,
+                body,
+                created_at: None,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+/// One comment of an import, see `POST /tickets/:id/comments/batch`.
+///
+/// Validated like an [`AddCommentRequest`]; `created_at`, when given, can't be
+/// in the future.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportCommentRequest {
+    /// Who wrote the comment
+    pub author: String,
+    /// The comment's text
+    pub body: String,
+    /// When the comment was originally written; defaults to now
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl ImportCommentRequest {
+    /// Validates the payload into a [`CommentDraft`].
+    ///
+    /// # Returns
+    /// * `Ok(CommentDraft)` - The validated comment
+    /// * `Err(Vec<FieldError>)` - One error per invalid field
+    pub fn validate(self) -> Result<CommentDraft, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let draft = AddCommentRequest {
+            author: self.author,
+            body: self.body,
+        }
+        .validate()
+        .map_err(|invalid| errors.extend(invalid))
+        .ok();
+
+        if self
+            .created_at
+            .is_some_and(|created_at| created_at > Utc::now())
+        {
+            errors.push(FieldError::new(
+                "created_at",
+                "Comment time cannot be in the future".to_string(),
+            ));
+        }
+
+        match draft {
+            Some(draft) if errors.is_empty() => Ok(CommentDraft {
+                created_at: self.created_at,
+                ..draft
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+/// Request payload for adding a tag to a ticket.
+///
+/// The tag must be non-empty and <= 30 characters; it's stored lowercased.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddTagRequest {
+    /// The tag to add
+    pub tag: String,
+}
+
+/// One step of a [`TransactionRequest`].
+///
+/// The `op` field names the operation, e.g.
+/// `{"op": "transition", "id": "...", "status": "InProgress"}`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TransactionOperation {
+    /// Create a ticket, validated like [`CreateTicketRequest`]
+    Create { ticket: CreateTicketRequest },
+    /// Update some fields of a ticket, like a PATCH
+    Patch {
+        id: String,
+        changes: PatchTicketRequest,
+        /// Bypass the description shrink guard
+        #[serde(default)]
+        force: bool,
+    },
+    /// Remove a ticket
+    Delete { id: String },
+    /// Move a ticket to another status
+    Transition { id: String, status: Status },
+}
+
+/// Request payload for applying several ticket changes all or nothing.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransactionRequest {
+    /// Operations to apply, in order. Later operations see the effects of
+    /// earlier ones, including tickets they created.
+    pub operations: Vec<TransactionOperation>,
+}
+
+/// A validated transaction step, ready for the store.
+#[derive(Debug)]
+pub enum TicketOperation {
+    /// Create a ticket from the draft
+    Create(TicketDraft),
+    /// Apply the patch to the ticket, bypassing the description shrink guard if set
+    Patch(TicketId, PatchTicketRequest, bool),
+    /// Remove the ticket
+    Delete(TicketId),
+    /// Move the ticket to the status
+    Transition(TicketId, Status),
+}
+
+/// Request payload for updating custom fields on many tickets at once.
+///
+/// `set` is merged into each ticket's custom fields, then every key in
+/// `unset` is removed. Every ticket must still satisfy the custom field
+/// limits afterwards. With `atomic`, either every ticket is updated or none is.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkCustomFieldsRequest {
+    /// UUID strings of the tickets to update
+    pub ids: Vec<String>,
+    /// Custom fields to add or overwrite
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub set: CustomFields,
+    /// Custom field names to remove
+    #[serde(default)]
+    pub unset: Vec<String>,
+    /// Apply all updates or none, defaulting to `false`
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+/// Request payload for fetching many tickets at once.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchGetRequest {
+    /// UUID strings of the tickets to fetch
+    pub ids: Vec<String>,
+}
+
+/// Request payload for moving many tickets to the same status at once.
+///
+/// Each ticket makes the transition on its own, so some may succeed while
+/// others fail.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkStatusRequest {
+    /// UUID strings of the tickets to update
+    pub ids: Vec<String>,
+    /// The status to move every ticket to
+    pub status: Status,
 }
 
 /// Response payload for a ticket.
@@ -140,7 +2105,7 @@ pub struct PatchTicketRequest {
 ///
 /// This is the JSON format returned by the API for all ticket operations.
 /// It flattens the internal ticket structure for a cleaner API response.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TicketResponse {
     /// The ticket's unique identifier
     pub id: TicketId,
@@ -150,6 +2115,143 @@ pub struct TicketResponse {
     pub description: String,
     /// The ticket's current status
     pub status: Status,
+    /// The ticket's current priority
+    pub priority: Priority,
+    /// The ticket's current sync state
+    pub sync_state: SyncState,
+    /// The person who filed the ticket, if known
+    pub reporter: Option<String>,
+    /// The ticket's category, if any
+    pub category: Option<String>,
+    /// The ticket's kind, if any
+    pub kind: Option<String>,
+    /// The person the ticket is assigned to, if any
+    pub assignee: Option<String>,
+    /// The ticket's custom fields
+    #[schema(value_type = Object)]
+    pub custom_fields: CustomFields,
+    /// The ticket's tags
+    pub tags: Vec<String>,
+    /// The tickets this one blocks, and its parent
+    pub relationships: Relationships,
+    /// Identifier of the ticket in an external system, if mirrored
+    pub external_ref: Option<String>,
+    /// When the ticket was created
+    pub created_at: DateTime<Utc>,
+    /// When the ticket last changed
+    pub updated_at: DateTime<Utc>,
+    /// When the ticket last entered `Done`, if it is done
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the ticket entered its current status
+    pub status_changed_at: DateTime<Utc>,
+    /// When the ticket is due, if it has a deadline
+    pub due_date: Option<DateTime<Utc>>,
+    /// The ticket's story points, if estimated
+    pub estimate: Option<u16>,
+    /// The ticket's backlog position; lower ranks come first
+    pub rank: f64,
+    /// The ticket's SLA standing, when SLA tracking covers its status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sla: Option<SlaStatus>,
+    /// The ticket's version, for use with `If-Match`
+    pub version: u64,
+    /// When the ticket was soft-deleted, if it was
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl TicketResponse {
+    /// The names of every field a ticket response can carry, in serialized form.
+    pub const FIELDS: [&'static str; 24] = [
+        "id",
+        "title",
+        "description",
+        "status",
+        "priority",
+        "sync_state",
+        "reporter",
+        "category",
+        "kind",
+        "assignee",
+        "custom_fields",
+        "tags",
+        "relationships",
+        "external_ref",
+        "created_at",
+        "updated_at",
+        "completed_at",
+        "status_changed_at",
+        "due_date",
+        "estimate",
+        "rank",
+        "sla",
+        "version",
+        "deleted_at",
+    ];
+}
+
+/// A [`TicketResponse`] borrowed from a ticket, for serializing without
+/// copying it.
+///
+/// Serializes exactly like the `TicketResponse` of the same ticket, without
+/// `sla`. Fields are declared in alphabetical order, the order in which a
+/// `TicketResponse` turned into a [`Value`] serializes.
+#[derive(Debug, Serialize)]
+pub struct TicketResponseRef<'a> {
+    pub assignee: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub custom_fields: &'a CustomFields,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub description: &'a str,
+    pub due_date: Option<DateTime<Utc>>,
+    pub estimate: Option<u16>,
+    pub external_ref: Option<&'a str>,
+    pub id: &'a TicketId,
+    pub kind: Option<&'a str>,
+    pub priority: Priority,
+    pub rank: f64,
+    pub relationships: &'a Relationships,
+    pub reporter: Option<&'a str>,
+    pub status: Status,
+    pub status_changed_at: DateTime<Utc>,
+    pub sync_state: SyncState,
+    pub tags: &'a [String],
+    pub title: &'a str,
+    pub updated_at: DateTime<Utc>,
+    pub version: u64,
+}
+
+impl<'a> From<&'a Ticket> for TicketResponseRef<'a> {
+    fn from(ticket: &'a Ticket) -> Self {
+        Self {
+            assignee: ticket.assignee.as_deref(),
+            category: ticket.category.as_deref(),
+            completed_at: ticket.completed_at,
+            created_at: ticket.created_at,
+            custom_fields: &ticket.custom_fields,
+            deleted_at: ticket.deleted_at,
+            description: &ticket.description.0,
+            due_date: ticket.due_date,
+            estimate: ticket.estimate,
+            external_ref: ticket.external_ref.as_deref(),
+            id: &ticket.id,
+            kind: ticket.kind.as_deref(),
+            priority: ticket.priority,
+            rank: ticket.rank,
+            relationships: &ticket.relationships,
+            reporter: ticket.reporter.as_deref(),
+            status: ticket.status,
+            status_changed_at: ticket.status_changed_at,
+            sync_state: ticket.sync_state,
+            tags: &ticket.tags,
+            title: &ticket.title.0,
+            updated_at: ticket.updated_at,
+            version: ticket.version,
+        }
+    }
 }
 
 /// Convert Ticket to TicketResponse for API output.
@@ -160,6 +2262,158 @@ impl From<Ticket> for TicketResponse {
             title: ticket.title.0,
             description: ticket.description.0,
             status: ticket.status,
+            priority: ticket.priority,
+            sync_state: ticket.sync_state,
+            reporter: ticket.reporter,
+            category: ticket.category,
+            kind: ticket.kind,
+            assignee: ticket.assignee,
+            custom_fields: ticket.custom_fields,
+            tags: ticket.tags,
+            relationships: ticket.relationships,
+            external_ref: ticket.external_ref,
+            created_at: ticket.created_at,
+            updated_at: ticket.updated_at,
+            completed_at: ticket.completed_at,
+            status_changed_at: ticket.status_changed_at,
+            due_date: ticket.due_date,
+            estimate: ticket.estimate,
+            rank: ticket.rank,
+            sla: None,
+            version: ticket.version,
+            deleted_at: ticket.deleted_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::TicketStore;
+
+    #[tokio::test]
+    async fn borrowed_and_owned_responses_serialize_the_same() {
+        let store = TicketStore::new();
+        let plain = TicketDraft::builder().title("Plain").build().unwrap();
+        let full = TicketDraft::builder()
+            .title("Full \"ticket\" ✨")
+            .description("Every optional field set")
+            .priority(Priority::High)
+            .reporter("ana")
+            .category("billing")
+            .kind("bug")
+            .assignee("bob")
+            .custom_field("team", serde_json::json!({"name": "core", "size": 3}))
+            .tag("urgent")
+            .tag("backend")
+            .due_date(Utc::now() + chrono::Duration::days(3))
+            .estimate(5)
+            .build()
+            .unwrap();
+        let plain = store.add_ticket(plain).await.unwrap();
+        let full = store.add_ticket(full).await.unwrap();
+        store
+            .transition_status(&full.id, Status::InProgress, None)
+            .await
+            .unwrap();
+        let done = store
+            .transition_status(&full.id, Status::Done, None)
+            .await
+            .unwrap()
+            .ticket;
+
+        for ticket in [plain, done] {
+            let borrowed = serde_json::to_vec(&TicketResponseRef::from(&ticket)).unwrap();
+            let value = serde_json::to_value(TicketResponse::from(ticket)).unwrap();
+            assert_eq!(
+                String::from_utf8(borrowed).unwrap(),
+                serde_json::to_string(&value).unwrap()
+            );
         }
     }
+
+    #[test]
+    fn lengths_are_counted_in_characters() {
+        let title = "日本語のタイトル";
+        assert_eq!(title.len(), 24);
+        assert_eq!(TicketTitle::new(title.to_string(), 8).unwrap().0, title);
+        assert!(TicketTitle::new(title.to_string(), 7).is_err());
+
+        let emoji = "🎫".repeat(100);
+        assert_eq!(emoji.len(), 400);
+        assert!(TicketTitle::new(emoji.clone(), 100).is_ok());
+        assert!(TicketTitle::new(format!("{}🎫", emoji), 100).is_err());
+        assert!(TicketDescription::new(emoji.clone(), 100, false).is_ok());
+        assert!(TicketDescription::new(format!("{}🎫", emoji), 100, false).is_err());
+    }
+
+    #[test]
+    fn status_accepts_several_spellings_and_serializes_canonically() {
+        for spelling in [
+            "InProgress",
+            "in_progress",
+            "in-progress",
+            "inprogress",
+            "IN PROGRESS",
+            "In_Progress",
+        ] {
+            let status: Status = serde_json::from_value(serde_json::json!(spelling)).unwrap();
+            assert_eq!(status, Status::InProgress, "{}", spelling);
+        }
+        for (spelling, expected) in [
+            ("todo", Status::ToDo),
+            ("to-do", Status::ToDo),
+            ("DONE", Status::Done),
+            ("cancelled", Status::Cancelled),
+        ] {
+            assert_eq!(spelling.parse::<Status>(), Ok(expected));
+        }
+        assert!(serde_json::from_value::<Status>(serde_json::json!("in progres")).is_err());
+        assert_eq!(
+            serde_json::to_value(Status::InProgress).unwrap(),
+            serde_json::json!("InProgress")
+        );
+    }
+
+    #[tokio::test]
+    async fn titles_are_stored_trimmed() {
+        assert_eq!(
+            TicketTitle::new("  Fix  the bug \t\n".to_string(), 100)
+                .unwrap()
+                .0,
+            "Fix  the bug"
+        );
+        assert!(TicketTitle::new(" \t ".to_string(), 100).is_err());
+        // The limit applies to the trimmed title
+        assert_eq!(
+            TicketTitle::new("   four   ".to_string(), 4).unwrap().0,
+            "four"
+        );
+
+        let store = TicketStore::new();
+        let draft = TicketDraft::builder()
+            .title("  Fix  bug  ")
+            .build()
+            .unwrap();
+        let ticket = store.add_ticket(draft).await.unwrap();
+        assert_eq!(ticket.title.0, "Fix  bug");
+    }
+
+    #[test]
+    fn comment_snippets_keep_some_context_around_the_match() {
+        let comment = Comment {
+            id: Uuid::new_v4(),
+            author: "ana".to_string(),
+            body: format!("{}ÉTÉ{}", "a".repeat(50), "b".repeat(50)),
+            created_at: Utc::now(),
+        };
+
+        let found = CommentMatch::find(&comment, "été").unwrap();
+        assert_eq!(
+            found.snippet,
+            format!("…{}<mark>ÉTÉ</mark>{}…", "a".repeat(40), "b".repeat(40))
+        );
+        assert_eq!(found.comment_id, comment.id);
+        assert!(CommentMatch::find(&comment, "").is_none());
+    }
 }
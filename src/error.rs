@@ -0,0 +1,198 @@
+//! Error responses for the Ticket API.
+//!
+//! Every failed request is answered with the same JSON shape:
+//!
+//! ```json
+//! { "error": { "code": "TICKET_NOT_FOUND", "message": "Ticket with id ... not found" } }
+//! ```
+//!
+//! `code` is stable and meant for programs; `message` is meant for people and
+//! may change. Some errors carry extra fields next to them, such as the
+//! per-field `errors` of a failed validation. With
+//! [`ServerConfig::verbose_errors`](crate::config::ServerConfig::verbose_errors)
+//! set, errors coming from the store also carry a `source` with the
+//! underlying error, for debugging.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::{json, Map, Value};
+
+use crate::data::Status;
+use crate::store::StoreError;
+
+/// An error response with an HTTP status and a machine-readable code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiError {
+    /// HTTP status of the response
+    pub status: StatusCode,
+    /// Stable identifier of the failure, e.g. `TICKET_NOT_FOUND`
+    pub code: &'static str,
+    /// Human-readable explanation
+    pub message: String,
+    /// Extra fields serialized next to `code` and `message`
+    pub details: Map<String, Value>,
+    /// The underlying error, only shown in verbose error bodies
+    pub source: Option<Value>,
+}
+
+/// Response extension carrying an error's [`ApiError::source`] to the
+/// [`verbose_errors`](crate::middleware::verbose_errors) middleware.
+#[derive(Clone, Debug)]
+pub struct ErrorSource(pub Value);
+
+impl ApiError {
+    /// Create an error without extra details.
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            details: Map::new(),
+            source: None,
+        }
+    }
+
+    /// Adds an extra field to the error object.
+    pub fn with_detail(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// `400 Bad Request` with code `INVALID_ID`.
+    pub fn invalid_id(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "INVALID_ID", message)
+    }
+
+    /// `400 Bad Request` with code `VALIDATION_FAILED` and the invalid fields
+    /// listed under `errors`.
+    pub fn validation_failed(errors: impl Into<Value>) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            "Validation failed",
+        )
+        .with_detail("errors", errors)
+    }
+
+    /// `500 Internal Server Error` with code `INTERNAL_ERROR`.
+    pub fn internal(message: impl std::fmt::Display) -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            format!("Internal server error: {}", message),
+        )
+    }
+}
+
+impl From<StoreError> for ApiError {
+    fn from(error: StoreError) -> Self {
+        let message = error.to_string();
+        let source = json!({
+            "error": message,
+            "debug": format!("{:?}", error),
+        });
+        let api_error = match error {
+            StoreError::TicketNotFound(_) => {
+                Self::new(StatusCode::NOT_FOUND, "TICKET_NOT_FOUND", message)
+            }
+            StoreError::InvalidField(msg) => {
+                Self::new(StatusCode::BAD_REQUEST, "INVALID_FIELD", msg)
+            }
+            StoreError::ValidationFailed(errors) => Self::validation_failed(json!(errors)),
+            StoreError::RateLimited(_) => {
+                Self::new(StatusCode::TOO_MANY_REQUESTS, "RATE_LIMITED", message)
+            }
+            StoreError::VersionConflict { expected, actual } => {
+                Self::new(StatusCode::CONFLICT, "VERSION_CONFLICT", message)
+                    .with_detail("expected", expected)
+                    .with_detail("actual", actual)
+            }
+            StoreError::MissingRequiredFields { missing, .. } => Self::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "MISSING_REQUIRED_FIELDS",
+                message,
+            )
+            .with_detail("missing", missing),
+            StoreError::DescriptionShrink { from, to } => {
+                Self::new(StatusCode::CONFLICT, "DESCRIPTION_SHRINK", message)
+                    .with_detail("from", from)
+                    .with_detail("to", to)
+            }
+            StoreError::TagNotFound(_) => {
+                Self::new(StatusCode::NOT_FOUND, "TAG_NOT_FOUND", message)
+            }
+            StoreError::CapacityExceeded { capacity } => Self::new(
+                StatusCode::INSUFFICIENT_STORAGE,
+                "CAPACITY_EXCEEDED",
+                message,
+            )
+            .with_detail("capacity", capacity),
+            StoreError::NotDone(status) => {
+                Self::new(StatusCode::CONFLICT, "TICKET_NOT_DONE", message)
+                    .with_detail("status", status.name())
+            }
+            StoreError::InvalidTransition { from, to, allowed } => {
+                let allowed: Vec<&str> = allowed.iter().map(Status::name).collect();
+                Self::new(StatusCode::CONFLICT, "INVALID_TRANSITION", message)
+                    .with_detail("from", from.name())
+                    .with_detail("to", to.name())
+                    .with_detail("allowed", allowed)
+            }
+            StoreError::IdempotencyKeyReused(_) => {
+                Self::new(StatusCode::CONFLICT, "IDEMPOTENCY_KEY_REUSED", message)
+            }
+            StoreError::NothingToUndo(_) => {
+                Self::new(StatusCode::CONFLICT, "NOTHING_TO_UNDO", message)
+            }
+            StoreError::Timeout(..) => {
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, "TICKET_BUSY", message)
+            }
+            StoreError::IdCollision(_) => {
+                Self::new(StatusCode::INTERNAL_SERVER_ERROR, "ID_COLLISION", message)
+            }
+            StoreError::StatusMismatch { expected, actual } => {
+                Self::new(StatusCode::CONFLICT, "STATUS_MISMATCH", message)
+                    .with_detail("expected", expected.name())
+                    .with_detail("actual", actual.name())
+            }
+            StoreError::StatusOwnerOnly { assignee } => {
+                Self::new(StatusCode::FORBIDDEN, "STATUS_OWNER_ONLY", message)
+                    .with_detail("assignee", assignee)
+            }
+            StoreError::ChangesExpired { floor, .. } => {
+                Self::new(StatusCode::GONE, "CHANGES_EXPIRED", message).with_detail("floor", floor)
+            }
+        };
+        Self {
+            source: Some(source),
+            ..api_error
+        }
+    }
+}
+
+impl ApiError {
+    /// Returns the error object: `code`, `message` and any extra fields.
+    ///
+    /// This is what a response wraps under `"error"`; batch endpoints embed
+    /// it per item.
+    pub fn to_json(&self) -> Value {
+        let mut error = self.details.clone();
+        error.insert("code".to_string(), json!(self.code));
+        error.insert("message".to_string(), json!(self.message));
+        Value::Object(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    /// Answers with the compact body. The source, if any, rides along as an
+    /// [`ErrorSource`] extension, for the middleware to add when verbose.
+    fn into_response(self) -> Response {
+        let mut response = (self.status, Json(json!({ "error": self.to_json() }))).into_response();
+        if let Some(source) = self.source {
+            response.extensions_mut().insert(ErrorSource(source));
+        }
+        response
+    }
+}
@@ -0,0 +1,100 @@
+//! Request extractors for the Ticket API.
+//!
+//! [`RequestJson`] reads a JSON request body like axum's `Json`, but a body
+//! it can't use is answered in the API's error format instead of axum's
+//! plain-text rejections:
+//!
+//! ```json
+//! { "error": { "code": "INVALID_BODY", "message": "status: unknown variant ...", "field": "status" } }
+//! ```
+//!
+//! `field` is the path of the value that failed, such as `tags[1]` or
+//! `tickets[0].priority`, and is left out when the body as a whole is wrong.
+
+use async_trait::async_trait;
+use axum::{
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    http::StatusCode,
+    Json,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::error::ApiError;
+
+/// A JSON request body, deserialized into `T`.
+///
+/// Any `application/json` or `application/*+json` content type is accepted.
+///
+/// # Rejections
+/// - `400 Bad Request` with code `MALFORMED_JSON` if the body isn't valid JSON
+/// - `413 Payload Too Large` with code `PAYLOAD_TOO_LARGE` if the body is
+///   over the router's limit
+/// - `415 Unsupported Media Type` with code `UNSUPPORTED_MEDIA_TYPE` if the
+///   request isn't labelled as JSON
+/// - `422 Unprocessable Entity` with code `INVALID_BODY` if the JSON doesn't
+///   fit `T`, see [`from_json_value`]
+pub struct RequestJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for RequestJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(body) = Json::<Value>::from_request(request, state)
+            .await
+            .map_err(rejection_error)?;
+        from_json_value(body).map(RequestJson)
+    }
+}
+
+/// Deserializes a JSON value into `T`, naming the field that doesn't fit.
+///
+/// # Returns
+/// - The deserialized value
+/// - `422 Unprocessable Entity` with code `INVALID_BODY` otherwise. The
+///   message is prefixed with the path of the offending field, which is also
+///   given as `field`; a missing field is named by its own path.
+pub fn from_json_value<T: DeserializeOwned>(body: Value) -> Result<T, ApiError> {
+    serde_path_to_error::deserialize(body).map_err(|e| {
+        let inner = e.inner().to_string();
+        let path = match e.path().to_string() {
+            path if path == "." => None,
+            path => Some(path),
+        };
+        let missing = inner
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.strip_suffix('`'));
+        let field = match (path, missing) {
+            (Some(path), Some(missing)) => Some(format!("{}.{}", path, missing)),
+            (None, Some(missing)) => Some(missing.to_string()),
+            (path, None) => path,
+        };
+
+        let message = match &field {
+            Some(field) => format!("{}: {}", field, inner),
+            None => inner,
+        };
+        let error = ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "INVALID_BODY", message);
+        match field {
+            Some(field) => error.with_detail("field", field),
+            None => error,
+        }
+    })
+}
+
+/// Converts a rejection of axum's `Json` extractor into an [`ApiError`].
+fn rejection_error(rejection: JsonRejection) -> ApiError {
+    let status = rejection.status();
+    let code = match &rejection {
+        JsonRejection::JsonSyntaxError(_) => "MALFORMED_JSON",
+        JsonRejection::MissingJsonContentType(_) => "UNSUPPORTED_MEDIA_TYPE",
+        _ if status == StatusCode::PAYLOAD_TOO_LARGE => "PAYLOAD_TOO_LARGE",
+        _ => "INVALID_BODY",
+    };
+    ApiError::new(status, code, rejection.body_text())
+}
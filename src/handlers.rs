@@ -7,90 +7,843 @@
 //! - Formatting appropriate HTTP responses
 //! - Error handling and status code selection
 //!
+//! Failures are returned as [`ApiError`], so every error response has the
+//! same `{"error": {"code", "message"}}` shape.
+//!
 //! The API supports the following operations:
 //! - `POST /tickets` - Create a new ticket
+//! - `POST /tickets/batch` - Create many tickets at once
+//! - `POST /tickets/validate` - Validate a create or patch payload without storing it
 //! - `GET /tickets` - List all tickets
 //! - `GET /tickets/:id` - Get a specific ticket
+//! - `POST /tickets/batch-get` - Get many tickets at once
 //! - `PATCH /tickets/:id` - Update a ticket
-//! - `GET /health` - Health check endpoint
+//! - `PUT /tickets/:id` - Replace a ticket
+//! - `DELETE /tickets/:id` - Soft-delete a ticket
+//! - `POST /tickets/:id/restore` - Restore a soft-deleted ticket
+//! - `POST /tickets/:id/transition` - Move a ticket to another status
+//! - `POST /tickets/:id/cas-status` - Move a ticket out of an expected status
+//! - `POST /tickets/:id/reorder` - Move a ticket in the backlog
+//! - `POST /tickets/:id/reopen` - Reopen a done ticket
+//! - `POST /tickets/:id/undo` - Revert the most recent change to a ticket
+//! - `POST /tickets/transaction` - Apply several ticket changes all or nothing
+//! - `GET /tickets/search` - Search tickets by title and description text
+//! - `GET /tickets/diff` - Compare two tickets field by field
+//! - `GET /tickets/count` - Count tickets in total and per status
+//! - `GET /tickets/lead-time` - Lead time statistics over a completion window
+//! - `GET /tickets/matrix` - Count tickets by priority and status
+//! - `GET /tickets/assignees` - Count tickets per assignee
+//! - `POST /tickets/upsert` - Get or create a ticket by external reference
+//! - `POST /tickets/bulk-custom` - Update custom fields on many tickets
+//! - `POST /tickets/bulk-status` - Move many tickets to the same status
+//! - `GET /tickets/events` - Stream ticket changes as Server-Sent Events
+//! - `GET /tickets/:id/wait` - Wait for a ticket to change
+//! - `GET /tickets/changes` - List the changes since a sequence number
+//! - `GET /tickets/pending-sync` - List tickets not yet synced
+//! - `POST /tickets/:id/mark-synced` - Mark a ticket as synced
+//! - `POST /tickets/:id/tags` - Add a tag to a ticket
+//! - `DELETE /tickets/:id/tags/:tag` - Remove a tag from a ticket
+//! - `POST /tickets/:id/comments` - Comment on a ticket
+//! - `GET /tickets/:id/comments` - List a ticket's comments
+//! - `GET /tickets/:id/history` - List a ticket's field changes
+//! - `POST /tickets/:id/block/:other` - Mark a ticket as blocking another
+//! - `DELETE /tickets/:id/block/:other` - Remove a blocking link
+//! - `POST /tickets/:id/parent/:parent` - Set a ticket's parent
+//! - `DELETE /tickets/:id/parent` - Clear a ticket's parent
+//! - `GET /tickets/export.csv` - Export every ticket as CSV
+//! - `GET /admin/fixture` - Export the store as a fixture
+//! - `POST /admin/fixture` - Load the store from a fixture
+//! - `GET /admin/snapshot` - Back up every ticket
+//! - `POST /admin/restore` - Replace every ticket with a snapshot
+//! - `POST /admin/purge-deleted` - Permanently remove soft-deleted tickets
+//! - `GET /metrics` - Request and ticket metrics for Prometheus
+//! - `GET /health` - Health check endpoint, an alias of `/health/live`
+//! - `GET /health/live` - Liveness check
+//! - `GET /health/ready` - Readiness check
+//! - `GET /openapi.json` - The OpenAPI description of the API
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
-    Json as RequestJson,
+    body::Body,
+    extract::{OriginalUri, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
 };
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
 use serde_json::{json, Value};
-use uuid::Uuid;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+use utoipa::{IntoParams, OpenApi, ToSchema};
 
 use crate::data::{
-    CreateTicketRequest, PatchTicketRequest, TicketDescription, TicketDraft, TicketId,
-    TicketResponse, TicketTitle,
+    AddCommentRequest, AddTagRequest, BacklogEnd, BatchGetRequest, BulkCustomFieldsRequest,
+    BulkStatusRequest, CasStatusRequest, Comment, CreateTicketRequest, Cursor, Fixture,
+    ImportCommentRequest, LeadTimeStats, PatchTicketRequest, Priority, ReopenTicketRequest,
+    ReorderRequest, ReorderTarget, ReplaceTicketRequest, Snapshot, SortField, SortOrder, SortSpec,
+    Status, Ticket, TicketDraft, TicketFilter, TicketId, TicketOperation, TicketResponse,
+    TicketTag, TransactionOperation, TransactionRequest, TransitionRequest, UpsertTicketRequest,
+};
+use crate::error::ApiError;
+use crate::extract::{from_json_value, RequestJson};
+use crate::metrics::{Metrics, PROMETHEUS_CONTENT_TYPE};
+use crate::openapi::{
+    ApiDoc, AssigneeList, BatchCreated, BatchTickets, BulkResults, ChangeFeed, CommentList,
+    CommentsImported, ErrorBody, FixtureLoaded, Health, HistoryList, Purged, Readiness,
+    SearchResults, TicketCount, TicketDiff, TicketList, TicketPage, TimeInStatusReport,
+    TransactionResults, TransitionResult, UndoResult,
 };
-use crate::store::{StoreError, TicketStore};
+use crate::repository::TicketRepository;
+use crate::store::StoreError;
+
+/// Media type requested by JSON:API clients.
+pub const JSON_API_MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// Request header naming the user making a change.
+pub const USER_HEADER: &str = "x-user";
+
+/// Returns the user named by the `X-User` header, if any.
+fn request_user(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(USER_HEADER)
+        .and_then(|value| value.to_str().ok())
+}
+
+/// Returns true when the `Accept` header asks for the JSON:API format.
+fn wants_json_api(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map(|accept| accept.contains(JSON_API_MEDIA_TYPE))
+        .unwrap_or(false)
+}
+
+/// The representations [`get_ticket`] and [`list_tickets`] can respond with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Json,
+    JsonApi,
+    Text,
+}
+
+impl Format {
+    /// Picks a format from the `Accept` header.
+    ///
+    /// JSON:API wins whenever it is listed, as before. Otherwise the first
+    /// listed media range that `application/json` or `text/plain` satisfies
+    /// decides, and a missing or empty header means JSON.
+    ///
+    /// # Returns
+    /// * `Ok(Format)` - The format to respond with
+    /// * `Err(ApiError)` - `406 Not Acceptable` if no listed media range can
+    ///   be served
+    fn negotiate(headers: &HeaderMap) -> Result<Self, ApiError> {
+        let accept = headers
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("");
+        if accept.trim().is_empty() {
+            return Ok(Format::Json);
+        }
+        if wants_json_api(headers) {
+            return Ok(Format::JsonApi);
+        }
+
+        accept
+            .split(',')
+            .filter_map(|range| range.split(';').next())
+            .find_map(|range| match range.trim().to_ascii_lowercase().as_str() {
+                "application/json" | "application/*" | "*/*" => Some(Format::Json),
+                "text/plain" | "text/*" => Some(Format::Text),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ApiError::new(
+                    StatusCode::NOT_ACCEPTABLE,
+                    "NOT_ACCEPTABLE",
+                    "Tickets can be served as application/json, application/vnd.api+json or text/plain",
+                )
+                .with_detail("accept", accept)
+            })
+    }
+}
+
+/// A ticket read, rendered in the format the client asked for.
+pub enum TicketBody {
+    /// A plain JSON document
+    Json(Value),
+    /// A JSON:API document
+    JsonApi(Value),
+    /// One `#<id-prefix> [Status] Title` line per ticket
+    Text(String),
+}
+
+impl IntoResponse for TicketBody {
+    fn into_response(self) -> Response {
+        match self {
+            TicketBody::Json(document) => Json(document).into_response(),
+            TicketBody::JsonApi(document) => json_api_response(document),
+            TicketBody::Text(text) => text.into_response(),
+        }
+    }
+}
+
+/// Renders tickets as terse text, one line each.
+///
+/// A line holds the first eight characters of the ID, the status and the
+/// title, e.g. `#1f0c9a2e [InProgress] Fix login`.
+fn text_lines(responses: &[TicketResponse]) -> String {
+    responses
+        .iter()
+        .map(|response| {
+            let id = response.id.to_string();
+            format!(
+                "#{} [{}] {}\n",
+                id.get(..8).unwrap_or(&id),
+                response.status.name(),
+                response.title
+            )
+        })
+        .collect()
+}
+
+/// Builds a JSON:API resource object for a ticket.
+///
+/// Every field except `id` becomes an attribute.
+fn json_api_resource(response: &TicketResponse) -> Value {
+    let mut attributes = json!(response);
+    if let Some(fields) = attributes.as_object_mut() {
+        fields.remove("id");
+    }
+    json!({
+        "type": "tickets",
+        "id": response.id,
+        "attributes": attributes,
+        "links": {
+            "self": format!("/tickets/{}", response.id)
+        }
+    })
+}
+
+/// Parses a comma-separated `fields` parameter into the fields to keep.
+///
+/// `id` is always kept, whether listed or not.
+///
+/// # Returns
+/// * `Ok(None)` - No `fields` parameter was given, so every field is kept
+/// * `Ok(Some(fields))` - The fields to keep
+/// * `Err(ApiError)` - `400 Bad Request` if a name isn't a ticket field
+fn parse_fields(fields: Option<&str>) -> Result<Option<HashSet<String>>, ApiError> {
+    let Some(fields) = fields else {
+        return Ok(None);
+    };
+    let mut keep = field_names(fields);
+
+    let mut unknown: Vec<&String> = keep
+        .iter()
+        .filter(|field| !TicketResponse::FIELDS.contains(&field.as_str()))
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort();
+        let message = format!(
+            "Unknown ticket fields: {}",
+            unknown
+                .iter()
+                .map(|field| field.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        return Err(
+            ApiError::new(StatusCode::BAD_REQUEST, "INVALID_FIELDS", message)
+                .with_detail("allowed", json!(TicketResponse::FIELDS)),
+        );
+    }
+
+    keep.insert("id".to_string());
+    Ok(Some(keep))
+}
+
+/// Like [`parse_fields`], but drops names that aren't ticket fields instead
+/// of rejecting them.
+fn known_fields(fields: Option<&str>) -> Option<HashSet<String>> {
+    let mut keep = field_names(fields?);
+    keep.retain(|field| TicketResponse::FIELDS.contains(&field.as_str()));
+    keep.insert("id".to_string());
+    Some(keep)
+}
+
+/// Splits a comma-separated `fields` parameter into the names it lists.
+fn field_names(fields: &str) -> HashSet<String> {
+    fields
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Serializes tickets, keeping only `fields` when given.
+fn ticket_values(responses: &[TicketResponse], fields: Option<&HashSet<String>>) -> Vec<Value> {
+    responses
+        .iter()
+        .map(|response| {
+            let mut value = json!(response);
+            if let (Some(fields), Some(object)) = (fields, value.as_object_mut()) {
+                object.retain(|key, _| fields.contains(key));
+            }
+            value
+        })
+        .collect()
+}
+
+/// Builds JSON:API resource objects for tickets, keeping only the
+/// attributes in `fields` when given.
+fn json_api_resources(
+    responses: &[TicketResponse],
+    fields: Option<&HashSet<String>>,
+) -> Vec<Value> {
+    responses
+        .iter()
+        .map(|response| {
+            let mut resource = json_api_resource(response);
+            if let (Some(fields), Some(attributes)) = (
+                fields,
+                resource
+                    .get_mut("attributes")
+                    .and_then(Value::as_object_mut),
+            ) {
+                attributes.retain(|key, _| fields.contains(key));
+            }
+            resource
+        })
+        .collect()
+}
+
+/// Wraps a JSON:API document in a response with the JSON:API content type.
+fn json_api_response(document: Value) -> Response {
+    (
+        [(header::CONTENT_TYPE, JSON_API_MEDIA_TYPE)],
+        Json(document),
+    )
+        .into_response()
+}
+
+/// Parses a ticket ID from a path or query parameter with [`TicketId::parse`].
+///
+/// # Returns
+/// - The parsed [`TicketId`]
+/// - `400 Bad Request` if the string isn't a UUID, or is the nil UUID, which
+///   can never identify a ticket
+fn parse_ticket_id(id: &str) -> Result<TicketId, ApiError> {
+    TicketId::parse(id).map_err(ApiError::invalid_id)
+}
 
 /// Application state shared across all handlers.
-/// Uses [`TicketStore`] for thread-safe ticket storage.
-pub type AppState = TicketStore;
+/// Any [`TicketRepository`] backend can be used; the router defaults to the
+/// in-memory [`TicketStore`](crate::store::TicketStore).
+pub type AppState = Arc<dyn TicketRepository>;
 
 /// Creates a new ticket from the provided request payload.
 ///
 /// # Request Body
 /// Expects a JSON object with:
-/// - `title`: String (1-100 characters)
-/// - `description`: String (max 1000 characters)
+/// - `title`: String (1-100 characters by default, see [`ServerConfig::max_title_len`](crate::config::ServerConfig::max_title_len))
+/// - `description`: String (max 1000 characters by default)
+/// - `priority`: Optional<Priority> ("Low", "Medium", "High", or "Critical")
+/// - `reporter`: Optional<String>
+/// - `category`: Optional<String>
+/// - `kind`: Optional<String>, deciding which custom fields are required
+/// - `assignee`: Optional<String> (1-80 characters)
+/// - `custom_fields`: Optional object of custom fields
+/// - `due_date`: Optional RFC 3339 time, which must be in the future
+/// - `estimate`: Optional story points (0-100 by default, see [`ServerConfig::estimate_range`](crate::config::ServerConfig::estimate_range))
+///
+/// # Headers
+/// - `Idempotency-Key`: Optional key (1-255 characters) making retries safe.
+///   A request repeating a remembered key gets the ticket its first request
+///   created, as it was then, with `Idempotent-Replayed: true`, and creates
+///   nothing.
 ///
 /// # Returns
-/// - `201 Created` with the created ticket on success
-/// - `400 Bad Request` if validation fails
-/// - `500 Internal Server Error` if ticket creation fails
+/// - `201 Created` with the created ticket on success. If live tickets with
+///   the same title, ignoring case and surrounding whitespace, already
+///   exist, the ticket is still created and carries a `warnings` array with
+///   a `POSSIBLE_DUPLICATE` warning listing their `ids`. A replay of a
+///   remembered idempotency key is also `201 Created`, marked by the
+///   `Idempotent-Replayed: true` header.
+/// - `400 Bad Request` if validation fails, listing every invalid field
+///   under `errors`. An overlong title's error comes with a truncated
+///   `suggestion`.
+/// - `409 Conflict` if the idempotency key was used with a different body
+/// - `422 Unprocessable Entity` if the ticket's kind requires custom fields
+///   that are missing
+/// - `429 Too Many Requests` if the reporter exceeded the creation throttle
+/// - `507 Insufficient Storage` if the store is at capacity
+#[utoipa::path(
+    post,
+    path = "/tickets",
+    tag = "tickets",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Key making retries safe")),
+    request_body = CreateTicketRequest,
+    responses(
+        (status = 201, description = "Ticket created, with `warnings` when it may be a duplicate, or the replay of an earlier request with the same idempotency key", body = TicketResponse,
+            headers(("Idempotent-Replayed" = String, description = "`true` when the response replays an earlier request's creation"))),
+        (status = 400, description = "Validation failed", body = ErrorBody),
+        (status = 409, description = "Idempotency key used with a different body", body = ErrorBody),
+        (status = 422, description = "Required custom fields are missing", body = ErrorBody),
+        (status = 429, description = "Reporter exceeded the creation throttle", body = ErrorBody),
+        (status = 507, description = "Store is at capacity", body = ErrorBody),
+    )
+)]
 pub async fn create_ticket(
     State(store): State<AppState>,
+    headers: HeaderMap,
     RequestJson(request): RequestJson<CreateTicketRequest>,
-) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
-    // Validate input
-    let title = match TicketTitle::new(request.title) {
-        Ok(title) => title,
-        Err(e) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": "Invalid title",
-                    "message": e
-                })),
-            ));
+) -> Result<Response, ApiError> {
+    let idempotency = match idempotency_key(&headers)? {
+        Some(key) => Some((key, request_fingerprint(&request)?)),
+        None => None,
+    };
+    if let Some((key, fingerprint)) = &idempotency {
+        if let Some(ticket) = store.idempotent_replay(key, *fingerprint)? {
+            return Ok(replayed_creation(ticket));
+        }
+    }
+
+    let draft = draft_from_request(&store, request)?;
+    check_draft(&store, &draft)?;
+
+    if let Some(reporter) = &draft.reporter {
+        store.throttle_creation(reporter)?;
+    }
+
+    let duplicates = store.find_by_title(&draft.title.0).await;
+    if let Some((key, fingerprint)) = idempotency {
+        let (ticket, created) = store
+            .add_ticket_idempotent(&key, fingerprint, draft)
+            .await?;
+        if !created {
+            return Ok(replayed_creation(ticket));
         }
+        return Ok(created_ticket(ticket, duplicates));
+    }
+
+    let ticket = store.add_ticket(draft).await?;
+    Ok(created_ticket(ticket, duplicates))
+}
+
+/// The `201 Created` response of [`create_ticket`].
+///
+/// When live tickets with the same title already existed, a `warnings`
+/// array is added to the ticket, naming them as possible duplicates.
+fn created_ticket(ticket: Ticket, duplicates: Vec<TicketId>) -> Response {
+    let mut response = json!(TicketResponse::from(ticket));
+    if !duplicates.is_empty() {
+        response["warnings"] = json!([{
+            "code": "POSSIBLE_DUPLICATE",
+            "message": "Tickets with the same title already exist",
+            "ids": duplicates
+        }]);
+    }
+    (StatusCode::CREATED, Json(response)).into_response()
+}
+
+/// Request header making [`create_ticket`] safe to retry.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header marking a [`create_ticket`] response as a replay.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Reads the `Idempotency-Key` header, if sent.
+///
+/// # Returns
+/// - The key, or `None` when the header is absent
+/// - `400 Bad Request` if the key is empty, longer than 255 characters or
+///   not visible ASCII
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, ApiError> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
     };
+    match value.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= 255 => Ok(Some(key.to_string())),
+        _ => Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "INVALID_IDEMPOTENCY_KEY",
+            "Idempotency-Key must hold 1 to 255 visible ASCII characters",
+        )),
+    }
+}
 
-    let description = match TicketDescription::new(request.description) {
-        Ok(description) => description,
-        Err(e) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": "Invalid description",
-                    "message": e
-                })),
-            ));
+/// Hashes a creation request, so a reused idempotency key can be told from
+/// a retry.
+///
+/// The parsed request is hashed, so formatting and key order don't matter.
+fn request_fingerprint(request: &CreateTicketRequest) -> Result<u64, ApiError> {
+    let body = serde_json::to_string(request).map_err(ApiError::internal)?;
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+/// Answers a retried creation with the ticket its first request created.
+fn replayed_creation(ticket: Ticket) -> Response {
+    let response = TicketResponse::from(ticket);
+    (
+        StatusCode::CREATED,
+        [(IDEMPOTENT_REPLAYED_HEADER, "true")],
+        Json(json!(response)),
+    )
+        .into_response()
+}
+
+/// Largest number of items [`create_tickets`] and [`add_comments`] accept in
+/// one request.
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+/// Creates many tickets in one request.
+///
+/// Every item is validated like in [`create_ticket`]. Invalid items are
+/// reported and skipped; the valid ones are stored together.
+///
+/// # Request Body
+/// A JSON array of [`create_ticket`] payloads, at most [`MAX_BATCH_SIZE`] long
+///
+/// # Returns
+/// - `200 OK` with the created tickets under `tickets`, in input order, and
+///   one entry per rejected item under `errors`, holding its `index` and
+///   `error`
+/// - `413 Payload Too Large` if the array has more than [`MAX_BATCH_SIZE`] items
+/// - `507 Insufficient Storage` if the valid tickets don't all fit in the
+///   store; none are created
+#[utoipa::path(
+    post,
+    path = "/tickets/batch",
+    tag = "tickets",
+    request_body = Vec<CreateTicketRequest>,
+    responses(
+        (status = 200, description = "Created tickets and rejected items", body = BatchCreated),
+        (status = 413, description = "Too many items", body = ErrorBody),
+        (status = 507, description = "Valid tickets don't fit in the store", body = ErrorBody),
+    )
+)]
+pub async fn create_tickets(
+    State(store): State<AppState>,
+    RequestJson(items): RequestJson<Vec<Value>>,
+) -> Result<Json<Value>, ApiError> {
+    if items.len() > MAX_BATCH_SIZE {
+        return Err(ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "BATCH_TOO_LARGE",
+            format!(
+                "A batch holds at most {} tickets, got {}",
+                MAX_BATCH_SIZE,
+                items.len()
+            ),
+        ));
+    }
+
+    let mut drafts = Vec::with_capacity(items.len());
+    let mut errors = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let draft = from_json_value::<CreateTicketRequest>(item)
+            .and_then(|request| draft_from_request(&store, request))
+            .and_then(|draft| check_draft(&store, &draft).map(|()| draft))
+            .and_then(|draft| match &draft.reporter {
+                Some(reporter) => store
+                    .throttle_creation(reporter)
+                    .map(|()| draft)
+                    .map_err(ApiError::from),
+                None => Ok(draft),
+            });
+        match draft {
+            Ok(draft) => drafts.push(draft),
+            Err(error) => errors.push(json!({
+                "index": index,
+                "error": error.to_json()
+            })),
         }
+    }
+
+    let responses: Vec<TicketResponse> = store
+        .add_tickets(drafts)
+        .await?
+        .into_iter()
+        .map(TicketResponse::from)
+        .collect();
+
+    Ok(Json(json!({
+        "tickets": responses,
+        "errors": errors
+    })))
+}
+
+/// Reads the expected ticket version from an `If-Match` header.
+///
+/// Accepts the version bare (`3`) or as an entity tag (`"3"`).
+///
+/// # Returns
+/// - `None` when the header is absent, so the update is unconditional
+/// - `400 Bad Request` if the header isn't a version number
+fn parse_if_match(headers: &HeaderMap) -> Result<Option<u64>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
     };
+    value
+        .to_str()
+        .ok()
+        .map(|value| value.trim().trim_matches('"'))
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Some)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "INVALID_IF_MATCH",
+                "If-Match must carry a ticket version number",
+            )
+        })
+}
 
-    let draft = TicketDraft { title, description };
-    let ticket_id = store.add_ticket(draft).await;
+/// Returns the entity tag of a ticket, its quoted version, e.g. `"3"`.
+///
+/// Every change to a ticket bumps its version, so the tag changes with any
+/// field and stays the same across unchanged reads. It is the same value
+/// `If-Match` accepts.
+fn ticket_etag(ticket: &Ticket) -> String {
+    format!("\"{}\"", ticket.version)
+}
 
-    // Retrieve the created ticket to return complete information
-    match store.get_ticket(&ticket_id).await {
-        Ok(ticket) => {
-            let response = TicketResponse::from(ticket);
-            Ok((StatusCode::CREATED, Json(json!(response))))
+/// Returns true if an `If-None-Match` header lists `etag` or is `*`.
+///
+/// Weak tags (`W/"3"`) match their strong counterpart.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(value) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// Formats `time` as an HTTP date, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+///
+/// This is the IMF-fixdate format of RFC 7231, section 7.1.1.1, which
+/// `Last-Modified` must use. Fractions of a second are dropped.
+fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Parses an HTTP date in any of the three formats of RFC 7231: the
+/// IMF-fixdate, the obsolete RFC 850 format and asctime's.
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    [
+        "%a, %d %b %Y %H:%M:%S GMT",
+        "%A, %d-%b-%y %H:%M:%S GMT",
+        "%a %b %e %H:%M:%S %Y",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|time| time.and_utc())
+}
+
+/// Returns true if an `If-Modified-Since` header holds a date at or after
+/// `updated_at`, to the second.
+///
+/// As RFC 7232 requires, the header is ignored when `If-None-Match` is sent
+/// or its date can't be parsed.
+fn not_modified_since(headers: &HeaderMap, updated_at: DateTime<Utc>) -> bool {
+    if headers.contains_key(header::IF_NONE_MATCH) {
+        return false;
+    }
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_http_date)
+        .is_some_and(|since| updated_at.timestamp() <= since.timestamp())
+}
+
+/// Validates a create payload into a [`TicketDraft`].
+///
+/// # Returns
+/// - The validated draft
+/// - `400 Bad Request` listing every invalid field under `errors`. An
+///   overlong title's error comes with a truncated `suggestion`.
+fn draft_from_request(
+    store: &AppState,
+    request: CreateTicketRequest,
+) -> Result<TicketDraft, ApiError> {
+    request
+        .validate(store.config())
+        .map_err(|errors| ApiError::validation_failed(json!(errors)))
+}
+
+/// Checks a draft against the store's custom field policies.
+///
+/// # Returns
+/// - `400 Bad Request` if the custom fields exceed the configured limits
+/// - `422 Unprocessable Entity` if the ticket's kind requires custom fields
+///   that are missing, listed under `missing`
+fn check_draft(store: &AppState, draft: &TicketDraft) -> Result<(), ApiError> {
+    Ok(store.validate_draft(draft)?)
+}
+
+/// Creates a ticket for an external reference unless one already exists.
+///
+/// Lets a mirroring job replay the same external record safely: the first
+/// call creates the ticket, later calls return it untouched.
+///
+/// # Request Body
+/// The same fields as [`create_ticket`], plus:
+/// - `external_ref`: String identifying the record in the external system
+///
+/// # Returns
+/// - `201 Created` with the new ticket if none existed for the reference
+/// - `200 OK` with the existing ticket otherwise
+/// - `400 Bad Request` if validation fails or `external_ref` is empty
+/// - `422 Unprocessable Entity` if the ticket's kind requires custom fields
+///   that are missing
+/// - `507 Insufficient Storage` if a new ticket doesn't fit in the store
+#[utoipa::path(
+    post,
+    path = "/tickets/upsert",
+    tag = "tickets",
+    request_body = UpsertTicketRequest,
+    responses(
+        (status = 201, description = "Ticket created for the reference", body = TicketResponse),
+        (status = 200, description = "Existing ticket for the reference", body = TicketResponse),
+        (status = 400, description = "Validation failed", body = ErrorBody),
+        (status = 422, description = "Required custom fields are missing", body = ErrorBody),
+        (status = 507, description = "Store is at capacity", body = ErrorBody),
+    )
+)]
+pub async fn upsert_ticket(
+    State(store): State<AppState>,
+    RequestJson(request): RequestJson<UpsertTicketRequest>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    if request.external_ref.trim().is_empty() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "INVALID_EXTERNAL_REF",
+            "External reference cannot be empty",
+        ));
+    }
+
+    let draft = draft_from_request(&store, request.ticket)?;
+    check_draft(&store, &draft)?;
+    let (ticket, created) = store
+        .get_or_create_by_ref(&request.external_ref, draft)
+        .await?;
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(json!(TicketResponse::from(ticket)))))
+}
+
+/// Query parameters for [`get_ticket`].
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetParams {
+    /// Return the ticket even if it is soft-deleted
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+/// Largest number of IDs [`get_tickets`] accepts in one request.
+pub const MAX_BATCH_GET_SIZE: usize = 200;
+
+/// Query parameters for [`get_tickets`].
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BatchGetParams {
+    /// Comma-separated ticket fields to return, e.g. `id,title,status`
+    pub fields: Option<String>,
+}
+
+/// Retrieves many tickets in one request.
+///
+/// # Query Parameters
+/// - `fields`: Optional comma-separated ticket fields to return for each
+///   ticket, as on [`list_tickets`]; `id` is always returned. Names that
+///   aren't ticket fields are ignored.
+///
+/// # Request Body
+/// Expects a JSON object with:
+/// - `ids`: Array of UUID strings, at most [`MAX_BATCH_GET_SIZE`] long
+///
+/// # Returns
+/// - `200 OK` with the found tickets under `tickets`, keyed by ID, the IDs
+///   that match no ticket, or a soft-deleted one, under `not_found`, and the
+///   strings that aren't UUIDs under `invalid_ids`
+/// - `400 Bad Request` if there are more than [`MAX_BATCH_GET_SIZE`] IDs
+/// - `422 Unprocessable Entity` if the body doesn't match the expected shape
+#[utoipa::path(
+    post,
+    path = "/tickets/batch-get",
+    tag = "tickets",
+    params(BatchGetParams),
+    request_body = BatchGetRequest,
+    responses(
+        (status = 200, description = "Found tickets, missing and malformed IDs", body = BatchTickets),
+        (status = 400, description = "Too many IDs", body = ErrorBody),
+        (status = 422, description = "Body doesn't match the expected shape", body = ErrorBody),
+    )
+)]
+pub async fn get_tickets(
+    State(store): State<AppState>,
+    Query(params): Query<BatchGetParams>,
+    RequestJson(request): RequestJson<BatchGetRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let fields = known_fields(params.fields.as_deref());
+    if request.ids.len() > MAX_BATCH_GET_SIZE {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "TOO_MANY_IDS",
+            format!(
+                "At most {} tickets can be fetched at once, got {}",
+                MAX_BATCH_GET_SIZE,
+                request.ids.len()
+            ),
+        ));
+    }
+
+    let mut ids = Vec::with_capacity(request.ids.len());
+    let mut invalid_ids = Vec::new();
+    for id in request.ids {
+        match TicketId::parse(&id) {
+            Ok(ticket_id) => ids.push(ticket_id),
+            Err(_) => invalid_ids.push(id),
         }
-        Err(_) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "error": "Failed to create ticket"
-            })),
-        )),
     }
+
+    let (tickets, not_found) = store.get_many(&ids).await;
+    let responses: Vec<TicketResponse> = tickets.into_iter().map(TicketResponse::from).collect();
+    let tickets: serde_json::Map<String, Value> = responses
+        .iter()
+        .map(|response| response.id.to_string())
+        .zip(ticket_values(&responses, fields.as_ref()))
+        .collect();
+
+    Ok(Json(json!({
+        "tickets": tickets,
+        "not_found": not_found,
+        "invalid_ids": invalid_ids
+    })))
 }
 
 /// Retrieves a ticket by its UUID.
@@ -98,48 +851,102 @@ pub async fn create_ticket(
 /// # Path Parameters
 /// - `id`: UUID string of the ticket to retrieve
 ///
+/// # Query Parameters
+/// - `include_deleted`: Optional, `true` to return a soft-deleted ticket
+///   (with its `deleted_at`) instead of `404`
+///
+/// # Headers
+/// - `If-None-Match`: Optional entity tag from an earlier response's `ETag`
+/// - `If-Modified-Since`: Optional HTTP date from an earlier response's
+///   `Last-Modified`; ignored when `If-None-Match` is sent
+///
 /// # Returns
-/// - `200 OK` with the ticket data if found
+/// - `200 OK` with the ticket data if found, an `ETag` header derived from
+///   the ticket's version and a `Last-Modified` header holding its
+///   `updated_at` as an HTTP date. When SLA tracking covers the ticket's
+///   status, an `sla` object gives the `deadline` for leaving it, the
+///   `remaining_seconds` and whether it is `breached`; the ticket's version,
+///   and so its `ETag`, doesn't change as the SLA clock runs.
+/// - `304 Not Modified` with the `ETag`, `Last-Modified` and no body if
+///   `If-None-Match` matches the ticket's current entity tag, or the ticket
+///   hasn't changed since `If-Modified-Since`. HTTP dates only count whole
+///   seconds, so prefer `If-None-Match` for tickets that change often.
 /// - `400 Bad Request` if the UUID is invalid
-/// - `404 Not Found` if no ticket matches the UUID
+/// - `404 Not Found` if no ticket matches the UUID, or it is soft-deleted
+/// - `406 Not Acceptable` if the `Accept` header allows none of the formats below
+/// - `503 Service Unavailable` if the ticket stayed locked by a write for
+///   longer than [`ServerConfig::lock_timeout`](crate::config::ServerConfig::lock_timeout)
 /// - `500 Internal Server Error` on unexpected errors
+///
+/// Responds in JSON:API format when the `Accept` header is
+/// `application/vnd.api+json`, and with a `#<id-prefix> [Status] Title` line
+/// when it is `text/plain`.
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), GetParams, ("If-None-Match" = Option<String>, Header, description = "Entity tag of a cached copy"), ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified date of a cached copy")),
+    responses(
+        (status = 200, description = "The ticket", body = TicketResponse),
+        (status = 304, description = "The cached copy is current"),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 406, description = "No acceptable format", body = ErrorBody),
+        (status = 503, description = "The ticket stayed locked too long", body = ErrorBody),
+    )
+)]
 pub async fn get_ticket(
     State(store): State<AppState>,
     Path(id): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    // Parse UUID
-    let uuid = match Uuid::parse_str(&id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": "Invalid ticket ID format"
-                })),
-            ));
-        }
-    };
+    Query(params): Query<GetParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = Format::negotiate(&headers)?;
+    let ticket_id = parse_ticket_id(&id)?;
 
-    let ticket_id = TicketId(uuid);
+    let ticket = store.get_ticket(&ticket_id).await?;
+    if ticket.is_deleted() && !params.include_deleted {
+        return Err(StoreError::TicketNotFound(ticket_id).into());
+    }
+    let etag = ticket_etag(&ticket);
+    let last_modified = http_date(ticket.updated_at);
+    if if_none_match(&headers, &etag) || not_modified_since(&headers, ticket.updated_at) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (header::LAST_MODIFIED, last_modified)],
+        )
+            .into_response());
+    }
 
-    match store.get_ticket(&ticket_id).await {
-        Ok(ticket) => {
-            let response = TicketResponse::from(ticket);
-            Ok(Json(json!(response)))
-        }
-        Err(StoreError::TicketNotFound(_)) => Err((
-            StatusCode::NOT_FOUND,
-            Json(json!({
-                "error": "Ticket not found"
-            })),
-        )),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "error": format!("Internal server error: {}", e)
-            })),
-        )),
+    let sla = store.sla_status(&ticket);
+    let response = TicketResponse {
+        sla,
+        ..TicketResponse::from(ticket)
+    };
+    let mut response = match format {
+        Format::JsonApi => TicketBody::JsonApi(json!({
+            "data": json_api_resource(&response)
+        })),
+        Format::Text => TicketBody::Text(text_lines(&[response])),
+        Format::Json => TicketBody::Json(json!(response)),
     }
+    .into_response();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&last_modified) {
+        response.headers_mut().insert(header::LAST_MODIFIED, value);
+    }
+    Ok(response)
+}
+
+/// Query parameters for [`patch_ticket`].
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PatchParams {
+    /// Apply the patch even if it shrinks the description past the guard
+    #[serde(default)]
+    pub force: bool,
 }
 
 /// Updates specific fields of an existing ticket.
@@ -148,78 +955,2737 @@ pub async fn get_ticket(
 /// - `id`: UUID string of the ticket to update
 ///
 /// # Request Body
-/// JSON object with optional fields:
-/// - `title`: Optional<String> (1-100 characters)
-/// - `description`: Optional<String> (max 1000 characters)
-/// - `status`: Optional<Status> ("ToDo", "InProgress", or "Done")
+/// A JSON Merge Patch (RFC 7386): omitted fields are left alone and `null`
+/// removes a field. Sent as `application/json` or
+/// `application/merge-patch+json`, with optional fields:
+/// - `title`: Optional<String> (1-100 characters by default), not `null`
+/// - `description`: Optional<String> (max 1000 characters by default), or
+///   `null` to clear
+/// - `status`: Optional<Status> ("ToDo", "InProgress", "Done" or "Cancelled"),
+///   not `null`
+/// - `priority`: Optional<Priority> ("Low", "Medium", "High", or "Critical"),
+///   not `null`
+/// - `assignee`: Optional<String> (1-80 characters), or `null` to unassign
+/// - `due_date`: Optional RFC 3339 time, past ones allowed, or `null` to clear
+/// - `estimate`: Optional story points (0-100 by default), or `null` to clear
+///
+/// # Query Parameters
+/// - `force`: Optional bool; `true` allows a patch that the description
+///   shrink guard would reject
+///
+/// # Headers
+/// - `If-Match`: Optional ticket version; the update only applies if the
+///   ticket is still at that version
+/// - `X-User`: Optional name of the user making the change
 ///
 /// # Returns
 /// - `200 OK` with the updated ticket
-/// - `400 Bad Request` if validation fails or UUID is invalid
+/// - `304 Not Modified` with the ticket's `ETag` and no body if every field
+///   in the patch already held its value; the version and `updated_at`
+///   stay put
+/// - `400 Bad Request` listing every invalid field under `errors`, including
+///   a `null` `title`, `status` or `priority`. An overlong title's error
+///   comes with a truncated `suggestion`.
+/// - `400 Bad Request` if the UUID is invalid, or if `status` names no
+///   status, with the closest valid status as `suggestion`
+/// - `403 Forbidden` if
+///   [`ServerConfig::status_owner_only`](crate::config::ServerConfig::status_owner_only)
+///   is set and the patch changes the status of a ticket assigned to
+///   someone other than `X-User`, with the `assignee`. Patches that leave
+///   the status alone are never refused.
 /// - `404 Not Found` if no ticket matches the UUID
+/// - `409 Conflict` if `If-Match` doesn't match the ticket's version, or if
+///   [`ServerConfig::description_shrink_guard`](crate::config::ServerConfig::description_shrink_guard)
+///   is set and the new description is too much shorter without `force=true`
+/// - `409 Conflict` with code `INVALID_TRANSITION` if the status change isn't
+///   an allowed transition (see [`Status::can_transition_to`]), with `from`,
+///   `to` and `allowed`
+/// - `422 Unprocessable Entity` if the body doesn't match the expected shape
+/// - `503 Service Unavailable` if the ticket stayed locked for longer than
+///   [`ServerConfig::lock_timeout`](crate::config::ServerConfig::lock_timeout)
+#[utoipa::path(
+    patch,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), PatchParams, ("If-Match" = Option<String>, Header, description = "Expected ticket version"), ("X-User" = Option<String>, Header, description = "User making the change")),
+    request_body = PatchTicketRequest,
+    responses(
+        (status = 200, description = "The updated ticket", body = TicketResponse),
+        (status = 304, description = "Nothing changed"),
+        (status = 400, description = "Validation failed or invalid UUID", body = ErrorBody),
+        (status = 403, description = "Only the assignee may change the status", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 409, description = "Version mismatch, description shrink or disallowed transition", body = ErrorBody),
+        (status = 422, description = "Body doesn't match the expected shape", body = ErrorBody),
+        (status = 503, description = "The ticket stayed locked too long", body = ErrorBody),
+    )
+)]
 pub async fn patch_ticket(
     State(store): State<AppState>,
     Path(id): Path<String>,
-    RequestJson(patch_request): RequestJson<PatchTicketRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    // Parse UUID
-    let uuid = match Uuid::parse_str(&id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            return Err((
+    Query(params): Query<PatchParams>,
+    headers: HeaderMap,
+    RequestJson(body): RequestJson<Value>,
+) -> Result<Response, ApiError> {
+    let patch_request = patch_from_body(body)?;
+
+    let ticket_id = parse_ticket_id(&id)?;
+    let expected_version = parse_if_match(&headers)?;
+    let user = request_user(&headers);
+
+    match store
+        .patch_ticket(
+            &ticket_id,
+            patch_request,
+            expected_version,
+            params.force,
+            user,
+        )
+        .await
+    {
+        Ok((ticket, false)) => Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, ticket_etag(&ticket))],
+        )
+            .into_response()),
+        Ok((ticket, true)) => {
+            let response = TicketResponse::from(ticket);
+            Ok(Json(json!(response)).into_response())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Deserializes a patch payload.
+///
+/// # Returns
+/// - The patch
+/// - `400 Bad Request` if `status` names no status, with the closest valid
+///   status as `suggestion`
+/// - `422 Unprocessable Entity` if the body doesn't match the expected shape
+fn patch_from_body(body: Value) -> Result<PatchTicketRequest, ApiError> {
+    // Catch unknown statuses before deserializing so we can suggest a fix
+    if let Some(Value::String(status)) = body.get("status") {
+        if let Err(e) = status.parse::<Status>() {
+            return Err(ApiError::new(
                 StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": "Invalid ticket ID format"
-                })),
-            ));
+                "INVALID_FIELD",
+                format!("status: {}", e),
+            )
+            .with_detail("suggestion", json!(Status::closest(status))));
         }
-    };
+    }
+
+    from_json_value(body)
+}
 
-    let ticket_id = TicketId(uuid);
+/// The kind of payload [`validate_ticket`] checks.
+#[derive(Clone, Copy, Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidateMode {
+    /// A [`create_ticket`] body
+    #[default]
+    Create,
+    /// A [`patch_ticket`] body
+    Patch,
+}
 
-    match store.patch_ticket(&ticket_id, patch_request).await {
-        Ok(ticket) => {
-            let response = TicketResponse::from(ticket);
-            Ok(Json(json!(response)))
+/// Query parameters for [`validate_ticket`].
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ValidateParams {
+    /// `create` (the default) or `patch`
+    #[serde(default)]
+    pub mode: ValidateMode,
+}
+
+/// Validates a ticket payload without storing anything.
+///
+/// Runs the same checks as [`create_ticket`] or [`patch_ticket`], so a UI
+/// can report problems before submitting. Patches are checked on their own:
+/// whether their status change is allowed and whether they shrink the
+/// description too much depend on the ticket they are applied to.
+///
+/// # Query Parameters
+/// - `mode`: Optional, `create` (default) or `patch`
+///
+/// # Request Body
+/// The body [`create_ticket`] or [`patch_ticket`] would take
+///
+/// # Returns
+/// - `200 OK` with `{"valid": true}`
+/// - `400 Bad Request` listing every invalid field under `errors`, or for an
+///   unknown status, like the endpoint being validated for
+/// - `422 Unprocessable Entity` if the body doesn't match the expected
+///   shape, or the ticket's kind requires custom fields that are missing
+#[utoipa::path(
+    post,
+    path = "/tickets/validate",
+    tag = "tickets",
+    params(ValidateParams),
+    request_body(content = Object, description = "A create or patch payload, depending on `mode`"),
+    responses(
+        (status = 200, description = "The payload is valid", body = Object),
+        (status = 400, description = "Validation failed", body = ErrorBody),
+        (status = 422, description = "Body doesn't match the expected shape, or required custom fields are missing", body = ErrorBody),
+    )
+)]
+pub async fn validate_ticket(
+    State(store): State<AppState>,
+    Query(params): Query<ValidateParams>,
+    RequestJson(body): RequestJson<Value>,
+) -> Result<Json<Value>, ApiError> {
+    match params.mode {
+        ValidateMode::Create => {
+            let request: CreateTicketRequest = from_json_value(body)?;
+            let draft = draft_from_request(&store, request)?;
+            check_draft(&store, &draft)?;
+        }
+        ValidateMode::Patch => {
+            patch_from_body(body)?
+                .validate(store.config())
+                .map_err(|errors| ApiError::validation_failed(json!(errors)))?;
         }
-        Err(StoreError::TicketNotFound(_)) => Err((
-            StatusCode::NOT_FOUND,
-            Json(json!({
-                "error": "Ticket not found"
-            })),
-        )),
-        Err(StoreError::InvalidField(msg)) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "Invalid field",
-                "message": msg
-            })),
-        )),
     }
+    Ok(Json(json!({ "valid": true })))
+}
+
+/// Replaces an existing ticket with the provided contents.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to replace
+///
+/// # Request Body
+/// The same fields as [`create_ticket`], plus:
+/// - `status`: Optional<Status>, `ToDo` when omitted
+///
+/// Fields left out of the body reset to their defaults instead of keeping
+/// their current values. The status follows the same transition rules as
+/// [`transition_status`], so an omitted status only works when the ticket
+/// can move to `ToDo`.
+///
+/// # Headers
+/// - `If-Match`: Optional ticket version; the replacement only applies if
+///   the ticket is still at that version
+/// - `X-User`: Optional name of the user making the change
+///
+/// # Returns
+/// - `200 OK` with the replaced ticket
+/// - `400 Bad Request` if validation fails or UUID is invalid
+/// - `403 Forbidden` if only the assignee may change the status and
+///   `X-User` isn't them (see [`patch_ticket`])
+/// - `404 Not Found` if no ticket matches the UUID
+/// - `409 Conflict` if `If-Match` doesn't match the ticket's version, or
+///   with code `INVALID_TRANSITION` if the ticket can't move to the status
+/// - `422 Unprocessable Entity` if the ticket's kind requires custom fields
+///   that are missing
+/// - `503 Service Unavailable` if the ticket stays locked for too long
+#[utoipa::path(
+    put,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("If-Match" = Option<String>, Header, description = "Expected ticket version"), ("X-User" = Option<String>, Header, description = "User making the change")),
+    request_body = ReplaceTicketRequest,
+    responses(
+        (status = 200, description = "The replaced ticket", body = TicketResponse),
+        (status = 400, description = "Validation failed or invalid UUID", body = ErrorBody),
+        (status = 403, description = "Only the assignee may change the status", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 409, description = "Version mismatch or invalid status transition", body = ErrorBody),
+        (status = 422, description = "Required custom fields are missing", body = ErrorBody),
+        (status = 503, description = "The ticket stayed locked for too long", body = ErrorBody),
+    )
+)]
+pub async fn put_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    RequestJson(request): RequestJson<ReplaceTicketRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let expected_version = parse_if_match(&headers)?;
+    let draft = draft_from_request(&store, request.ticket)?;
+    check_draft(&store, &draft)?;
+    let status = request.status.unwrap_or(Status::ToDo);
+
+    let ticket = store
+        .replace_ticket(
+            &ticket_id,
+            draft,
+            status,
+            expected_version,
+            request_user(&headers),
+        )
+        .await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
 }
 
-/// Lists all tickets in the system.
+/// Soft-deletes a ticket by its UUID.
+///
+/// The ticket disappears from reads but is kept in the trash, see
+/// [`list_trash`], so it can be brought back with [`restore_ticket`].
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to delete
 ///
 /// # Returns
-/// - `200 OK` with an array of all tickets in the system
-/// - Returns an empty array if no tickets exist
-pub async fn list_tickets(State(store): State<AppState>) -> Json<Value> {
-    let tickets = store.list_tickets().await;
-    let responses: Vec<TicketResponse> = tickets.into_iter().map(TicketResponse::from).collect();
-    Json(json!({
-        "tickets": responses
-    }))
+/// - `204 No Content` if the ticket was deleted
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID, or it is already deleted
+#[utoipa::path(
+    delete,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 204, description = "Ticket deleted"),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID, or it is already deleted", body = ErrorBody),
+    )
+)]
+pub async fn delete_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    store.delete_ticket(&ticket_id).await?;
+    Ok(StatusCode::NO_CONTENT)
 }
 
-/// Health check endpoint to verify the service is running.
+/// Restores a soft-deleted ticket.
+///
+/// Restoring a ticket that isn't deleted is a harmless no-op.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to restore
 ///
 /// # Returns
-/// - `200 OK` with a JSON object containing:
-///   - `status`: "healthy"
-///   - `service`: "ticket-api"
-pub async fn health_check() -> Json<Value> {
-    Json(json!({
-        "status": "healthy",
-        "service": "ticket-api"
-    }))
+/// - `200 OK` with the restored ticket
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/restore",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 200, description = "The restored ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn restore_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let ticket = store.restore(&ticket_id).await?;
+    Ok(Json(json!(TicketResponse::from(ticket))))
+}
+
+/// Lists the tickets in the trash.
+///
+/// Deleted tickets stay in the trash until restored, purged by an admin or,
+/// with a [`TrashPolicy`](crate::config::TrashPolicy), until their retention
+/// runs out or newer deletions push them out.
+///
+/// # Returns
+/// - `200 OK` with an array of the soft-deleted tickets, the most recently
+///   deleted first, each with its `deleted_at`
+#[utoipa::path(
+    get,
+    path = "/tickets/trash",
+    tag = "tickets",
+    responses(
+        (status = 200, description = "Soft-deleted tickets", body = TicketList),
+    )
+)]
+pub async fn list_trash(State(store): State<AppState>) -> Json<Value> {
+    let tickets = store.list_trash().await;
+    let responses: Vec<TicketResponse> = tickets.into_iter().map(TicketResponse::from).collect();
+    Json(json!({
+        "tickets": responses
+    }))
+}
+
+/// Restores a ticket from the trash.
+///
+/// Like [`restore_ticket`], but only for tickets that are in the trash.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to restore
+///
+/// # Returns
+/// - `200 OK` with the restored ticket
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket in the trash matches the UUID
+#[utoipa::path(
+    post,
+    path = "/tickets/trash/{id}/restore",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 200, description = "The restored ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket in the trash matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn restore_from_trash(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let ticket = store.restore_from_trash(&ticket_id).await?;
+    Ok(Json(json!(TicketResponse::from(ticket))))
+}
+
+/// Reverts the most recent change to a ticket.
+///
+/// The newest entry of the ticket's history is undone, one field at a
+/// time: the field goes back to its previous value. The revert is recorded
+/// in the history like any other change, so undoing again redoes it.
+/// Bookkeeping fields such as `completed_at` and the ticket's links aren't
+/// undone on their own; see [`UNDOABLE_FIELDS`](crate::store::UNDOABLE_FIELDS).
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket
+///
+/// # Headers
+/// - `X-User`: Optional name of the user making the change
+///
+/// # Returns
+/// - `200 OK` with `field`, the reverted field, and the updated `ticket`
+/// - `400 Bad Request` if the UUID is invalid, or the previous value no
+///   longer passes validation
+/// - `403 Forbidden` if only the assignee may change the status and
+///   `X-User` isn't them (see [`patch_ticket`])
+/// - `404 Not Found` if no ticket matches the UUID, or it is soft-deleted
+/// - `409 Conflict` if the ticket has no change to undo, or if reverting its
+///   status isn't an allowed transition
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/undo",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("X-User" = Option<String>, Header, description = "User making the change")),
+    responses(
+        (status = 200, description = "The reverted field and the updated ticket", body = UndoResult),
+        (status = 400, description = "Invalid UUID, or the previous value is invalid", body = ErrorBody),
+        (status = 403, description = "Only the assignee may change the status", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 409, description = "Nothing to undo, or the status can't move back", body = ErrorBody),
+    )
+)]
+pub async fn undo_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let (ticket, field) = store
+        .undo_last_change(&ticket_id, request_user(&headers))
+        .await?;
+    Ok(Json(json!({
+        "field": field,
+        "ticket": TicketResponse::from(ticket)
+    })))
+}
+
+/// Moves a ticket to another status.
+///
+/// Unlike a status `PATCH`, the response says where the ticket moved from,
+/// and a refused move lists where it could go instead.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to move
+///
+/// # Request Body
+/// Expects a JSON object with:
+/// - `to`: Status to move to ("ToDo", "InProgress", "Done" or "Cancelled").
+///   The move must be allowed by [`Status::can_transition_to`].
+///
+/// # Headers
+/// - `X-User`: Optional name of the user making the change
+///
+/// # Returns
+/// - `200 OK` with `from`, `to` and the updated `ticket`. Moving a ticket
+///   to its current status changes nothing and reports `from` equal to `to`.
+/// - `400 Bad Request` if the UUID is invalid
+/// - `403 Forbidden` if only the assignee may change the status and
+///   `X-User` isn't them (see [`patch_ticket`])
+/// - `404 Not Found` if no ticket matches the UUID, or it is soft-deleted
+/// - `409 Conflict` if the move isn't allowed, with the statuses the ticket
+///   can move to in `allowed`
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/transition",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("X-User" = Option<String>, Header, description = "User making the change")),
+    request_body = TransitionRequest,
+    responses(
+        (status = 200, description = "The previous and new status, and the ticket", body = TransitionResult),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 403, description = "Only the assignee may change the status", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 409, description = "The move isn't allowed", body = ErrorBody),
+    )
+)]
+pub async fn transition_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    RequestJson(request): RequestJson<TransitionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let transition = store
+        .transition_status(&ticket_id, request.to, request_user(&headers))
+        .await?;
+    Ok(Json(json!({
+        "from": transition.from,
+        "to": transition.to,
+        "ticket": TicketResponse::from(transition.ticket)
+    })))
+}
+
+/// Moves a ticket to another status, if it is still in the expected one.
+///
+/// A compare-and-swap: of several clients making the same move, exactly one
+/// wins and the others learn the status it left the ticket in, without
+/// tracking versions for `If-Match`.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to move
+///
+/// # Request Body
+/// Expects a JSON object with:
+/// - `expected`: Status the ticket must be in
+/// - `new`: Status to move to. The move must be allowed by
+///   [`Status::can_transition_to`].
+///
+/// # Headers
+/// - `X-User`: Optional name of the user making the change
+///
+/// # Returns
+/// - `200 OK` with the updated ticket. When `new` equals `expected`,
+///   nothing changes.
+/// - `400 Bad Request` if the UUID is invalid
+/// - `403 Forbidden` if only the assignee may change the status and
+///   `X-User` isn't them (see [`patch_ticket`])
+/// - `404 Not Found` if no ticket matches the UUID, or it is soft-deleted
+/// - `409 Conflict` with code `STATUS_MISMATCH` and the `actual` status if
+///   the ticket isn't in `expected`, or `INVALID_TRANSITION` if the move
+///   isn't allowed
+/// - `422 Unprocessable Entity` if the body doesn't match the expected shape
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/cas-status",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("X-User" = Option<String>, Header, description = "User making the change")),
+    request_body = CasStatusRequest,
+    responses(
+        (status = 200, description = "The updated ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 403, description = "Only the assignee may change the status", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 409, description = "Status mismatch, or the move isn't allowed", body = ErrorBody),
+        (status = 422, description = "Body doesn't match the expected shape", body = ErrorBody),
+    )
+)]
+pub async fn cas_status(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    RequestJson(request): RequestJson<CasStatusRequest>,
+) -> Result<Json<TicketResponse>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let ticket = store
+        .compare_and_swap_status(
+            &ticket_id,
+            request.expected,
+            request.new,
+            request_user(&headers),
+        )
+        .await?;
+    Ok(Json(TicketResponse::from(ticket)))
+}
+
+/// Moves a ticket to another position in the backlog.
+///
+/// List the backlog with `GET /tickets?sort=rank`.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to move
+///
+/// # Request Body
+/// Expects a JSON object with exactly one of:
+/// - `after`: UUID of the ticket to move right after
+/// - `before`: UUID of the ticket to move right before
+/// - `to`: `"start"` or `"end"` of the backlog
+///
+/// # Returns
+/// - `200 OK` with the ticket and its new `rank`. A ticket already in the
+///   requested position is returned unchanged.
+/// - `400 Bad Request` if a UUID is invalid, the body doesn't set exactly
+///   one field, or the ticket is to move next to itself
+/// - `404 Not Found` if no live ticket matches the UUID, or the ticket to
+///   move next to
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/reorder",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    request_body = ReorderRequest,
+    responses(
+        (status = 200, description = "The ticket with its new rank", body = TicketResponse),
+        (status = 400, description = "Invalid UUID or position", body = ErrorBody),
+        (status = 404, description = "No ticket matches a UUID", body = ErrorBody),
+    )
+)]
+pub async fn reorder_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    RequestJson(request): RequestJson<ReorderRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let target = match (request.after, request.before, request.to) {
+        (Some(after), None, None) => ReorderTarget::After(parse_ticket_id(&after)?),
+        (None, Some(before), None) => ReorderTarget::Before(parse_ticket_id(&before)?),
+        (None, None, Some(BacklogEnd::Start)) => ReorderTarget::Start,
+        (None, None, Some(BacklogEnd::End)) => ReorderTarget::End,
+        _ => {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "INVALID_REORDER",
+                "Set exactly one of 'after', 'before' or 'to'",
+            ))
+        }
+    };
+
+    let ticket = store.reorder(&ticket_id, target).await?;
+    Ok(Json(json!(TicketResponse::from(ticket))))
+}
+
+/// Longest reason [`reopen_ticket`] accepts, in characters.
+pub const MAX_REOPEN_REASON_LEN: usize = 500;
+
+/// Reopens a ticket that is `Done`.
+///
+/// The status change is recorded in the ticket's history with the reason.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to reopen
+///
+/// # Request Body
+/// Expects a JSON object with:
+/// - `reason`: Optional<String> saying why, at most 500 characters
+/// - `backlog`: Optional bool, `true` to move the ticket to `ToDo` instead
+///   of `InProgress`
+///
+/// # Headers
+/// - `X-User`: Optional name of the user making the change
+///
+/// # Returns
+/// - `200 OK` with the reopened ticket
+/// - `400 Bad Request` if the UUID or the reason is invalid
+/// - `403 Forbidden` if only the assignee may change the status and
+///   `X-User` isn't them (see [`patch_ticket`])
+/// - `404 Not Found` if no ticket matches the UUID, or it is soft-deleted
+/// - `409 Conflict` if the ticket isn't `Done`
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/reopen",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("X-User" = Option<String>, Header, description = "User making the change")),
+    request_body = ReopenTicketRequest,
+    responses(
+        (status = 200, description = "The reopened ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID or reason", body = ErrorBody),
+        (status = 403, description = "Only the assignee may change the status", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 409, description = "The ticket isn't Done", body = ErrorBody),
+    )
+)]
+pub async fn reopen_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    RequestJson(request): RequestJson<ReopenTicketRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let reason = request
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if reason
+        .as_ref()
+        .is_some_and(|reason| reason.chars().count() > MAX_REOPEN_REASON_LEN)
+    {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "INVALID_REASON",
+            format!(
+                "Reason cannot be longer than {} characters",
+                MAX_REOPEN_REASON_LEN
+            ),
+        ));
+    }
+
+    let ticket = store
+        .reopen(&ticket_id, reason, request.backlog, request_user(&headers))
+        .await?;
+    Ok(Json(json!(TicketResponse::from(ticket))))
+}
+
+/// Applies several ticket changes all or nothing.
+///
+/// Operations run in order under one store-wide lock, so later operations
+/// see earlier ones and no other change interleaves. If any operation fails,
+/// none is applied.
+///
+/// # Request Body
+/// JSON object with `operations`, an array of objects tagged by `op`:
+/// - `{"op": "create", "ticket": {...}}` with the fields of [`create_ticket`]
+/// - `{"op": "patch", "id": "...", "changes": {...}}` with the fields of
+///   [`patch_ticket`], and an optional `"force": true`
+/// - `{"op": "delete", "id": "..."}`, a soft delete like [`delete_ticket`]
+/// - `{"op": "transition", "id": "...", "status": "..."}`
+///
+/// # Headers
+/// - `X-User`: Optional name of the user making the changes; status
+///   changes are checked as in [`patch_ticket`]
+///
+/// # Returns
+/// - `200 OK` with one result per operation under `results`, each with its
+///   `op` and the resulting `ticket` (or the deleted `id`)
+/// - On failure, the error of the first failing operation with its index
+///   under `operation`, e.g. `400 Bad Request` for invalid fields or IDs,
+///   `403 Forbidden` for a status change only the assignee may make,
+///   `404 Not Found` for a missing ticket, or `429 Too Many Requests` if a
+///   reporter exceeded the creation throttle
+#[utoipa::path(
+    post,
+    path = "/tickets/transaction",
+    tag = "tickets",
+    params(("X-User" = Option<String>, Header, description = "User making the change")),
+    request_body = TransactionRequest,
+    responses(
+        (status = 200, description = "One result per operation", body = TransactionResults),
+        (status = 400, description = "An operation has invalid fields or IDs", body = ErrorBody),
+        (status = 403, description = "An operation changes a status only the assignee may change", body = ErrorBody),
+        (status = 404, description = "An operation's ticket doesn't exist", body = ErrorBody),
+        (status = 429, description = "Reporter exceeded the creation throttle", body = ErrorBody),
+    )
+)]
+pub async fn apply_transaction(
+    State(store): State<AppState>,
+    headers: HeaderMap,
+    RequestJson(request): RequestJson<TransactionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let mut operations = Vec::with_capacity(request.operations.len());
+    for (index, operation) in request.operations.into_iter().enumerate() {
+        let operation = match operation {
+            TransactionOperation::Create { ticket } => {
+                draft_from_request(&store, ticket).map(TicketOperation::Create)
+            }
+            TransactionOperation::Patch { id, changes, force } => {
+                parse_ticket_id(&id).map(|id| TicketOperation::Patch(id, changes, force))
+            }
+            TransactionOperation::Delete { id } => {
+                parse_ticket_id(&id).map(TicketOperation::Delete)
+            }
+            TransactionOperation::Transition { id, status } => {
+                parse_ticket_id(&id).map(|id| TicketOperation::Transition(id, status))
+            }
+        };
+        operations.push(operation.map_err(|e| e.with_detail("operation", index))?);
+    }
+
+    for (index, operation) in operations.iter().enumerate() {
+        if let TicketOperation::Create(TicketDraft {
+            reporter: Some(reporter),
+            ..
+        }) = operation
+        {
+            store
+                .throttle_creation(reporter)
+                .map_err(|e| ApiError::from(e).with_detail("operation", index))?;
+        }
+    }
+
+    let labels: Vec<(&str, Option<TicketId>)> = operations
+        .iter()
+        .map(|operation| match operation {
+            TicketOperation::Create(_) => ("create", None),
+            TicketOperation::Patch(..) => ("patch", None),
+            TicketOperation::Delete(id) => ("delete", Some(id.clone())),
+            TicketOperation::Transition(..) => ("transition", None),
+        })
+        .collect();
+
+    let tickets = store
+        .apply_transaction(operations, request_user(&headers))
+        .await
+        .map_err(|(index, e)| ApiError::from(e).with_detail("operation", index))?;
+
+    let results: Vec<Value> = labels
+        .into_iter()
+        .zip(tickets)
+        .map(|((op, id), ticket)| match ticket {
+            Some(ticket) => json!({
+                "op": op,
+                "ticket": TicketResponse::from(ticket)
+            }),
+            None => json!({
+                "op": op,
+                "id": id
+            }),
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "results": results
+    })))
+}
+
+/// Default page size for [`list_tickets`].
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+
+/// Largest page size [`list_tickets`] will return.
+pub const MAX_PAGE_LIMIT: usize = 500;
+
+/// Query parameters for [`list_tickets`].
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListParams {
+    /// Page size, defaulting to [`DEFAULT_PAGE_LIMIT`] and capped at [`MAX_PAGE_LIMIT`]
+    pub limit: Option<usize>,
+    /// Number of tickets to skip, defaulting to 0
+    pub offset: Option<usize>,
+    /// Only list tickets in this status, e.g. `InProgress`
+    pub status: Option<String>,
+    /// Only list tickets with this priority, e.g. `High`
+    pub priority: Option<String>,
+    /// Only list tickets assigned to this assignee
+    pub assignee: Option<String>,
+    /// Only list tickets with this tag, matched ignoring case
+    pub tag: Option<String>,
+    /// Time budget in milliseconds; when exceeded the tickets read so far are
+    /// returned along with a continuation token
+    pub deadline_ms: Option<u64>,
+    /// Token from a previous partial response to resume listing from
+    pub continuation: Option<String>,
+    /// Cursor from a previous page's `next_cursor`; empty for the first page
+    pub cursor: Option<String>,
+    /// List soft-deleted tickets too
+    #[serde(default)]
+    pub include_deleted: bool,
+    /// Only list open tickets past their due date
+    #[serde(default)]
+    pub overdue: bool,
+    /// Field to order by: `created_at`, `updated_at`, `title`, `status` or `rank`
+    pub sort: Option<String>,
+    /// Sort direction, `asc` (the default) or `desc`
+    pub order: Option<String>,
+    /// Comma-separated ticket fields to return, e.g. `id,title,status`
+    pub fields: Option<String>,
+}
+
+/// Lists tickets in the system, one page at a time.
+///
+/// # Query Parameters
+/// - `limit`: Optional page size (default 50, max 500)
+/// - `offset`: Optional number of tickets to skip (default 0)
+/// - `status`: Optional status to filter by ("ToDo", "InProgress", "Done" or "Cancelled")
+/// - `priority`: Optional priority to filter by ("Low", "Medium", "High" or
+///   "Critical", ignoring case)
+/// - `assignee`: Optional assignee to filter by, matched exactly
+/// - `tag`: Optional tag to filter by, ignoring case
+/// - `deadline_ms`: Optional time budget for the read. If it runs out, the
+///   response holds the tickets gathered so far and a `continuation_token`.
+/// - `continuation`: Optional token to resume a partial listing
+/// - `cursor`: Optional, switches to cursor paging. Empty for the first
+///   page, then the previous response's `next_cursor`.
+/// - `include_deleted`: Optional, `true` to list soft-deleted tickets too
+/// - `overdue`: Optional, `true` to list only tickets whose `due_date` has
+///   passed and that are neither `Done` nor `Cancelled`
+/// - `sort`: Optional field to order by before paging: `created_at`,
+///   `updated_at`, `title` (ignoring case), `status` (in workflow order) or
+///   `rank` (backlog order)
+/// - `order`: Optional sort direction, `asc` (default) or `desc`
+/// - `fields`: Optional comma-separated ticket fields to return, such as
+///   `id,title,status`. `id` is always returned. Other fields are left out
+///   of each ticket, or of its attributes in JSON:API format.
+///
+/// # Returns
+/// - `200 OK` with the page of tickets, ordered by creation time unless
+///   `sort` or `order` says otherwise, along with `total`, `limit` and `offset`.
+///   A `Link` header points to the `first`, `prev`, `next` and `last` pages,
+///   leaving out `prev` on the first page and `next` on the last.
+/// - Returns an empty array if no tickets exist
+/// - `400 Bad Request` if the status, priority, sort, order, continuation
+///   token or cursor is invalid, or `fields` names an unknown field
+/// - `406 Not Acceptable` if the `Accept` header allows none of the formats below
+///
+/// The filters `status`, `priority`, `assignee`, `tag` and `overdue`
+/// combine, so only tickets matching all of the given ones are listed.
+/// Unknown query parameters are ignored.
+///
+/// When `deadline_ms` or `continuation` is given, tickets are instead listed
+/// in ID order without paging or filtering, and the response carries `continuation_token`,
+/// which is `null` once the listing is complete.
+///
+/// When `cursor` is given, live tickets are listed `limit` at a time in
+/// creation order without filtering or sorting, and the response carries
+/// `next_cursor`, which is `null` on the last page. Tickets created or
+/// deleted between pages don't make later pages skip or repeat a ticket.
+/// A `limit` of 0 is rejected with `400 Bad Request` when paging by cursor,
+/// since such a page couldn't move the cursor on.
+///
+/// Responds in JSON:API format when the `Accept` header is
+/// `application/vnd.api+json`. With `text/plain`, the tickets are listed one
+/// `#<id-prefix> [Status] Title` line each, without the paging fields.
+#[utoipa::path(
+    get,
+    path = "/tickets",
+    tag = "tickets",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of tickets", body = TicketPage, headers(("Link" = String, description = "URLs of the first, previous, next and last pages"))),
+        (status = 400, description = "Invalid status, priority, sort, order, continuation token, cursor, limit or fields", body = ErrorBody),
+        (status = 406, description = "No acceptable format", body = ErrorBody),
+    )
+)]
+pub async fn list_tickets(
+    State(store): State<AppState>,
+    Query(params): Query<ListParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = Format::negotiate(&headers)?;
+    let fields = parse_fields(params.fields.as_deref())?;
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .min(MAX_PAGE_LIMIT);
+    if let Some(cursor) = params.cursor.as_deref() {
+        if limit == 0 {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "INVALID_LIMIT",
+                "A cursor page needs a limit of at least 1",
+            ));
+        }
+        let cursor = match cursor {
+            "" => None,
+            cursor => Some(
+                cursor
+                    .parse::<Cursor>()
+                    .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "INVALID_CURSOR", e))?,
+            ),
+        };
+        let (tickets, next) = store.list_after_cursor(cursor, limit).await;
+
+        let responses: Vec<TicketResponse> =
+            tickets.into_iter().map(TicketResponse::from).collect();
+        let next_cursor = next.map(|cursor| cursor.encode());
+        return Ok(match format {
+            Format::JsonApi => {
+                let data = json_api_resources(&responses, fields.as_ref());
+                TicketBody::JsonApi(json!({
+                    "data": data,
+                    "links": {
+                        "self": "/tickets"
+                    },
+                    "meta": {
+                        "limit": limit,
+                        "next_cursor": next_cursor
+                    }
+                }))
+            }
+            Format::Text => TicketBody::Text(text_lines(&responses)),
+            Format::Json => TicketBody::Json(json!({
+                "tickets": ticket_values(&responses, fields.as_ref()),
+                "limit": limit,
+                "next_cursor": next_cursor
+            })),
+        }
+        .into_response());
+    }
+
+    let partial = params.deadline_ms.is_some() || params.continuation.is_some();
+    if partial {
+        let after = match &params.continuation {
+            Some(token) => Some(parse_continuation_token(token)?),
+            None => None,
+        };
+        let deadline = params
+            .deadline_ms
+            .map(|ms| Instant::now() + Duration::from_millis(ms));
+        let (tickets, continuation) = store
+            .list_tickets_partial(after.as_ref(), deadline, params.include_deleted)
+            .await;
+
+        let responses: Vec<TicketResponse> =
+            tickets.into_iter().map(TicketResponse::from).collect();
+        let continuation_token = continuation.map(|id| id.to_string());
+        return Ok(match format {
+            Format::JsonApi => {
+                let data = json_api_resources(&responses, fields.as_ref());
+                TicketBody::JsonApi(json!({
+                    "data": data,
+                    "links": {
+                        "self": "/tickets"
+                    },
+                    "meta": {
+                        "continuation_token": continuation_token
+                    }
+                }))
+            }
+            Format::Text => TicketBody::Text(text_lines(&responses)),
+            Format::Json => TicketBody::Json(json!({
+                "tickets": ticket_values(&responses, fields.as_ref()),
+                "continuation_token": continuation_token
+            })),
+        }
+        .into_response());
+    }
+
+    let offset = params.offset.unwrap_or(0);
+    let filter = parse_filter(&params)?;
+    let sort = parse_sort(params.sort.as_deref(), params.order.as_deref())?;
+
+    if format == Format::Json && fields.is_none() && sort.is_none() {
+        let (mut response, total) = borrowed_page(&store, &filter, limit, offset).await?;
+        if let Some(links) = page_links(&uri, offset, limit, total) {
+            response.headers_mut().insert(header::LINK, links);
+        }
+        return Ok(response);
+    }
+
+    let matching = if filter.is_unfiltered() && sort.is_none() {
+        None
+    } else {
+        Some(store.query(&filter).await)
+    };
+    let (tickets, total) = match matching {
+        Some(mut matching) => {
+            if let Some(sort) = sort {
+                sort.sort(&mut matching);
+            }
+            let total = matching.len();
+            let page = matching.into_iter().skip(offset).take(limit).collect();
+            (page, total)
+        }
+        None => {
+            store
+                .list_tickets_paginated(offset, limit, params.include_deleted)
+                .await
+        }
+    };
+
+    let responses: Vec<TicketResponse> = tickets.into_iter().map(TicketResponse::from).collect();
+    let body = match format {
+        Format::JsonApi => {
+            let data = json_api_resources(&responses, fields.as_ref());
+            TicketBody::JsonApi(json!({
+                "data": data,
+                "links": {
+                    "self": "/tickets"
+                },
+                "meta": {
+                    "total": total,
+                    "limit": limit,
+                    "offset": offset
+                }
+            }))
+        }
+        Format::Text => TicketBody::Text(text_lines(&responses)),
+        Format::Json => TicketBody::Json(json!({
+            "tickets": ticket_values(&responses, fields.as_ref()),
+            "total": total,
+            "limit": limit,
+            "offset": offset
+        })),
+    };
+    let mut response = body.into_response();
+    if let Some(links) = page_links(&uri, offset, limit, total) {
+        response.headers_mut().insert(header::LINK, links);
+    }
+    Ok(response)
+}
+
+/// Renders a plain JSON page of [`list_tickets`] straight from the tickets.
+///
+/// The store serializes each ticket in place, see
+/// [`TicketRepository::write_page_json`], instead of cloning it and turning
+/// it into a [`Value`] first. The body is byte for byte what the cloning
+/// path renders.
+///
+/// # Returns
+/// The response, and the total number of matching tickets
+async fn borrowed_page(
+    store: &AppState,
+    filter: &TicketFilter,
+    limit: usize,
+    offset: usize,
+) -> Result<(Response, usize), ApiError> {
+    let mut body = format!(r#"{{"limit":{},"offset":{},"tickets":"#, limit, offset).into_bytes();
+    let total = store
+        .write_page_json(filter, offset, limit, &mut body)
+        .await
+        .map_err(ApiError::internal)?;
+    body.extend_from_slice(format!(r#","total":{}}}"#, total).as_bytes());
+
+    let response = ([(header::CONTENT_TYPE, "application/json")], body).into_response();
+    Ok((response, total))
+}
+
+/// Builds the `Link` header (RFC 8288) of an offset-paged listing.
+///
+/// Each link is the request's own path and query with `offset` and `limit`
+/// replaced, so the links keep the client's filters and any path prefix.
+/// `first` and `last` are always given, `prev` only after the first page and
+/// `next` only before the last. A `limit` of 0 pages nowhere, so gets none.
+fn page_links(uri: &Uri, offset: usize, limit: usize, total: usize) -> Option<HeaderValue> {
+    if limit == 0 {
+        return None;
+    }
+    let kept: Vec<&str> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && key != "offset" && key != "limit"
+        })
+        .collect();
+    let link = |offset: usize, rel: &str| {
+        let mut query = kept.clone();
+        let paging = format!("limit={}&offset={}", limit, offset);
+        query.push(&paging);
+        format!("<{}?{}>; rel=\"{}\"", uri.path(), query.join("&"), rel)
+    };
+
+    let last = total.saturating_sub(1) / limit * limit;
+    let mut links = vec![link(0, "first")];
+    if offset > 0 {
+        links.push(link(offset.saturating_sub(limit).min(last), "prev"));
+    }
+    if offset + limit < total {
+        links.push(link(offset + limit, "next"));
+    }
+    links.push(link(last, "last"));
+    HeaderValue::from_str(&links.join(", ")).ok()
+}
+
+/// Parses the filtering parameters of [`list_tickets`] into one filter.
+fn parse_filter(params: &ListParams) -> Result<TicketFilter, ApiError> {
+    let (status, priority) =
+        parse_status_and_priority(params.status.as_deref(), params.priority.as_deref())?;
+
+    Ok(TicketFilter {
+        status,
+        priority,
+        assignee: params.assignee.clone(),
+        tag: params.tag.clone(),
+        overdue_at: params.overdue.then(Utc::now),
+        include_deleted: params.include_deleted,
+    })
+}
+
+/// Parses the `status` and `priority` filter parameters.
+fn parse_status_and_priority(
+    status: Option<&str>,
+    priority: Option<&str>,
+) -> Result<(Option<Status>, Option<Priority>), ApiError> {
+    let status = status
+        .map(str::parse::<Status>)
+        .transpose()
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "INVALID_STATUS_FILTER", e))?;
+    let priority = priority
+        .map(str::parse::<Priority>)
+        .transpose()
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "INVALID_PRIORITY_FILTER", e))?;
+    Ok((status, priority))
+}
+
+/// Parses the `sort` and `order` parameters of [`list_tickets`].
+///
+/// Giving only `order` sorts by `created_at`. Returns `None` when neither is
+/// given, leaving the default order.
+fn parse_sort(sort: Option<&str>, order: Option<&str>) -> Result<Option<SortSpec>, ApiError> {
+    if sort.is_none() && order.is_none() {
+        return Ok(None);
+    }
+    let invalid = |e: String| ApiError::new(StatusCode::BAD_REQUEST, "INVALID_SORT", e);
+    let field = match sort {
+        Some(sort) => sort.parse::<SortField>().map_err(invalid)?,
+        None => SortField::CreatedAt,
+    };
+    let order = match order {
+        Some(order) => order.parse::<SortOrder>().map_err(invalid)?,
+        None => SortOrder::default(),
+    };
+    Ok(Some(SortSpec { field, order }))
+}
+
+/// Parses a continuation token returned by a partial [`list_tickets`] response.
+///
+/// The token is the ID of the last ticket that was returned.
+fn parse_continuation_token(token: &str) -> Result<TicketId, ApiError> {
+    parse_ticket_id(token).map_err(|_| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "INVALID_CONTINUATION_TOKEN",
+            "Invalid continuation token",
+        )
+    })
+}
+
+/// Query parameters for [`search_tickets`] and [`search_all`].
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchParams {
+    /// Text to look for
+    pub q: Option<String>,
+}
+
+impl SearchParams {
+    /// Takes the query text out of the parameters.
+    ///
+    /// # Returns
+    /// * `Err(ApiError)` - `400 INVALID_QUERY` if `q` is missing, empty or
+    ///   only whitespace
+    fn into_query(self) -> Result<String, ApiError> {
+        match self.q {
+            Some(q) if !q.trim().is_empty() => Ok(q),
+            _ => Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "INVALID_QUERY",
+                "Query parameter 'q' cannot be empty",
+            )),
+        }
+    }
+}
+
+/// Finds tickets whose title or description contains the query text.
+///
+/// # Query Parameters
+/// - `q`: Text to match, case-insensitively, as a substring
+///
+/// # Returns
+/// - `200 OK` with the matching tickets, ordered by creation time, then ID
+/// - `400 Bad Request` if `q` is missing, empty or only whitespace
+#[utoipa::path(
+    get,
+    path = "/tickets/search",
+    tag = "tickets",
+    params(SearchParams),
+    responses(
+        (status = 200, description = "Matching tickets", body = TicketList),
+        (status = 400, description = "Missing or empty query", body = ErrorBody),
+    )
+)]
+pub async fn search_tickets(
+    State(store): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Value>, ApiError> {
+    let query = params.into_query()?;
+
+    let tickets = store.search(&query).await;
+    let responses: Vec<TicketResponse> = tickets.into_iter().map(TicketResponse::from).collect();
+    Ok(Json(json!({
+        "tickets": responses
+    })))
+}
+
+/// Finds tickets whose title, description or comments contain the query text.
+///
+/// Each ticket comes with its matching comments and a snippet of each,
+/// the match wrapped in `<mark>`.
+///
+/// # Query Parameters
+/// - `q`: Text to match, case-insensitively, as a substring
+///
+/// # Returns
+/// - `200 OK` with the matching tickets, ordered by creation time, then ID
+/// - `400 Bad Request` if `q` is missing, empty or only whitespace
+#[utoipa::path(
+    get,
+    path = "/tickets/search-all",
+    tag = "tickets",
+    params(SearchParams),
+    responses(
+        (status = 200, description = "Matching tickets and comments", body = SearchResults),
+        (status = 400, description = "Missing or empty query", body = ErrorBody),
+    )
+)]
+pub async fn search_all(
+    State(store): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Value>, ApiError> {
+    let query = params.into_query()?;
+
+    let results: Vec<Value> = store
+        .search_all(&query)
+        .await
+        .into_iter()
+        .map(|(ticket, comments)| {
+            json!({
+                "ticket": TicketResponse::from(ticket),
+                "comments": comments,
+            })
+        })
+        .collect();
+    Ok(Json(json!({
+        "results": results
+    })))
+}
+
+/// Query parameters for [`diff_tickets`].
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiffParams {
+    /// UUID string of the first ticket
+    pub a: String,
+    /// UUID string of the second ticket
+    pub b: String,
+}
+
+/// Compares two tickets field by field.
+///
+/// # Query Parameters
+/// - `a`: UUID string of the first ticket
+/// - `b`: UUID string of the second ticket
+///
+/// # Returns
+/// - `200 OK` with the list of differing fields and their values on each ticket
+/// - `400 Bad Request` if either UUID is invalid
+/// - `404 Not Found` if either ticket doesn't exist or is soft-deleted
+#[utoipa::path(
+    get,
+    path = "/tickets/diff",
+    tag = "tickets",
+    params(DiffParams),
+    responses(
+        (status = 200, description = "The differing fields", body = TicketDiff),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "A ticket doesn't exist", body = ErrorBody),
+    )
+)]
+pub async fn diff_tickets(
+    State(store): State<AppState>,
+    Query(params): Query<DiffParams>,
+) -> Result<Json<Value>, ApiError> {
+    let mut tickets = Vec::with_capacity(2);
+    for id in [&params.a, &params.b] {
+        let ticket_id = parse_ticket_id(id)?;
+        let ticket = store.get_ticket(&ticket_id).await?;
+        if ticket.is_deleted() {
+            return Err(StoreError::TicketNotFound(ticket_id).into());
+        }
+        tickets.push(ticket);
+    }
+
+    Ok(Json(json!({
+        "a": tickets[0].id,
+        "b": tickets[1].id,
+        "differences": tickets[0].diff(&tickets[1])
+    })))
+}
+
+/// Query parameters for [`lead_time`].
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LeadTimeParams {
+    /// Start of the completion window (RFC 3339), inclusive
+    pub from: Option<DateTime<Utc>>,
+    /// End of the completion window (RFC 3339), exclusive
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Reports lead time statistics for tickets completed in a window.
+///
+/// Lead time runs from a ticket's creation to its completion. Tickets that
+/// aren't `Done` are excluded.
+///
+/// # Query Parameters
+/// - `from`: Optional RFC 3339 timestamp, inclusive
+/// - `to`: Optional RFC 3339 timestamp, exclusive
+///
+/// # Returns
+/// - `200 OK` with the `count`, `average_seconds`, `median_seconds` and
+///   `p90_seconds`. The figures are `null` when no ticket was completed.
+/// - `400 Bad Request` if `from` is after `to`
+#[utoipa::path(
+    get,
+    path = "/tickets/lead-time",
+    tag = "tickets",
+    params(LeadTimeParams),
+    responses(
+        (status = 200, description = "Lead time statistics", body = LeadTimeStats),
+        (status = 400, description = "`from` is after `to`", body = ErrorBody),
+    )
+)]
+pub async fn lead_time(
+    State(store): State<AppState>,
+    Query(params): Query<LeadTimeParams>,
+) -> Result<Json<Value>, ApiError> {
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from > to {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "INVALID_WINDOW",
+                "'from' cannot be after 'to'",
+            ));
+        }
+    }
+
+    let stats = store.lead_time_stats(params.from, params.to).await;
+    Ok(Json(json!(stats)))
+}
+
+/// Counts tickets in total and per status.
+///
+/// Cheap enough to poll: no ticket is cloned.
+///
+/// # Returns
+/// - `200 OK` with the `total`, a `by_status` object and the
+///   `total_estimate` of the tickets not yet `Done`. Every status is
+///   present, with zero for empty ones.
+#[utoipa::path(
+    get,
+    path = "/tickets/count",
+    tag = "tickets",
+    responses(
+        (status = 200, description = "Ticket counts", body = TicketCount),
+    )
+)]
+pub async fn count_tickets(State(store): State<AppState>) -> Json<Value> {
+    let (total, by_status) = store.count_by_status().await;
+    let total_estimate = store.total_estimate().await;
+    Json(json!({
+        "total": total,
+        "by_status": by_status,
+        "total_estimate": total_estimate
+    }))
+}
+
+/// Counts tickets per assignee, for a roster view.
+///
+/// # Returns
+/// - `200 OK` with an `assignees` array of `{assignee, total, by_status}`
+///   objects ordered by assignee name. Unassigned tickets come last, under a
+///   `null` assignee, when there are any. Every status is present in
+///   `by_status`, with zero for empty ones.
+#[utoipa::path(
+    get,
+    path = "/tickets/assignees",
+    tag = "tickets",
+    responses(
+        (status = 200, description = "Ticket counts per assignee", body = AssigneeList),
+    )
+)]
+pub async fn list_assignees(State(store): State<AppState>) -> Json<Value> {
+    let assignees = store.assignee_summary().await;
+    Json(json!({ "assignees": assignees }))
+}
+
+/// Counts tickets in every priority and status combination.
+///
+/// # Returns
+/// - `200 OK` with a nested object of counts keyed by priority, then status.
+///   Every combination is present, with zero for empty cells.
+#[utoipa::path(
+    get,
+    path = "/tickets/matrix",
+    tag = "tickets",
+    responses(
+        (status = 200, description = "Counts keyed by priority, then status", body = Object),
+    )
+)]
+pub async fn ticket_matrix(State(store): State<AppState>) -> Json<Value> {
+    let matrix = store.count_by_priority_and_status().await;
+    Json(json!(matrix))
+}
+
+/// Describes a ticket a bulk update couldn't change.
+///
+/// The `outcome` groups the failure for bulk clients, while `code`,
+/// `message` and any extra fields are what the single-ticket endpoint
+/// would answer with.
+fn bulk_failure(id: &str, error: StoreError) -> Value {
+    let outcome = match &error {
+        StoreError::TicketNotFound(_) => "not_found",
+        StoreError::InvalidField(_)
+        | StoreError::ValidationFailed(_)
+        | StoreError::MissingRequiredFields { .. } => "invalid",
+        StoreError::InvalidTransition { .. }
+        | StoreError::NotDone(_)
+        | StoreError::StatusMismatch { .. } => "invalid_transition",
+        StoreError::StatusOwnerOnly { .. } => "forbidden",
+        StoreError::Timeout(..) => "busy",
+        StoreError::RateLimited(_)
+        | StoreError::VersionConflict { .. }
+        | StoreError::DescriptionShrink { .. }
+        | StoreError::ChangesExpired { .. }
+        | StoreError::TagNotFound(_)
+        | StoreError::CapacityExceeded { .. }
+        | StoreError::IdempotencyKeyReused(_)
+        | StoreError::NothingToUndo(_)
+        | StoreError::IdCollision(_) => "failed",
+    };
+    let error = ApiError::from(error);
+
+    let mut result = error.details;
+    result.insert("id".to_string(), json!(id));
+    result.insert("outcome".to_string(), json!(outcome));
+    result.insert("code".to_string(), json!(error.code));
+    result.insert("message".to_string(), json!(error.message));
+    Value::Object(result)
+}
+
+/// Updates custom fields on many tickets in one store operation.
+///
+/// # Request Body
+/// JSON object with:
+/// - `ids`: Array of UUID strings
+/// - `set`: Optional object of custom fields to add or overwrite
+/// - `unset`: Optional array of custom field names to remove
+/// - `atomic`: Optional bool. When true, either every ticket is updated or
+///   none is, and the store is locked against other changes meanwhile.
+///
+/// # Returns
+/// - `200 OK` with one result per ID, in input order. Each result has the
+///   `id`, an `outcome` (`updated`, `invalid_id`, `not_found`, `invalid`, or
+///   `not_applied` for an atomic batch rolled back by another item's failure)
+///   and either the updated `ticket` or an error `message`. A failed ticket
+///   also gets the `code` that [`patch_ticket`] would answer with.
+#[utoipa::path(
+    post,
+    path = "/tickets/bulk-custom",
+    tag = "tickets",
+    request_body = BulkCustomFieldsRequest,
+    responses(
+        (status = 200, description = "One result per ID", body = BulkResults),
+    )
+)]
+pub async fn bulk_update_custom_fields(
+    State(store): State<AppState>,
+    RequestJson(request): RequestJson<BulkCustomFieldsRequest>,
+) -> Json<Value> {
+    let parsed: Vec<Option<TicketId>> = request
+        .ids
+        .iter()
+        .map(|id| parse_ticket_id(id).ok())
+        .collect();
+    let valid_ids: Vec<TicketId> = parsed.iter().flatten().cloned().collect();
+
+    let (outcomes, applied) = if !request.atomic {
+        let outcomes = store
+            .bulk_update_custom_fields(&valid_ids, &request.set, &request.unset)
+            .await;
+        (outcomes, true)
+    } else if valid_ids.len() < parsed.len() {
+        // An unparseable ID already dooms the batch, so don't lock the store
+        (Vec::new(), false)
+    } else {
+        match store
+            .bulk_update_custom_fields_atomic(&valid_ids, &request.set, &request.unset)
+            .await
+        {
+            Ok(tickets) => (tickets.into_iter().map(Ok).collect(), true),
+            Err(outcomes) => (outcomes, false),
+        }
+    };
+    let mut outcomes = outcomes.into_iter();
+
+    let results: Vec<Value> = request
+        .ids
+        .iter()
+        .zip(parsed)
+        .map(|(id, ticket_id)| {
+            if ticket_id.is_none() {
+                return json!({
+                    "id": id,
+                    "outcome": "invalid_id",
+                    "message": "Invalid ticket ID format"
+                });
+            }
+            match (outcomes.next(), applied) {
+                (Some(Ok(ticket)), true) => json!({
+                    "id": id,
+                    "outcome": "updated",
+                    "ticket": TicketResponse::from(ticket)
+                }),
+                (Some(Err(error)), _) => bulk_failure(id, error),
+                (Some(Ok(_)), false) | (None, _) => json!({
+                    "id": id,
+                    "outcome": "not_applied",
+                    "message": "Not applied because another ticket in the atomic batch failed"
+                }),
+            }
+        })
+        .collect();
+
+    Json(json!({
+        "results": results
+    }))
+}
+
+/// Moves many tickets to the same status in one store operation.
+///
+/// Each ticket makes the transition independently, so the ones that can
+/// are updated even when others fail.
+///
+/// # Request Body
+/// JSON object with:
+/// - `ids`: Array of UUID strings
+/// - `status`: The status to move every ticket to
+///
+/// # Headers
+/// - `X-User`: Optional name of the user making the change
+///
+/// # Returns
+/// - `200 OK` with one result per ID, in input order. Each result has the
+///   `id`, an `outcome` (`updated`, `invalid_id`, `not_found`,
+///   `invalid_transition`, `forbidden`, `busy` if the ticket stayed locked
+///   too long, or `failed`) and either the updated `ticket` or an error
+///   `message`. A ticket already in the status counts as
+///   `updated`. `forbidden` means only the ticket's assignee may change its
+///   status (see [`patch_ticket`]). A failed ticket also gets the `code`
+///   and extra fields that [`transition_ticket`] would answer with.
+#[utoipa::path(
+    post,
+    path = "/tickets/bulk-status",
+    tag = "tickets",
+    params(("X-User" = Option<String>, Header, description = "User making the change")),
+    request_body = BulkStatusRequest,
+    responses(
+        (status = 200, description = "One result per ID", body = BulkResults),
+    )
+)]
+pub async fn bulk_update_status(
+    State(store): State<AppState>,
+    headers: HeaderMap,
+    RequestJson(request): RequestJson<BulkStatusRequest>,
+) -> Json<Value> {
+    let parsed: Vec<Option<TicketId>> = request
+        .ids
+        .iter()
+        .map(|id| parse_ticket_id(id).ok())
+        .collect();
+    let valid_ids: Vec<TicketId> = parsed.iter().flatten().cloned().collect();
+    let mut outcomes = store
+        .bulk_update_status(&valid_ids, request.status, request_user(&headers))
+        .await
+        .into_iter();
+
+    let results: Vec<Value> = request
+        .ids
+        .iter()
+        .zip(parsed)
+        .map(|(id, ticket_id)| {
+            if ticket_id.is_none() {
+                return json!({
+                    "id": id,
+                    "outcome": "invalid_id",
+                    "message": "Invalid ticket ID format"
+                });
+            }
+            match outcomes.next() {
+                Some(Ok(ticket)) => json!({
+                    "id": id,
+                    "outcome": "updated",
+                    "ticket": TicketResponse::from(ticket)
+                }),
+                Some(Err(error)) => bulk_failure(id, error),
+                None => json!({
+                    "id": id,
+                    "outcome": "not_found",
+                    "message": "Ticket not found"
+                }),
+            }
+        })
+        .collect();
+
+    Json(json!({
+        "results": results
+    }))
+}
+
+/// Query parameters for [`ticket_events`].
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventParams {
+    /// Only events about tickets in this status
+    pub status: Option<String>,
+    /// Only events about tickets with this priority
+    pub priority: Option<String>,
+    /// Only events about tickets assigned to exactly this assignee
+    pub assignee: Option<String>,
+    /// Only events about tickets with this tag, matched ignoring case
+    pub tag: Option<String>,
+}
+
+/// Seconds a client turned away by the event subscriber cap is told to wait.
+pub const EVENT_SUBSCRIBERS_RETRY_AFTER_SECS: u64 = 5;
+
+/// Streams ticket events as Server-Sent Events.
+///
+/// Each event's data is a JSON [`TicketEvent`](crate::data::TicketEvent),
+/// e.g. `{"type":"Updated","id":"..."}`. Subscribers only see events from
+/// the moment they connect. A subscriber that falls too far behind skips the
+/// events it missed instead of being disconnected.
+///
+/// The filter parameters narrow the stream to events about matching
+/// tickets. Each event is matched against its ticket as it is when the
+/// event is sent, soft-deleted tickets included, so a `Deleted` event still
+/// reaches subscribers of the deleted ticket. Events about tickets removed
+/// for good in the meantime are skipped.
+///
+/// With [`ServerConfig::max_event_subscribers`](crate::config::ServerConfig::max_event_subscribers)
+/// set, each open stream holds one of that many slots until the client
+/// disconnects.
+///
+/// # Query Parameters
+/// - `status`: Optional, only events about tickets in this status
+/// - `priority`: Optional, only events about tickets with this priority
+/// - `assignee`: Optional, only events about tickets assigned to exactly
+///   this assignee
+/// - `tag`: Optional, only events about tickets with this tag, ignoring case
+///
+/// # Returns
+/// - `200 OK` with a `text/event-stream` body that stays open
+/// - `400 Bad Request` if the status or priority isn't valid, before the
+///   stream starts
+/// - `503 Service Unavailable` with a `Retry-After` header if every
+///   subscriber slot is taken
+#[utoipa::path(
+    get,
+    path = "/tickets/events",
+    tag = "tickets",
+    params(EventParams),
+    responses(
+        (status = 200, description = "Stream of ticket events", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Invalid status or priority filter", body = ErrorBody),
+        (status = 503, description = "Too many subscribers", body = ErrorBody,
+            headers(("Retry-After" = u64, description = "Seconds to wait before subscribing again"))),
+    )
+)]
+pub async fn ticket_events(
+    State((store, subscribers)): State<(AppState, Option<Arc<Semaphore>>)>,
+    Query(params): Query<EventParams>,
+) -> Result<Response, ApiError> {
+    let (status, priority) =
+        parse_status_and_priority(params.status.as_deref(), params.priority.as_deref())?;
+    let filter = TicketFilter {
+        status,
+        priority,
+        assignee: params.assignee,
+        tag: params.tag,
+        overdue_at: None,
+        include_deleted: true,
+    };
+    // Held by the stream, so the slot frees up when the client disconnects
+    let permit = match subscribers.map(Semaphore::try_acquire_owned) {
+        Some(Err(_)) => {
+            let mut response = ApiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "TOO_MANY_SUBSCRIBERS",
+                "Too many clients are streaming events, try again later",
+            )
+            .with_detail("retry_after", EVENT_SUBSCRIBERS_RETRY_AFTER_SECS)
+            .into_response();
+            response.headers_mut().insert(
+                header::RETRY_AFTER,
+                HeaderValue::from(EVENT_SUBSCRIBERS_RETRY_AFTER_SECS),
+            );
+            return Ok(response);
+        }
+        Some(Ok(permit)) => Some(permit),
+        None => None,
+    };
+
+    let receiver = store.subscribe();
+    let state = (receiver, store, filter, permit);
+    let events = stream::unfold(state, |(mut receiver, store, filter, permit)| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    if !filter.is_unfiltered() {
+                        let matches = store
+                            .get_ticket(event.id())
+                            .await
+                            .is_ok_and(|ticket| filter.matches(&ticket));
+                        if !matches {
+                            continue;
+                        }
+                    }
+                    if let Ok(sse_event) = Event::default().json_data(&event) {
+                        return Some((
+                            Ok::<_, Infallible>(sse_event),
+                            (receiver, store, filter, permit),
+                        ));
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event subscriber lagged, skipping {} events", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+/// Longest a client may ask [`wait_ticket`] to wait, in seconds.
+pub const MAX_WAIT_SECONDS: u64 = 300;
+
+/// Query parameters for [`wait_ticket`].
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WaitParams {
+    /// The version the client already has
+    pub since: u64,
+    /// Seconds to wait for a change, 30 by default and at most 300
+    pub timeout: Option<u64>,
+}
+
+/// Waits for a ticket to change, as a long poll.
+///
+/// Returns as soon as the ticket's version is past `since`, which is right
+/// away when the client is already behind. Otherwise the request is held
+/// open and woken by the store's events until the ticket changes or the
+/// timeout runs out.
+///
+/// # Path Parameters
+/// - `id`: UUID of the ticket
+///
+/// # Query Parameters
+/// - `since`: The version the client already has
+/// - `timeout`: Optional, seconds to wait, 30 by default and at most
+///   [`MAX_WAIT_SECONDS`]
+///
+/// # Returns
+/// - `200 OK` with the ticket once its version exceeds `since`
+/// - `304 Not Modified` with no body if the ticket didn't change in time
+/// - `400 Bad Request` if the UUID is invalid or `since` is missing
+/// - `404 Not Found` if no ticket matches the UUID, or it is or becomes
+///   soft-deleted
+/// - `500 Internal Server Error` on unexpected errors
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}/wait",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), WaitParams),
+    responses(
+        (status = 200, description = "The changed ticket", body = TicketResponse),
+        (status = 304, description = "No change before the timeout"),
+        (status = 400, description = "Invalid UUID or query", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn wait_ticket(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<WaitParams>,
+) -> Result<Response, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let timeout = Duration::from_secs(params.timeout.unwrap_or(30).min(MAX_WAIT_SECONDS));
+    let deadline = Instant::now() + timeout;
+
+    // Subscribe before the first read, so a change in between isn't missed
+    let mut receiver = store.subscribe();
+    loop {
+        let ticket = store.get_ticket(&ticket_id).await?;
+        if ticket.is_deleted() {
+            return Err(StoreError::TicketNotFound(ticket_id).into());
+        }
+        if ticket.version > params.since {
+            let sla = store.sla_status(&ticket);
+            let response = TicketResponse {
+                sla,
+                ..TicketResponse::from(ticket)
+            };
+            return Ok(Json(response).into_response());
+        }
+
+        // Sleep until an event for this ticket, then read it again
+        loop {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Err(_) | Ok(Err(RecvError::Closed)) => {
+                    return Ok(StatusCode::NOT_MODIFIED.into_response());
+                }
+                Ok(Err(RecvError::Lagged(_))) => break,
+                Ok(Ok(event)) if event.id() == &ticket_id => break,
+                Ok(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+/// Query parameters for [`list_changes`].
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChangesParams {
+    /// The last sequence the client has seen; 0 or absent for every retained change
+    #[serde(default)]
+    pub since: u64,
+}
+
+/// Lists the changes made after a sequence number, for incremental sync.
+///
+/// Every mutation gets the next sequence number, so a client can poll with
+/// the `sequence` of its previous response and apply just the difference.
+/// Each change is a [`ChangeRecord`](crate::data::ChangeRecord), e.g.
+/// `{"sequence":7,"type":"Updated","id":"...","fields":["title"],"at":"..."}`.
+///
+/// # Query Parameters
+/// - `since`: The last sequence the client has seen, defaults to 0
+///
+/// # Returns
+/// - `200 OK` with `{"changes": [...], "sequence": <current>}`, oldest change first
+/// - `410 Gone` with code `CHANGES_EXPIRED` if changes after `since` have
+///   fallen out of the retained log; the client must refetch everything
+#[utoipa::path(
+    get,
+    path = "/tickets/changes",
+    tag = "tickets",
+    params(ChangesParams),
+    responses(
+        (status = 200, description = "Changes after `since`", body = ChangeFeed),
+        (status = 410, description = "Changes after `since` are no longer retained", body = ErrorBody),
+    )
+)]
+pub async fn list_changes(
+    State(store): State<AppState>,
+    Query(params): Query<ChangesParams>,
+) -> Result<Json<Value>, ApiError> {
+    let (changes, sequence) = store.changes_since(params.since)?;
+    Ok(Json(json!({
+        "changes": changes,
+        "sequence": sequence
+    })))
+}
+
+/// Lists all tickets with changes not yet pulled by the sync client.
+///
+/// # Returns
+/// - `200 OK` with an array of all tickets in the `Pending` sync state,
+///   soft-deleted ones included with their `deleted_at`
+#[utoipa::path(
+    get,
+    path = "/tickets/pending-sync",
+    tag = "tickets",
+    responses(
+        (status = 200, description = "Tickets not yet synced", body = TicketList),
+    )
+)]
+pub async fn list_pending_sync(State(store): State<AppState>) -> Json<Value> {
+    let tickets = store.list_pending_sync().await;
+    let responses: Vec<TicketResponse> = tickets.into_iter().map(TicketResponse::from).collect();
+    Json(json!({
+        "tickets": responses
+    }))
+}
+
+/// Marks a ticket as synced with the offline client.
+///
+/// The ticket's version and `updated_at` stay put, and no change is
+/// recorded. Soft-deleted tickets can be marked too.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to mark
+///
+/// # Returns
+/// - `200 OK` with the ticket in its `Synced` state
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/mark-synced",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 200, description = "The synced ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn mark_synced(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let ticket = store.mark_synced(&ticket_id).await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
+}
+
+/// Adds a tag to a ticket.
+///
+/// Tags are lowercased, so adding `Backend` to a ticket tagged `backend`
+/// changes nothing.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to tag
+///
+/// # Request Body
+/// JSON object with a `tag` field
+///
+/// # Returns
+/// - `200 OK` with the ticket, whether or not it already had the tag
+/// - `400 Bad Request` if the UUID or the tag is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/tags",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    request_body = AddTagRequest,
+    responses(
+        (status = 200, description = "The tagged ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID or tag", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn add_tag(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    RequestJson(request): RequestJson<AddTagRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let tag = TicketTag::new(request.tag)
+        .map_err(|message| ApiError::new(StatusCode::BAD_REQUEST, "INVALID_TAG", message))?;
+
+    let ticket = store.add_tag(&ticket_id, tag).await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
+}
+
+/// Appends a comment to a ticket.
+///
+/// Comments can't be edited or removed once added.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to comment on
+///
+/// # Request Body
+/// JSON object with:
+/// - `author`: String (1-80 characters)
+/// - `body`: String (1-500 characters)
+///
+/// # Returns
+/// - `201 Created` with the comment, including its `id` and `created_at`
+/// - `400 Bad Request` if the UUID is invalid, or listing every invalid field
+///   under `errors`
+/// - `404 Not Found` if no ticket matches the UUID
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/comments",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    request_body = AddCommentRequest,
+    responses(
+        (status = 201, description = "The comment", body = Comment),
+        (status = 400, description = "Invalid UUID or fields", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn add_comment(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    RequestJson(request): RequestJson<AddCommentRequest>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let draft = request
+        .validate()
+        .map_err(|errors| ApiError::validation_failed(json!(errors)))?;
+
+    let comment = store.add_comment(&ticket_id, draft).await?;
+    Ok((StatusCode::CREATED, Json(json!(comment))))
+}
+
+/// Appends many comments to a ticket in one request, e.g. to import a thread.
+///
+/// Every item is validated like in [`add_comment`]. Invalid items are
+/// reported and skipped; the valid ones are appended together, in order.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to comment on
+///
+/// # Request Body
+/// A JSON array, at most [`MAX_BATCH_SIZE`] long, of objects with:
+/// - `author`: String (1-80 characters)
+/// - `body`: String (1-500 characters)
+/// - `created_at`: Optional RFC 3339 time the comment was written, not in the
+///   future; defaults to now
+///
+/// # Returns
+/// - `200 OK` with the added comments under `comments`, in input order, and
+///   one entry per rejected item under `errors`, holding its `index` and
+///   `error`
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+/// - `413 Payload Too Large` if the array has more than [`MAX_BATCH_SIZE`] items
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/comments/batch",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    request_body = Vec<ImportCommentRequest>,
+    responses(
+        (status = 200, description = "Added comments and rejected items", body = CommentsImported),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 413, description = "Too many items", body = ErrorBody),
+    )
+)]
+pub async fn add_comments(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+    RequestJson(items): RequestJson<Vec<Value>>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    if items.len() > MAX_BATCH_SIZE {
+        return Err(ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "BATCH_TOO_LARGE",
+            format!(
+                "A batch holds at most {} comments, got {}",
+                MAX_BATCH_SIZE,
+                items.len()
+            ),
+        ));
+    }
+
+    let mut drafts = Vec::with_capacity(items.len());
+    let mut errors = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let draft = from_json_value::<ImportCommentRequest>(item).and_then(|request| {
+            request
+                .validate()
+                .map_err(|errors| ApiError::validation_failed(json!(errors)))
+        });
+        match draft {
+            Ok(draft) => drafts.push(draft),
+            Err(error) => errors.push(json!({
+                "index": index,
+                "error": error.to_json()
+            })),
+        }
+    }
+
+    let comments = store.add_comments(&ticket_id, drafts).await?;
+    Ok(Json(json!({
+        "comments": comments,
+        "errors": errors
+    })))
+}
+
+/// Lists a ticket's comments.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket
+///
+/// # Returns
+/// - `200 OK` with `{"comments": [...]}`, oldest first
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}/comments",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 200, description = "The ticket's comments", body = CommentList),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn list_comments(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let comments = store.list_comments(&ticket_id).await?;
+    Ok(Json(json!({
+        "comments": comments
+    })))
+}
+
+/// Lists a ticket's field changes.
+///
+/// Each entry names one changed `field` with its `old` and `new` values and
+/// when it changed (`at`). Soft-deleted tickets keep their history.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket
+///
+/// # Returns
+/// - `200 OK` with `{"history": [...]}`, oldest first
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}/history",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 200, description = "The ticket's field changes", body = HistoryList),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn ticket_history(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let history = store.get_history(&ticket_id).await?;
+    Ok(Json(json!({
+        "history": history
+    })))
+}
+
+/// Reports how long a ticket has spent in each status.
+///
+/// Computed from the ticket's history: every stay in a status adds up, and
+/// the status the ticket is in now counts up to the moment of the request.
+/// Soft-deleted tickets keep their report, like their history.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket
+///
+/// # Returns
+/// - `200 OK` with `{"statuses": [{"status", "seconds"}, ...]}`, one entry
+///   per status, zero for statuses the ticket was never in
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+/// - `503 Service Unavailable` if the ticket stayed locked for too long
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}/time-in-status",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 200, description = "Time spent in each status", body = TimeInStatusReport),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+        (status = 503, description = "The ticket stayed locked too long", body = ErrorBody),
+    )
+)]
+pub async fn time_in_status(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let statuses = store.time_in_status(&ticket_id).await?;
+    Ok(Json(json!({
+        "statuses": statuses
+    })))
+}
+
+/// Removes a tag from a ticket.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the ticket to untag
+/// - `tag`: The tag to remove, matched ignoring case
+///
+/// # Returns
+/// - `200 OK` with the ticket without the tag
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID or the ticket doesn't have the tag
+#[utoipa::path(
+    delete,
+    path = "/tickets/{id}/tags/{tag}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("tag" = String, Path, description = "Tag to remove")),
+    responses(
+        (status = 200, description = "The ticket without the tag", body = TicketResponse),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID, or it doesn't have the tag", body = ErrorBody),
+    )
+)]
+pub async fn remove_tag(
+    State(store): State<AppState>,
+    Path((id, tag)): Path<(String, String)>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let ticket = store.remove_tag(&ticket_id, &tag).await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
+}
+
+/// Records that one ticket blocks another.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the blocking ticket
+/// - `other`: UUID string of the ticket it blocks
+///
+/// # Returns
+/// - `200 OK` with the blocking ticket, whether or not it already blocked `other`
+/// - `400 Bad Request` if a UUID is invalid, `other` doesn't exist or is the
+///   ticket itself, or `other` already blocks the ticket, directly or indirectly
+/// - `404 Not Found` if no ticket matches `id`
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/block/{other}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("other" = String, Path, description = "UUID of the blocked ticket")),
+    responses(
+        (status = 200, description = "The blocking ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID, unknown `other` or a cycle", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn block_ticket(
+    State(store): State<AppState>,
+    Path((id, other)): Path<(String, String)>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let other_id = parse_ticket_id(&other)?;
+
+    let ticket = store.add_block(&ticket_id, &other_id).await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
+}
+
+/// Removes a blocking link between two tickets.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the blocking ticket
+/// - `other`: UUID string of the ticket it blocks
+///
+/// # Returns
+/// - `200 OK` with the ticket, whether or not it blocked `other`
+/// - `400 Bad Request` if a UUID is invalid
+/// - `404 Not Found` if no ticket matches `id`
+#[utoipa::path(
+    delete,
+    path = "/tickets/{id}/block/{other}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("other" = String, Path, description = "UUID of the blocked ticket")),
+    responses(
+        (status = 200, description = "The ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn unblock_ticket(
+    State(store): State<AppState>,
+    Path((id, other)): Path<(String, String)>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let other_id = parse_ticket_id(&other)?;
+
+    let ticket = store.remove_block(&ticket_id, &other_id).await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
+}
+
+/// Makes a ticket part of another one.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the child ticket
+/// - `parent`: UUID string of its new parent
+///
+/// # Returns
+/// - `200 OK` with the child ticket
+/// - `400 Bad Request` if a UUID is invalid, the parent doesn't exist, is the
+///   ticket itself or descends from it
+/// - `404 Not Found` if no ticket matches `id`
+#[utoipa::path(
+    post,
+    path = "/tickets/{id}/parent/{parent}",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket"), ("parent" = String, Path, description = "UUID of the parent ticket")),
+    responses(
+        (status = 200, description = "The child ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID, unknown parent or a cycle", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn set_parent(
+    State(store): State<AppState>,
+    Path((id, parent)): Path<(String, String)>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+    let parent_id = parse_ticket_id(&parent)?;
+
+    let ticket = store.set_parent(&ticket_id, Some(&parent_id)).await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
+}
+
+/// Detaches a ticket from its parent.
+///
+/// # Path Parameters
+/// - `id`: UUID string of the child ticket
+///
+/// # Returns
+/// - `200 OK` with the ticket, whether or not it had a parent
+/// - `400 Bad Request` if the UUID is invalid
+/// - `404 Not Found` if no ticket matches the UUID
+#[utoipa::path(
+    delete,
+    path = "/tickets/{id}/parent",
+    tag = "tickets",
+    params(("id" = String, Path, description = "UUID of the ticket")),
+    responses(
+        (status = 200, description = "The ticket", body = TicketResponse),
+        (status = 400, description = "Invalid UUID", body = ErrorBody),
+        (status = 404, description = "No ticket matches the UUID", body = ErrorBody),
+    )
+)]
+pub async fn clear_parent(
+    State(store): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_id = parse_ticket_id(&id)?;
+
+    let ticket = store.set_parent(&ticket_id, None).await?;
+    let response = TicketResponse::from(ticket);
+    Ok(Json(json!(response)))
+}
+
+/// Columns of [`export_csv`], in order.
+const CSV_COLUMNS: [&str; 5] = ["id", "title", "description", "status", "created_at"];
+
+/// Exports every live ticket as CSV, for reporting.
+///
+/// The rows are streamed as the tickets are read, in creation order, after
+/// a header row naming the columns `id`, `title`, `description`, `status`
+/// and `created_at`. Values holding commas, quotes or line breaks are quoted.
+///
+/// # Returns
+/// - `200 OK` with `text/csv` content, as an attachment named `tickets.csv`
+#[utoipa::path(
+    get,
+    path = "/tickets/export.csv",
+    tag = "tickets",
+    responses(
+        (status = 200, description = "Every ticket as CSV", content_type = "text/csv", body = String),
+    )
+)]
+pub async fn export_csv(State(store): State<AppState>) -> Response {
+    let header_row = stream::once(async { csv_row(CSV_COLUMNS) });
+    let rows = store.stream_tickets().await.map(|ticket| {
+        csv_row([
+            ticket.id.to_string(),
+            ticket.title.0,
+            ticket.description.0,
+            ticket.status.name().to_string(),
+            ticket.created_at.to_rfc3339(),
+        ])
+    });
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"tickets.csv\"",
+            ),
+        ],
+        Body::from_stream(header_row.chain(rows)),
+    )
+        .into_response()
+}
+
+/// Encodes one CSV record, quoting the fields that need it.
+fn csv_row<I, F>(fields: I) -> Result<Vec<u8>, csv::Error>
+where
+    I: IntoIterator<Item = F>,
+    F: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+    writer
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))
+}
+
+/// Exports the whole store as a fixture for bug reports and tests.
+///
+/// Requires the admin token.
+///
+/// # Returns
+/// - `200 OK` with every ticket, the store's write version, each ticket's
+///   history, the change log and the live idempotency keys
+#[utoipa::path(
+    get,
+    path = "/admin/fixture",
+    tag = "admin",
+    responses(
+        (status = 200, description = "The store as a fixture", body = Fixture),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorBody),
+    )
+)]
+pub async fn export_fixture(State(store): State<AppState>) -> Json<Value> {
+    let fixture = store.export_fixture().await;
+    Json(json!(fixture))
+}
+
+/// Replaces the whole store with a fixture.
+///
+/// Requires the admin token.
+///
+/// # Request Body
+/// A fixture as returned by [`export_fixture`]
+///
+/// # Returns
+/// - `200 OK` with the number of loaded tickets and the restored write version
+/// - `400 Bad Request` if a ticket fails validation, two tickets share an ID,
+///   the history names a missing ticket or the change log is out of order
+#[utoipa::path(
+    post,
+    path = "/admin/fixture",
+    tag = "admin",
+    request_body = Fixture,
+    responses(
+        (status = 200, description = "Fixture loaded", body = FixtureLoaded),
+        (status = 400, description = "Invalid fixture", body = ErrorBody),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorBody),
+    )
+)]
+pub async fn load_fixture(
+    State(store): State<AppState>,
+    RequestJson(fixture): RequestJson<Fixture>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_count = fixture.tickets.len();
+    let store_version = fixture.store_version;
+
+    match store.load_fixture(fixture).await {
+        Ok(()) => Ok(Json(json!({
+            "tickets": ticket_count,
+            "store_version": store_version
+        }))),
+        Err(StoreError::InvalidField(msg)) => Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "INVALID_FIXTURE",
+            msg,
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Backs up every ticket as one consistent snapshot.
+///
+/// Requires the admin token.
+///
+/// # Returns
+/// - `200 OK` with every ticket, soft-deleted ones included
+#[utoipa::path(
+    get,
+    path = "/admin/snapshot",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Every ticket", body = Snapshot),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorBody),
+    )
+)]
+pub async fn export_snapshot(State(store): State<AppState>) -> Json<Value> {
+    let tickets = store.export_snapshot().await;
+    Json(json!(Snapshot { tickets }))
+}
+
+/// Replaces every ticket with those of a snapshot.
+///
+/// Requires the admin token. The snapshot is validated in full first, so a
+/// rejected one leaves the store as it was.
+///
+/// # Request Body
+/// A snapshot as returned by [`export_snapshot`]
+///
+/// # Returns
+/// - `200 OK` with the number of restored tickets and the new write version
+/// - `400 Bad Request` if a ticket fails validation or two tickets share an ID
+#[utoipa::path(
+    post,
+    path = "/admin/restore",
+    tag = "admin",
+    request_body = Snapshot,
+    responses(
+        (status = 200, description = "Snapshot restored", body = FixtureLoaded),
+        (status = 400, description = "Invalid snapshot", body = ErrorBody),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorBody),
+    )
+)]
+pub async fn restore_snapshot(
+    State(store): State<AppState>,
+    RequestJson(snapshot): RequestJson<Snapshot>,
+) -> Result<Json<Value>, ApiError> {
+    let ticket_count = snapshot.tickets.len();
+
+    match store.import_snapshot(snapshot.tickets).await {
+        Ok(store_version) => Ok(Json(json!({
+            "tickets": ticket_count,
+            "store_version": store_version
+        }))),
+        Err(StoreError::InvalidField(msg)) => Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "INVALID_SNAPSHOT",
+            msg,
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Query parameters for [`purge_deleted`].
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PurgeParams {
+    /// Only purge tickets deleted at least this many days ago
+    pub older_than_days: Option<u64>,
+}
+
+/// Permanently removes soft-deleted tickets.
+///
+/// Requires the admin token. Live tickets are never removed, and purged
+/// tickets can't be restored.
+///
+/// # Query Parameters
+/// - `older_than_days`: Optional, only purge tickets deleted at least this
+///   many days ago; every soft-deleted ticket is purged without it
+///
+/// # Returns
+/// - `200 OK` with the number of tickets removed as `purged`
+#[utoipa::path(
+    post,
+    path = "/admin/purge-deleted",
+    tag = "admin",
+    params(PurgeParams),
+    responses(
+        (status = 200, description = "Soft-deleted tickets purged", body = Purged),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorBody),
+    )
+)]
+pub async fn purge_deleted(
+    State(store): State<AppState>,
+    Query(params): Query<PurgeParams>,
+) -> Json<Value> {
+    let older_than = params
+        .older_than_days
+        .map(|days| Duration::from_secs(days.saturating_mul(24 * 60 * 60)));
+    let purged = store.purge_soft_deleted(older_than).await;
+    tracing::info!("Purged {} soft-deleted ticket(s)", purged);
+
+    Json(json!({
+        "purged": purged
+    }))
+}
+
+/// Renders the request counters and ticket count for Prometheus.
+///
+/// # Returns
+/// - `200 OK` with a `text/plain; version=0.0.4` body holding the
+///   `ticket_api_requests_total` counters, by method, endpoint and status,
+///   and the `ticket_api_tickets` gauge of live tickets
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", content_type = "text/plain", body = String),
+    )
+)]
+pub async fn metrics(
+    State((metrics, store)): State<(Arc<Metrics>, AppState)>,
+) -> impl IntoResponse {
+    let (tickets, _) = store.count_by_status().await;
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        metrics.render(tickets),
+    )
+}
+
+/// Liveness check: answers as long as the process runs.
+///
+/// Served at both `/health/live` and, for existing probes, `/health`.
+///
+/// # Returns
+/// - `200 OK` with a JSON object containing:
+///   - `status`: "healthy"
+///   - `service`: "ticket-api"
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "The service is alive", body = Health),
+    )
+)]
+pub async fn health_check() -> Json<Value> {
+    Json(json!({
+        "status": "healthy",
+        "service": "ticket-api"
+    }))
+}
+
+/// Readiness check: answers whether the service can serve requests.
+///
+/// The store must be readable within a second. There is no persistence to
+/// check yet.
+///
+/// # Returns
+/// - `200 OK` with `status` "ready" and the result of each check under `checks`
+/// - `503 Service Unavailable` with `status` "unavailable" if a check failed
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "The service is ready", body = Readiness),
+        (status = 503, description = "A check failed", body = Readiness),
+    )
+)]
+pub async fn readiness_check(State(store): State<AppState>) -> (StatusCode, Json<Value>) {
+    let store_ready = store.is_ready().await;
+    let (status, label) = if store_ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    };
+    (
+        status,
+        Json(json!({
+            "status": label,
+            "service": "ticket-api",
+            "checks": {
+                "store": if store_ready { "ok" } else { "unavailable" }
+            }
+        })),
+    )
+}
+
+/// Serves the OpenAPI document describing every endpoint.
+///
+/// # Returns
+/// - `200 OK` with the OpenAPI 3.1 document as JSON
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "monitoring",
+    responses(
+        (status = 200, description = "The OpenAPI document", body = Object),
+    )
+)]
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
 }
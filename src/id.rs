@@ -0,0 +1,121 @@
+//! Ticket ID generation strategies.
+//!
+//! The store asks an [`IdGenerator`] for every new ticket's ID, so the ID
+//! scheme can change without touching the code that creates tickets:
+//! - [`UuidV4Generator`]: random UUIDs, the default
+//! - [`UuidV7Generator`]: time-ordered UUIDs
+//! - [`SequentialIdGenerator`]: UUIDs counting up from 1, handy for tests
+//! - Any `Fn() -> TicketId` closure, for tests that need specific IDs
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+use crate::data::TicketId;
+
+/// A source of IDs for new tickets.
+///
+/// Generators are shared by every request, so `generate` may be called
+/// concurrently and must never hand out the same ID twice.
+pub trait IdGenerator: Send + Sync {
+    /// Returns the ID for a new ticket.
+    fn generate(&self) -> TicketId;
+
+    /// Tells the generator `id` is already in the store, e.g. after a
+    /// fixture load, so it never hands it out.
+    ///
+    /// Generators that can't repeat an existing ID ignore it, which is the
+    /// default.
+    fn skip(&self, _id: &TicketId) {}
+}
+
+/// Generates random (version 4) UUIDs.
+#[derive(Clone, Debug, Default)]
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn generate(&self) -> TicketId {
+        TicketId(Uuid::new_v4())
+    }
+}
+
+/// Generates time-ordered (version 7) UUIDs, so IDs sort by creation time.
+#[derive(Clone, Debug, Default)]
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn generate(&self) -> TicketId {
+        TicketId(Uuid::now_v7())
+    }
+}
+
+/// Generates UUIDs whose value counts up by one per ticket.
+///
+/// The first ID is `00000000-0000-0000-0000-000000000001` by default (the nil
+/// UUID is never a valid ticket ID). IDs restart with every generator; a
+/// store loaded with existing tickets moves it past their IDs, see
+/// [`IdGenerator::skip`].
+#[derive(Debug)]
+pub struct SequentialIdGenerator {
+    /// The value of the next ID
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Create a generator whose first ID has the value `first`.
+    pub fn starting_at(first: u64) -> Self {
+        Self {
+            next: AtomicU64::new(first.max(1)),
+        }
+    }
+}
+
+impl Default for SequentialIdGenerator {
+    fn default() -> Self {
+        Self::starting_at(1)
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate(&self) -> TicketId {
+        let value = self.next.fetch_add(1, Ordering::Relaxed);
+        TicketId(Uuid::from_u128(u128::from(value)))
+    }
+
+    fn skip(&self, id: &TicketId) {
+        if let Ok(value) = u64::try_from(id.0.as_u128()) {
+            self.next
+                .fetch_max(value.saturating_add(1), Ordering::Relaxed);
+        }
+    }
+}
+
+/// Closures generate IDs too, so a test can hand the store any sequence.
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::sync::Arc;
+/// use ticket_api::{ServerConfig, TicketDraft, TicketId, TicketStore};
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let next = AtomicU64::new(42);
+/// let store = TicketStore::with_id_generator(
+///     ServerConfig::default(),
+///     Arc::new(move || {
+///         let value = next.fetch_add(1, Ordering::Relaxed);
+///         TicketId(uuid::Uuid::from_u128(u128::from(value)))
+///     }),
+/// );
+///
+/// let draft = TicketDraft::builder().title("Fix login").build().unwrap();
+/// let ticket = store.add_ticket(draft).await.unwrap();
+/// assert_eq!(ticket.id.to_string(), "00000000-0000-0000-0000-00000000002a");
+/// # });
+/// ```
+impl<F> IdGenerator for F
+where
+    F: Fn() -> TicketId + Send + Sync,
+{
+    fn generate(&self) -> TicketId {
+        self()
+    }
+}
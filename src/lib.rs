@@ -7,23 +7,77 @@
 //! - Thread-safe in-memory storage
 //! - CORS support and health checking
 //!
-//! The crate is organized into three main modules:
+//! The crate is organized into thirteen main modules:
+//! - `config`: Runtime configuration and policies
 //! - `data`: Core data types and validation
+//! - `error`: Uniform API error responses
+//! - `extract`: Request extractors reporting errors in the API's format
 //! - `handlers`: HTTP route handlers
+//! - `id`: Ticket ID generation strategies
+//! - `metrics`: Request metrics for monitoring
+//! - `middleware`: Cross-cutting request/response processing
+//! - `openapi`: The OpenAPI description of the API
+//! - `repository`: Storage backend abstraction
+//! - `router`: Application router construction
 //! - `store`: Thread-safe ticket storage
+//! - `webhook`: Webhook notifications of ticket events
+
+/// Runtime configuration for the server and the ticket store.
+/// All policies default to disabled.
+pub mod config;
 
 /// Core data structures and validation logic for the ticket system.
 /// Includes types for tickets, their components, and request/response DTOs.
 pub mod data;
 
+/// The error type returned by handlers and middleware.
+/// Serializes every failure as `{"error": {"code", "message"}}`.
+pub mod error;
+
+/// Extractors for request bodies whose rejections use the API's error format.
+pub mod extract;
+
 /// HTTP route handlers implementing the REST API endpoints.
 /// Uses Axum for routing and request handling.
 pub mod handlers;
 
+/// Pluggable strategies for generating new ticket IDs.
+pub mod id;
+
+/// Request counters rendered in the Prometheus text format.
+pub mod metrics;
+
+/// Middleware applied by the router around the handlers.
+pub mod middleware;
+
+/// The OpenAPI document generated from the handler annotations.
+pub mod openapi;
+
+/// The storage trait implemented by ticket backends.
+/// Lets the router run on any backend, not just the in-memory store.
+pub mod repository;
+
+/// Builds the application router from a configuration.
+/// Usable from tests without binding a socket.
+pub mod router;
+
 /// Thread-safe, in-memory storage for tickets.
 /// Provides CRUD operations with proper error handling.
 pub mod store;
 
+/// Delivers ticket events to configured webhook URLs in the background.
+pub mod webhook;
+
+pub use config::*;
 pub use data::*;
+pub use error::*;
+pub use extract::*;
 pub use handlers::*;
+pub use id::*;
+pub use metrics::*;
+pub use middleware::*;
+pub use openapi::*;
+pub use repository::*;
+pub use router::*;
 pub use store::*;
+pub use webhook::*;
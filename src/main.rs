@@ -1,52 +1,205 @@
-use axum::{
-    routing::{get, patch, post},
-    Router,
-};
 use std::net::SocketAddr;
-use tower::ServiceBuilder;
-use tower_http::cors::CorsLayer;
-use tracing_subscriber;
-
+use std::sync::Arc;
 use ticket_api::{
-    create_ticket, get_ticket, health_check, list_tickets, patch_ticket, AppState, TicketStore,
+    build_router_with_repository, spawn_webhooks, AppState, CorsPolicy, ServerConfig, TicketStore,
 };
+use tracing_subscriber::EnvFilter;
+
+/// Sets up tracing output.
+///
+/// Logs are JSON objects, one per line and with each field as its own key,
+/// when `TICKET_API_LOG_FORMAT` is `json`, and human-readable lines
+/// otherwise. The level filter comes from `TICKET_API_LOG_LEVEL`, then
+/// `RUST_LOG`, in [`EnvFilter`] syntax such as `debug` or
+/// `info,ticket_api=trace`, and is `info` when neither is set.
+///
+/// # Returns
+/// * `Ok(())` - Tracing is set up
+/// * `Err(String)` - If the level filter can't be parsed
+fn init_tracing() -> Result<(), String> {
+    let directives = std::env::var("TICKET_API_LOG_LEVEL")
+        .or_else(|_| std::env::var(EnvFilter::DEFAULT_ENV))
+        .unwrap_or_else(|_| "info".to_string());
+    let filter = EnvFilter::try_new(&directives)
+        .map_err(|e| format!("invalid log level {:?}: {}", directives, e))?;
+
+    let json = std::env::var("TICKET_API_LOG_FORMAT")
+        .is_ok_and(|format| format.eq_ignore_ascii_case("json"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    if json {
+        builder.json().flatten_event(true).init();
+    } else {
+        builder.init();
+    }
+    Ok(())
+}
 
 /// Entry point for the Ticket API server.
 ///
 /// Sets up and runs the HTTP server with:
-/// - Tracing for logging
-/// - CORS middleware
+/// - Configuration from `TICKET_API_*` environment variables
+/// - Demo tickets when started with `--seed` or `TICKET_API_SEED=1`
+/// - Webhook notifications for `TICKET_API_WEBHOOK_URLS`
+/// - Periodic purging of old `Done` tickets for `TICKET_API_PURGE_AFTER`
+/// - Client addresses for the rate limit of `TICKET_API_RATE_LIMIT`
+/// - Tracing for logging, as JSON for `TICKET_API_LOG_FORMAT=json`
+/// - CORS middleware, limited to `TICKET_API_CORS_ORIGINS` when set
 /// - Route handlers for all endpoints
 /// - Pretty-printed endpoint documentation
 #[tokio::main]
 async fn main() {
     // Initialize tracing
-    tracing_subscriber::init();
+    if let Err(e) = init_tracing() {
+        eprintln!("❌ Invalid configuration: {}", e);
+        std::process::exit(1);
+    }
+
+    // Load the configuration, refusing to start on invalid values
+    let config = match ServerConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ Invalid configuration: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let (host, port) = (config.host.clone(), config.port);
+    if config.cors == CorsPolicy::Permissive {
+        tracing::warn!(
+            "CORS allows any origin; set TICKET_API_CORS_ORIGINS to restrict it in production"
+        );
+    }
+
+    // Seed demo tickets if asked to
+    let store = TicketStore::with_config(config.clone());
+    if config.seed_demo_data || std::env::args().skip(1).any(|arg| arg == "--seed") {
+        match store.seed_demo_data().await {
+            Ok(0) => println!("🌱 Store already holds tickets, not seeding"),
+            Ok(count) => println!("🌱 Seeded {} demo tickets", count),
+            Err(e) => {
+                eprintln!("❌ Could not seed demo tickets: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 
-    // Create the ticket store
-    let store = TicketStore::new();
+    // Notify webhooks of ticket events in the background
+    let store: AppState = Arc::new(store);
+    if spawn_webhooks(config.webhooks.clone(), store.clone()).is_some() {
+        println!(
+            "🔔 Sending ticket events to {} webhook(s)",
+            config.webhooks.urls.len()
+        );
+    }
+
+    // Purge tickets that have long been done in the background
+    if let Some(purge) = config.purge.clone() {
+        println!(
+            "🧹 Purging tickets done for over {}s, checking every {}s",
+            purge.older_than.as_secs(),
+            purge.interval.as_secs()
+        );
+        let store = store.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(purge.interval);
+            loop {
+                ticker.tick().await;
+                let removed = store.purge_expired(purge.older_than).await;
+                tracing::info!(
+                    "Purge removed {} ticket(s) done for over {}s",
+                    removed,
+                    purge.older_than.as_secs()
+                );
+            }
+        });
+    }
+
+    // Empty the trash of tickets deleted too long ago in the background
+    if let Some(trash) = config.trash.clone() {
+        println!(
+            "🗑️  Keeping deleted tickets for {}s, at most {}, checking every {}s",
+            trash.retention.as_secs(),
+            trash.max_tickets,
+            trash.interval.as_secs()
+        );
+        let store = store.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(trash.interval);
+            loop {
+                ticker.tick().await;
+                let removed = store.purge_soft_deleted(Some(trash.retention)).await;
+                tracing::info!(
+                    "Trash purge removed {} ticket(s) deleted over {}s ago",
+                    removed,
+                    trash.retention.as_secs()
+                );
+            }
+        });
+    }
 
     // Build the application with routes
-    let app = Router::new()
-        .route("/health", get(health_check))
-        .route("/tickets", post(create_ticket))
-        .route("/tickets", get(list_tickets))
-        .route("/tickets/:id", get(get_ticket))
-        .route("/tickets/:id", patch(patch_ticket))
-        .layer(ServiceBuilder::new().layer(CorsLayer::permissive()))
-        .with_state(store);
-
-    // Define the address to bind to
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
-    println!("🚀 Server starting on http://{}", addr);
+    let app = build_router_with_repository(config, store);
+
+    println!("🚀 Server starting on http://{}:{}", host, port);
 
     // Print available endpoints
     println!("📋 Available endpoints:");
     println!("  GET    /health           - Health check");
+    println!("  GET    /health/live      - Liveness check");
+    println!("  GET    /health/ready     - Readiness check");
     println!("  POST   /tickets          - Create a new ticket");
+    println!("  POST   /tickets/batch    - Create many tickets at once");
+    println!("  POST   /tickets/batch-get - Get many tickets at once");
+    println!(
+        "  POST   /tickets/validate - Validate a payload without storing it (?mode=create|patch)"
+    );
     println!("  GET    /tickets          - List all tickets");
     println!("  GET    /tickets/:id      - Get a specific ticket");
     println!("  PATCH  /tickets/:id      - Update a specific ticket");
+    println!("  PUT    /tickets/:id      - Replace a specific ticket");
+    println!("  DELETE /tickets/:id      - Soft-delete a specific ticket");
+    println!("  POST   /tickets/:id/restore - Restore a soft-deleted ticket");
+    println!("  GET    /tickets/trash    - List soft-deleted tickets");
+    println!("  POST   /tickets/trash/:id/restore - Restore a ticket from the trash");
+    println!("  POST   /tickets/:id/transition - Move a ticket to another status");
+    println!("  POST   /tickets/:id/cas-status - Move a ticket only from an expected status");
+    println!("  POST   /tickets/:id/reorder - Move a ticket in the backlog");
+    println!("  POST   /tickets/:id/reopen - Reopen a done ticket");
+    println!("  POST   /tickets/:id/undo - Revert the most recent change to a ticket");
+    println!("  POST   /tickets/transaction - Apply several ticket changes all or nothing");
+    println!("  GET    /tickets/search   - Search tickets by text (?q=<text>)");
+    println!("  GET    /tickets/search-all - Search tickets and comments by text (?q=<text>)");
+    println!("  GET    /tickets/diff     - Diff two tickets (?a=<id>&b=<id>)");
+    println!("  GET    /tickets/count    - Ticket counts in total and by status");
+    println!("  GET    /tickets/lead-time - Lead time stats (?from=<time>&to=<time>)");
+    println!("  GET    /tickets/matrix   - Ticket counts by priority and status");
+    println!("  GET    /tickets/assignees - Ticket counts per assignee");
+    println!("  POST   /tickets/upsert   - Get or create a ticket by external reference");
+    println!("  POST   /tickets/bulk-custom - Update custom fields on many tickets");
+    println!("  POST   /tickets/bulk-status - Move many tickets to the same status");
+    println!("  GET    /tickets/events   - Stream ticket changes (Server-Sent Events)");
+    println!("  GET    /tickets/:id/wait - Wait for a ticket to change (?since=<version>)");
+    println!("  GET    /tickets/changes  - List changes since a sequence (?since=<seq>)");
+    println!("  GET    /tickets/pending-sync - List tickets not yet synced");
+    println!("  POST   /tickets/:id/mark-synced - Mark a ticket as synced");
+    println!("  POST   /tickets/:id/tags - Add a tag to a ticket");
+    println!("  DELETE /tickets/:id/tags/:tag - Remove a tag from a ticket");
+    println!("  POST   /tickets/:id/comments - Comment on a ticket");
+    println!("  GET    /tickets/:id/comments - List a ticket's comments");
+    println!("  POST   /tickets/:id/comments/batch - Append many comments to a ticket");
+    println!("  GET    /tickets/:id/history - List a ticket's field changes");
+    println!("  GET    /tickets/:id/time-in-status - Report time spent in each status");
+    println!("  GET    /tickets/export.csv - Export every ticket as CSV");
+    println!("  POST   /tickets/:id/block/:other - Mark a ticket as blocking another");
+    println!("  DELETE /tickets/:id/block/:other - Remove a blocking link");
+    println!("  POST   /tickets/:id/parent/:parent - Set a ticket's parent");
+    println!("  DELETE /tickets/:id/parent - Clear a ticket's parent");
+    println!("  GET    /metrics          - Request and ticket metrics (Prometheus)");
+    println!("  GET    /openapi.json     - OpenAPI description of the API");
+    println!("  GET    /admin/fixture    - Export the store as a fixture (admin)");
+    println!("  POST   /admin/fixture    - Load the store from a fixture (admin)");
+    println!("  GET    /admin/snapshot   - Back up every ticket (admin)");
+    println!("  POST   /admin/restore    - Replace every ticket with a snapshot (admin)");
+    println!("  POST   /admin/purge-deleted - Remove soft-deleted tickets for good (admin)");
     println!();
     println!("📝 Example usage:");
     println!("  curl -X POST http://localhost:3000/tickets \\");
@@ -55,6 +208,13 @@ async fn main() {
     println!();
 
     // Start the server
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    let listener = tokio::net::TcpListener::bind((host.as_str(), port))
+        .await
+        .unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
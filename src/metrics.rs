@@ -0,0 +1,103 @@
+//! Request metrics in the Prometheus text format.
+//!
+//! [`Metrics`] counts handled requests by method, route and status code. The
+//! counters are atomics behind a map that is only write-locked the first time
+//! a combination is seen, so counting a request costs a read lock and an
+//! atomic increment.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Media type of the Prometheus text exposition format.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Identifies one request counter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct RequestKey {
+    method: String,
+    endpoint: String,
+    status: u16,
+}
+
+/// Request counters shared by every request.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Handled requests per method, route and status code
+    requests: RwLock<HashMap<RequestKey, AtomicU64>>,
+}
+
+impl Metrics {
+    /// Create a set of counters, all at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts one handled request.
+    ///
+    /// # Arguments
+    /// * `method` - The request method, e.g. `GET`
+    /// * `endpoint` - The matched route, e.g. `/tickets/:id`, so IDs don't
+    ///   create a counter each
+    /// * `status` - The response status code
+    pub fn record_request(&self, method: &str, endpoint: &str, status: u16) {
+        let key = RequestKey {
+            method: method.to_string(),
+            endpoint: endpoint.to_string(),
+            status,
+        };
+        if let Some(counter) = self.requests.read().unwrap().get(&key) {
+            counter.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.requests
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Renders the counters and the ticket gauge in the Prometheus text format.
+    ///
+    /// # Arguments
+    /// * `tickets` - The current number of tickets, reported as a gauge
+    ///
+    /// # Returns
+    /// The exposition text, with counters sorted by method, route and status.
+    pub fn render(&self, tickets: usize) -> String {
+        let mut output = String::new();
+        output.push_str(
+            "# HELP ticket_api_requests_total HTTP requests handled, by method, endpoint and status code.\n",
+        );
+        output.push_str("# TYPE ticket_api_requests_total counter\n");
+
+        let requests = self.requests.read().unwrap();
+        let mut keys: Vec<&RequestKey> = requests.keys().collect();
+        keys.sort();
+        for key in keys {
+            let _ = writeln!(
+                output,
+                "ticket_api_requests_total{{method=\"{}\",endpoint=\"{}\",status=\"{}\"}} {}",
+                escape_label(&key.method),
+                escape_label(&key.endpoint),
+                key.status,
+                requests[key].load(Ordering::Relaxed)
+            );
+        }
+
+        output.push_str("# HELP ticket_api_tickets Current number of tickets.\n");
+        output.push_str("# TYPE ticket_api_tickets gauge\n");
+        let _ = writeln!(output, "ticket_api_tickets {}", tickets);
+        output
+    }
+}
+
+/// Escapes a label value for the Prometheus text format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
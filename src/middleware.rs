@@ -0,0 +1,559 @@
+//! HTTP middleware for the Ticket API.
+//!
+//! Cross-cutting request/response processing that applies to many routes:
+//! - Legacy response field renaming
+//! - Read-your-writes version tokens
+//! - Admin token and API key checks
+//! - Request body size limits and timeouts
+//! - Rate limits per client IP
+//! - Request IDs and request logging
+//! - Request metrics
+//! - Verbose error bodies
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{ConnectInfo, MatchedPath, Query, Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::config::{LegacyFields, RateLimit};
+use crate::error::{ApiError, ErrorSource};
+use crate::handlers::AppState;
+use crate::metrics::Metrics;
+
+/// Header a client sends to opt into legacy response field names.
+pub const COMPAT_HEADER: &str = "x-response-compat";
+
+/// Response header carrying the store's write version.
+pub const STORE_VERSION_HEADER: &str = "x-store-version";
+
+/// Response header set when a read could not wait for the requested version.
+pub const STALE_HEADER: &str = "x-store-stale";
+
+/// Response header carrying the ID assigned to the request.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Request header carrying the API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Request header naming the client, and any proxies, a request came through.
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Most clients a [`RateLimiter`] keeps a bucket for at once.
+pub const MAX_RATE_LIMITED_CLIENTS: usize = 10_000;
+
+/// Query parameter accepted by every route for read-your-writes reads.
+#[derive(Debug, Deserialize)]
+struct ConsistencyParams {
+    /// Write version the response must reflect
+    min_version: Option<u64>,
+}
+
+/// Exposes the store's write version and honors `min_version` on reads.
+///
+/// Every response carries an `X-Store-Version` header. For writes it is the
+/// version after the write, which the client can pass back as `?min_version=`
+/// on a later read. A read asking for a version the store hasn't reached waits
+/// briefly; if the version still isn't reached, the read is served anyway
+/// with `X-Store-Stale: true`.
+pub async fn read_your_writes(
+    State(store): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let params = match Query::<ConsistencyParams>::try_from_uri(request.uri()) {
+        Ok(Query(params)) => params,
+        Err(_) => {
+            return ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "INVALID_MIN_VERSION",
+                "min_version must be a non-negative integer",
+            )
+            .into_response()
+        }
+    };
+
+    let is_read = matches!(*request.method(), Method::GET | Method::HEAD);
+    let stale = match params.min_version {
+        Some(min_version) if store.version() < min_version => {
+            !store.wait_for_version(min_version).await
+        }
+        _ => false,
+    };
+
+    // A read reflects at least the version current when it started; a write
+    // is only covered by the version observed after it completed.
+    let read_version = store.version();
+    let mut response = next.run(request).await;
+    let version = if is_read {
+        read_version
+    } else {
+        store.version()
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(STORE_VERSION_HEADER, HeaderValue::from(version));
+    if stale {
+        headers.insert(STALE_HEADER, HeaderValue::from_static("true"));
+    }
+    response
+}
+
+/// Assigns every request a UUID and logs it once the response is ready.
+///
+/// The ID is returned in the `X-Request-Id` header, so a client report can be
+/// matched to the log line, which carries the request ID, method, path,
+/// status and latency. With `logging` off only the header is set.
+pub async fn request_logging(
+    State(logging): State<bool>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let request_id = Uuid::new_v4();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let started = Instant::now();
+
+    let mut response = next.run(request).await;
+
+    if logging {
+        tracing::info!(
+            request_id = %request_id,
+            method = %method,
+            path = %path,
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "request completed"
+        );
+    }
+    if let Ok(value) = HeaderValue::from_str(&request_id.to_string()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Counts every request in [`Metrics`] by method, matched route and status.
+///
+/// Requests that match no route are counted under the `unmatched` endpoint.
+pub async fn track_metrics(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let endpoint = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+    metrics.record_request(method.as_str(), &endpoint, response.status().as_u16());
+    response
+}
+
+/// Rejects requests that don't carry the configured admin token.
+///
+/// The token must be sent as `Authorization: Bearer <token>`. When no token
+/// is configured, every request is refused, so admin endpoints are off by
+/// default.
+pub async fn require_admin(
+    State(admin_token): State<Arc<Option<String>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = admin_token.as_deref() else {
+        return ApiError::new(
+            StatusCode::FORBIDDEN,
+            "ADMIN_DISABLED",
+            "Admin endpoints are disabled",
+        )
+        .into_response();
+    };
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !provided.is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes())) {
+        return ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHORIZED",
+            "A valid admin token is required",
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Rejects requests that don't carry the configured API key.
+///
+/// The key must be sent in the `X-Api-Key` header. When no key is
+/// configured, every request is let through, so local setups need none.
+pub async fn require_api_key(
+    State(api_key): State<Arc<Option<String>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(expected) = api_key.as_deref() {
+        let provided = request
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok());
+        if !provided.is_some_and(|key| constant_time_eq(key.as_bytes(), expected.as_bytes())) {
+            return ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "A valid API key is required",
+            )
+            .into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Rejects requests whose declared body is larger than `limit` bytes.
+///
+/// The `Content-Length` is checked before any of the body is read. Bodies
+/// sent without one are cut off at the same limit by the router's
+/// [`DefaultBodyLimit`](axum::extract::DefaultBodyLimit) instead.
+pub async fn limit_body_size(State(limit): State<usize>, request: Request, next: Next) -> Response {
+    let length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if length.is_some_and(|length| length > limit as u64) {
+        return ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "PAYLOAD_TOO_LARGE",
+            format!("Request body cannot be larger than {} bytes", limit),
+        )
+        .with_detail("limit", limit)
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Token buckets enforcing a [`RateLimit`] per client IP.
+///
+/// Buckets are created on a client's first request. Once per `per`, buckets
+/// that have been idle long enough to refill completely are dropped, since a
+/// fresh bucket would behave the same, so clients that went away don't use
+/// memory forever. At most [`MAX_RATE_LIMITED_CLIENTS`] buckets are kept: a
+/// new client arriving when that many are in use first drops the idle ones,
+/// then, if none were idle, the least recently used one.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<RateLimiterState>,
+}
+
+/// The buckets of a [`RateLimiter`], and when idle ones were last dropped.
+#[derive(Debug)]
+struct RateLimiterState {
+    buckets: HashMap<IpAddr, Bucket>,
+    last_sweep: Instant,
+}
+
+/// The tokens a client has left, as of `updated`.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter enforcing `limit`, with every client's bucket full.
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(RateLimiterState {
+                buckets: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes a token from `client`'s bucket.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The request may go ahead
+    /// * `Err(Duration)` - The bucket is empty; how long until a token refills
+    pub fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        let capacity = f64::from(self.limit.requests);
+        let per = self.limit.per.as_secs_f64();
+        if capacity == 0.0 || per == 0.0 {
+            return Err(self.limit.per);
+        }
+        let refill_per_sec = capacity / per;
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let full =
+            state.buckets.len() >= MAX_RATE_LIMITED_CLIENTS && !state.buckets.contains_key(&client);
+        if full || now.duration_since(state.last_sweep) >= self.limit.per {
+            state
+                .buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated) < self.limit.per);
+            state.last_sweep = now;
+        }
+        if full && state.buckets.len() >= MAX_RATE_LIMITED_CLIENTS {
+            let oldest = state
+                .buckets
+                .iter()
+                .min_by_key(|(_, bucket)| bucket.updated)
+                .map(|(address, _)| *address);
+            if let Some(oldest) = oldest {
+                state.buckets.remove(&oldest);
+            }
+        }
+
+        let bucket = state.buckets.entry(client).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / refill_per_sec,
+            ))
+        }
+    }
+}
+
+/// Throttles each client to the configured [`RateLimit`].
+///
+/// The client is the connection's peer address, or, when that peer is one of
+/// the limit's trusted proxies, the address it names in `X-Forwarded-For`;
+/// see [`client_ip`]. Requests from an unknown client, which only happens
+/// when the router is served without connect info, and
+/// requests to `/health` and its variants are never throttled, so probes
+/// keep working under load. A throttled request gets `429 Too Many
+/// Requests` with a `Retry-After` header in whole seconds.
+pub async fn rate_limit(
+    State(limiter): State<Arc<Option<RateLimiter>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = limiter.as_ref() else {
+        return next.run(request).await;
+    };
+    let path = request.uri().path();
+    if path == "/health" || path.starts_with("/health/") {
+        return next.run(request).await;
+    }
+    let Some(client) = client_ip(&request, &limiter.limit.trusted_proxies) else {
+        return next.run(request).await;
+    };
+
+    if let Err(wait) = limiter.check(client) {
+        let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+        let mut response = ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "RATE_LIMITED",
+            "Too many requests, slow down",
+        )
+        .with_detail("retry_after", retry_after)
+        .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    next.run(request).await
+}
+
+/// The IP a request came from, `None` without connect info.
+///
+/// That's the connection's peer address, unless the peer is one of
+/// `trusted_proxies`. Then it's the last `X-Forwarded-For` address that
+/// isn't a trusted proxy too: each proxy appends the address it got the
+/// request from, and anything before that could be made up by the client.
+fn client_ip(request: &Request, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip())?;
+    if !trusted_proxies.contains(&peer) {
+        return Some(peer);
+    }
+
+    let forwarded: Vec<IpAddr> = request
+        .headers()
+        .get_all(FORWARDED_FOR_HEADER)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|address| address.trim().parse().ok())
+        .collect();
+    Some(
+        forwarded
+            .into_iter()
+            .rev()
+            .find(|address| !trusted_proxies.contains(address))
+            .unwrap_or(peer),
+    )
+}
+
+/// Answers a request that ran past the router's timeout.
+///
+/// Used with [`HandleErrorLayer`](axum::error_handling::HandleErrorLayer)
+/// around the timeout, whose elapsed error is the only one that reaches it.
+pub async fn request_timed_out(_error: BoxError) -> ApiError {
+    ApiError::new(
+        StatusCode::REQUEST_TIMEOUT,
+        "REQUEST_TIMEOUT",
+        "The request took too long to process",
+    )
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Renames JSON response fields to their legacy names for opted-in clients.
+///
+/// The rename happens after serialization, so handlers stay unaware of the
+/// legacy scheme. Keys inside `custom_fields` are user data and are left as-is.
+pub async fn legacy_field_names(
+    State(legacy): State<Arc<LegacyFields>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let opted_in = legacy.always
+        || request
+            .headers()
+            .get(COMPAT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.eq_ignore_ascii_case("legacy"))
+            .unwrap_or(false);
+
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.contains("json"))
+        .unwrap_or(false);
+    if !opted_in || !is_json || legacy.mapping.is_empty() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    let mut json: Value = match serde_json::from_slice(&bytes) {
+        Ok(json) => json,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+
+    rename_keys(&mut json, &legacy.mapping);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(json.to_string()))
+}
+
+/// Adds the underlying error to error bodies when `verbose` is set.
+///
+/// Errors from the store carry their source, the store error's message and
+/// its `Debug` form, as an [`ErrorSource`] response extension. When verbose,
+/// it is added to the body as `error.source`; otherwise the body stays
+/// compact. The extension is dropped either way.
+pub async fn verbose_errors(State(verbose): State<bool>, request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    let Some(ErrorSource(source)) = response.extensions_mut().remove::<ErrorSource>() else {
+        return response;
+    };
+    if !verbose {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    let mut json: Value = match serde_json::from_slice(&bytes) {
+        Ok(json) => json,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+
+    if let Some(error) = json.get_mut("error").and_then(Value::as_object_mut) {
+        error.insert("source".to_string(), source);
+    }
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(json.to_string()))
+}
+
+/// Recursively renames object keys according to `mapping`.
+fn rename_keys(value: &mut Value, mapping: &HashMap<String, String>) {
+    match value {
+        Value::Object(fields) => {
+            let renamed = std::mem::take(fields)
+                .into_iter()
+                .map(|(key, mut field)| {
+                    if key != "custom_fields" {
+                        rename_keys(&mut field, mapping);
+                    }
+                    let key = mapping.get(&key).cloned().unwrap_or(key);
+                    (key, field)
+                })
+                .collect();
+            *fields = renamed;
+        }
+        Value::Array(items) => {
+            for item in items {
+                rename_keys(item, mapping);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn a_full_rate_limiter_evicts_the_least_recently_used_client() {
+        let limiter = RateLimiter::new(RateLimit::per_minute(1));
+        let first = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        limiter.check(first).unwrap();
+        assert!(limiter.check(first).is_err());
+
+        for n in 0..MAX_RATE_LIMITED_CLIENTS as u32 {
+            let client = IpAddr::V4(Ipv4Addr::from(0x0b00_0000 + n));
+            limiter.check(client).unwrap();
+        }
+
+        let state = limiter.state.lock().unwrap();
+        assert_eq!(state.buckets.len(), MAX_RATE_LIMITED_CLIENTS);
+        assert!(!state.buckets.contains_key(&first));
+    }
+}
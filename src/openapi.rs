@@ -0,0 +1,453 @@
+//! The OpenAPI description of the Ticket API.
+//!
+//! [`ApiDoc`] collects the `#[utoipa::path]` annotations on the handlers and
+//! the schemas of the request and response types into an OpenAPI 3.1
+//! document, served at `GET /openapi.json`.
+//!
+//! Most handlers build their responses with `json!`, so the envelope types
+//! below only describe those bodies for the spec; nothing serializes them at
+//! runtime.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use utoipa::openapi::path::{Operation, PathItem};
+use utoipa::openapi::security::{
+    ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme,
+};
+use utoipa::openapi::{ContentBuilder, Ref, ResponseBuilder};
+use utoipa::{Modify, OpenApi, ToSchema};
+
+use crate::data::{
+    AddCommentRequest, AddTagRequest, AssigneeSummary, AuditEntry, BacklogEnd, BatchGetRequest,
+    BulkCustomFieldsRequest, BulkStatusRequest, CasStatusRequest, ChangeRecord, Comment,
+    CommentMatch, CreateTicketRequest, FieldDiff, FieldError, Fixture, FixtureIdempotencyKey,
+    ImportCommentRequest, LeadTimeStats, PatchTicketRequest, Priority, Relationships,
+    ReopenTicketRequest, ReorderRequest, ReplaceTicketRequest, SlaStatus, Snapshot, Status,
+    SyncState, Ticket, TicketId, TicketResponse, TimeInStatus, TransactionOperation,
+    TransactionRequest, TransitionRequest, UpsertTicketRequest,
+};
+use crate::handlers;
+
+/// Every failed request: `{"error": {"code", "message"}}`.
+#[derive(ToSchema)]
+pub struct ErrorBody {
+    pub error: ErrorObject,
+}
+
+/// The error object of an [`ErrorBody`].
+///
+/// Some errors carry extra fields next to `code` and `message`, such as the
+/// per-field `errors` of a failed validation.
+#[derive(ToSchema)]
+pub struct ErrorObject {
+    /// Stable identifier of the failure, e.g. `TICKET_NOT_FOUND`
+    pub code: String,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+/// A page of tickets from `GET /tickets`.
+#[derive(ToSchema)]
+pub struct TicketPage {
+    pub tickets: Vec<TicketResponse>,
+    /// Number of tickets matching the filters, across all pages
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// A plain list of tickets.
+#[derive(ToSchema)]
+pub struct TicketList {
+    pub tickets: Vec<TicketResponse>,
+}
+
+/// A ticket matched by `GET /tickets/search-all`.
+#[derive(ToSchema)]
+pub struct SearchHit {
+    pub ticket: TicketResponse,
+    /// The ticket's matching comments, empty when only the title or
+    /// description matched
+    pub comments: Vec<CommentMatch>,
+}
+
+/// The tickets matched by `GET /tickets/search-all`.
+#[derive(ToSchema)]
+pub struct SearchResults {
+    pub results: Vec<SearchHit>,
+}
+
+/// The outcome of `POST /tickets/batch`.
+#[derive(ToSchema)]
+pub struct BatchCreated {
+    /// The created tickets, in request order
+    pub tickets: Vec<TicketResponse>,
+    /// The items that were rejected
+    pub errors: Vec<BatchItemError>,
+}
+
+/// The outcome of `POST /tickets/{id}/comments/batch`.
+#[derive(ToSchema)]
+pub struct CommentsImported {
+    /// The added comments, in request order
+    pub comments: Vec<Comment>,
+    /// The items that were rejected
+    pub errors: Vec<BatchItemError>,
+}
+
+/// The outcome of `POST /tickets/batch-get`.
+#[derive(ToSchema)]
+pub struct BatchTickets {
+    /// The found tickets, keyed by ID
+    pub tickets: BTreeMap<String, TicketResponse>,
+    /// IDs that match no live ticket
+    pub not_found: Vec<TicketId>,
+    /// Strings that aren't ticket IDs
+    pub invalid_ids: Vec<String>,
+}
+
+/// A rejected item of a batch.
+#[derive(ToSchema)]
+pub struct BatchItemError {
+    /// Position of the item in the request
+    pub index: usize,
+    pub error: ErrorObject,
+}
+
+/// The outcome of `POST /tickets/{id}/transition`.
+#[derive(ToSchema)]
+pub struct TransitionResult {
+    /// The status the ticket was in
+    pub from: Status,
+    /// The status the ticket is in now
+    pub to: Status,
+    pub ticket: TicketResponse,
+}
+
+/// The outcome of `POST /tickets/{id}/undo`.
+#[derive(ToSchema)]
+pub struct UndoResult {
+    /// The field set back to its previous value
+    pub field: String,
+    pub ticket: TicketResponse,
+}
+
+/// The outcome of `POST /tickets/transaction`, one result per operation.
+#[derive(ToSchema)]
+pub struct TransactionResults {
+    /// `{"op", "ticket"}`, or `{"op", "id"}` for a deleted ticket
+    #[schema(value_type = Vec<Object>)]
+    pub results: Vec<Value>,
+}
+
+/// The outcome of `POST /tickets/bulk-custom` or `POST /tickets/bulk-status`,
+/// one result per ID.
+#[derive(ToSchema)]
+pub struct BulkResults {
+    pub results: Vec<BulkResult>,
+}
+
+/// The outcome for one ticket of a bulk update.
+#[derive(ToSchema)]
+pub struct BulkResult {
+    pub id: String,
+    /// `updated`, `invalid`, `invalid_id`, `invalid_transition`, `not_applied`
+    /// or `not_found`
+    pub outcome: String,
+    /// The updated ticket, when `outcome` is `updated`
+    pub ticket: Option<TicketResponse>,
+    /// Why the ticket wasn't updated
+    pub message: Option<String>,
+}
+
+/// The differing fields of two tickets.
+#[derive(ToSchema)]
+pub struct TicketDiff {
+    pub a: TicketId,
+    pub b: TicketId,
+    pub differences: Vec<FieldDiff>,
+}
+
+/// Ticket counts in total and per status.
+#[derive(ToSchema)]
+pub struct TicketCount {
+    pub total: usize,
+    /// Count per status; every status is present
+    #[schema(value_type = Object)]
+    pub by_status: Value,
+    /// Story points of the tickets not yet `Done`
+    pub total_estimate: u64,
+}
+
+/// Ticket counts per assignee from `GET /tickets/assignees`.
+#[derive(ToSchema)]
+pub struct AssigneeList {
+    pub assignees: Vec<AssigneeSummary>,
+}
+
+/// The changes after a sequence number.
+#[derive(ToSchema)]
+pub struct ChangeFeed {
+    pub changes: Vec<ChangeRecord>,
+    /// The sequence to poll from next
+    pub sequence: u64,
+}
+
+/// A ticket's comments, oldest first.
+#[derive(ToSchema)]
+pub struct CommentList {
+    pub comments: Vec<Comment>,
+}
+
+/// A ticket's field changes, oldest first.
+#[derive(ToSchema)]
+pub struct HistoryList {
+    pub history: Vec<AuditEntry>,
+}
+
+/// How long a ticket has spent in each status.
+#[derive(ToSchema)]
+pub struct TimeInStatusReport {
+    pub statuses: Vec<TimeInStatus>,
+}
+
+/// The outcome of loading a fixture.
+#[derive(ToSchema)]
+pub struct FixtureLoaded {
+    /// Number of tickets loaded
+    pub tickets: usize,
+    pub store_version: u64,
+}
+
+/// The outcome of `POST /admin/purge-deleted`.
+#[derive(ToSchema)]
+pub struct Purged {
+    /// Number of tickets removed
+    pub purged: usize,
+}
+
+/// The liveness check response.
+#[derive(ToSchema)]
+pub struct Health {
+    /// Always `healthy`
+    pub status: String,
+    pub service: String,
+}
+
+/// The readiness check response.
+#[derive(ToSchema)]
+pub struct Readiness {
+    /// `ready` or `unavailable`
+    pub status: String,
+    pub service: String,
+    /// Result of each check, e.g. `{"store": "ok"}`
+    #[schema(value_type = Object)]
+    pub checks: Value,
+}
+
+/// Adds what the path annotations can't express: the security schemes, which
+/// routes need them, and the `/health` alias of `/health/live`.
+struct RouterDetails;
+
+impl Modify for RouterDetails {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(
+                crate::middleware::API_KEY_HEADER,
+            ))),
+        );
+        components.add_security_scheme(
+            "admin_token",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+
+        let unauthorized = ResponseBuilder::new()
+            .description("Missing or wrong API key, when one is configured")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ErrorBody")))
+                    .build(),
+            )
+            .build();
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            let scheme = if path.starts_with("/tickets") {
+                "api_key"
+            } else if path.starts_with("/admin") {
+                "admin_token"
+            } else {
+                continue;
+            };
+            for operation in operations(item) {
+                operation.security = Some(vec![SecurityRequirement::new::<_, _, &str>(scheme, [])]);
+                if scheme == "api_key" {
+                    operation
+                        .responses
+                        .responses
+                        .insert("401".to_string(), unauthorized.clone().into());
+                }
+            }
+        }
+
+        if let Some(mut health) = openapi.paths.paths.get("/health/live").cloned() {
+            // Operation IDs must be unique, so the alias gets its own
+            for operation in operations(&mut health) {
+                operation.operation_id = Some("health".to_string());
+            }
+            openapi.paths.paths.insert("/health".to_string(), health);
+        }
+    }
+}
+
+/// The operations of a path, one per method.
+fn operations(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.patch,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// The OpenAPI document for every endpoint.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Ticket API",
+        description = "An asynchronous REST API for ticket management."
+    ),
+    paths(
+        handlers::create_ticket,
+        handlers::create_tickets,
+        handlers::validate_ticket,
+        handlers::list_tickets,
+        handlers::get_ticket,
+        handlers::get_tickets,
+        handlers::patch_ticket,
+        handlers::put_ticket,
+        handlers::delete_ticket,
+        handlers::restore_ticket,
+        handlers::list_trash,
+        handlers::restore_from_trash,
+        handlers::transition_ticket,
+        handlers::cas_status,
+        handlers::reorder_ticket,
+        handlers::reopen_ticket,
+        handlers::undo_ticket,
+        handlers::apply_transaction,
+        handlers::search_tickets,
+        handlers::search_all,
+        handlers::diff_tickets,
+        handlers::count_tickets,
+        handlers::lead_time,
+        handlers::ticket_matrix,
+        handlers::list_assignees,
+        handlers::upsert_ticket,
+        handlers::bulk_update_custom_fields,
+        handlers::bulk_update_status,
+        handlers::ticket_events,
+        handlers::wait_ticket,
+        handlers::list_changes,
+        handlers::list_pending_sync,
+        handlers::mark_synced,
+        handlers::add_tag,
+        handlers::remove_tag,
+        handlers::add_comment,
+        handlers::add_comments,
+        handlers::list_comments,
+        handlers::ticket_history,
+        handlers::time_in_status,
+        handlers::block_ticket,
+        handlers::unblock_ticket,
+        handlers::set_parent,
+        handlers::clear_parent,
+        handlers::export_csv,
+        handlers::export_fixture,
+        handlers::load_fixture,
+        handlers::export_snapshot,
+        handlers::restore_snapshot,
+        handlers::purge_deleted,
+        handlers::metrics,
+        handlers::health_check,
+        handlers::readiness_check,
+        handlers::openapi_json,
+    ),
+    components(schemas(
+        Ticket,
+        TicketId,
+        TicketResponse,
+        Status,
+        Priority,
+        SyncState,
+        Relationships,
+        Comment,
+        CommentMatch,
+        AuditEntry,
+        ChangeRecord,
+        SlaStatus,
+        FieldDiff,
+        FieldError,
+        LeadTimeStats,
+        TimeInStatus,
+        AssigneeSummary,
+        Fixture,
+        FixtureIdempotencyKey,
+        Snapshot,
+        CreateTicketRequest,
+        UpsertTicketRequest,
+        ReplaceTicketRequest,
+        PatchTicketRequest,
+        AddCommentRequest,
+        ImportCommentRequest,
+        AddTagRequest,
+        ReopenTicketRequest,
+        TransitionRequest,
+        CasStatusRequest,
+        ReorderRequest,
+        BacklogEnd,
+        handlers::ValidateMode,
+        TransactionRequest,
+        TransactionOperation,
+        BulkCustomFieldsRequest,
+        BulkStatusRequest,
+        BatchGetRequest,
+        ErrorBody,
+        ErrorObject,
+        TicketPage,
+        TicketList,
+        SearchHit,
+        SearchResults,
+        BatchCreated,
+        CommentsImported,
+        BatchItemError,
+        BatchTickets,
+        TransactionResults,
+        TransitionResult,
+        UndoResult,
+        BulkResults,
+        BulkResult,
+        TicketDiff,
+        TicketCount,
+        AssigneeList,
+        ChangeFeed,
+        CommentList,
+        HistoryList,
+        TimeInStatusReport,
+        FixtureLoaded,
+        Purged,
+        Health,
+        Readiness,
+    )),
+    modifiers(&RouterDetails),
+    tags(
+        (name = "tickets", description = "Ticket management"),
+        (name = "admin", description = "Fixture import and export, behind the admin token"),
+        (name = "monitoring", description = "Health checks, metrics and this document"),
+    )
+)]
+pub struct ApiDoc;
@@ -0,0 +1,710 @@
+//! Storage abstraction for the Ticket API.
+//!
+//! Handlers talk to storage only through [`TicketRepository`], so a different
+//! backend can be plugged into the router without touching handler code. The
+//! in-memory [`TicketStore`] is the default implementation.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::config::ServerConfig;
+use crate::data::{
+    AssigneeSummary, AuditEntry, ChangeRecord, Comment, CommentDraft, CommentMatch, Cursor,
+    CustomFields, Fixture, LeadTimeStats, PatchTicketRequest, Priority, ReorderTarget, SlaStatus,
+    Status, StatusTransition, Ticket, TicketDraft, TicketEvent, TicketFilter, TicketId,
+    TicketOperation, TicketTag, TimeInStatus,
+};
+use crate::store::{StoreError, TicketStore};
+
+/// Operations a ticket storage backend must provide.
+///
+/// Implementations are shared across requests, so they must be cheap to call
+/// concurrently. See [`TicketStore`] for the reference semantics of each method.
+#[async_trait]
+pub trait TicketRepository: Send + Sync {
+    /// Returns the policies the backend applies, such as validation limits.
+    fn config(&self) -> &ServerConfig;
+
+    /// Returns the current write version, bumped after every successful mutation.
+    fn version(&self) -> u64;
+
+    /// Reports whether the backend can serve requests right now.
+    async fn is_ready(&self) -> bool;
+
+    /// Waits briefly for the write version to reach `min_version`, returning
+    /// whether it did.
+    async fn wait_for_version(&self, min_version: u64) -> bool;
+
+    /// Subscribes to the events of every mutation from now on.
+    fn subscribe(&self) -> broadcast::Receiver<TicketEvent>;
+
+    /// Returns the retained changes after sequence `since` and the current
+    /// sequence.
+    fn changes_since(&self, since: u64) -> Result<(Vec<ChangeRecord>, u64), StoreError>;
+
+    /// Records a ticket creation by `reporter` against the creation throttle.
+    fn throttle_creation(&self, reporter: &str) -> Result<(), StoreError>;
+
+    /// Checks a draft against the backend's custom field policies.
+    fn validate_draft(&self, draft: &TicketDraft) -> Result<(), StoreError>;
+
+    /// Stores a new ticket built from `draft` and returns it, failing
+    /// if the backend is full.
+    async fn add_ticket(&self, draft: TicketDraft) -> Result<Ticket, StoreError>;
+
+    /// Returns the ticket created earlier with idempotency key `key`, if the
+    /// key is still remembered. Fails if the key came with another request.
+    fn idempotent_replay(&self, key: &str, fingerprint: u64) -> Result<Option<Ticket>, StoreError>;
+
+    /// Stores a new ticket built from `draft` unless `key` already created
+    /// one, atomically. The flag is `true` if it was created.
+    async fn add_ticket_idempotent(
+        &self,
+        key: &str,
+        fingerprint: u64,
+        draft: TicketDraft,
+    ) -> Result<(Ticket, bool), StoreError>;
+
+    /// Stores many new tickets at once and returns them as created, in draft
+    /// order. Stores none if they don't all fit.
+    async fn add_tickets(&self, drafts: Vec<TicketDraft>) -> Result<Vec<Ticket>, StoreError>;
+
+    /// Returns the ticket mirrored from `external_ref`, creating it from
+    /// `draft` if there is none. The flag is `true` if it was created.
+    async fn get_or_create_by_ref(
+        &self,
+        external_ref: &str,
+        draft: TicketDraft,
+    ) -> Result<(Ticket, bool), StoreError>;
+
+    /// Retrieves a ticket by its ID.
+    async fn get_ticket(&self, id: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Retrieves the live tickets among `ids`, and the IDs that matched none.
+    async fn get_many(&self, ids: &[TicketId]) -> (Vec<Ticket>, Vec<TicketId>);
+
+    /// Updates the fields present in `patch`, if the ticket is at
+    /// `expected_version` (or unconditionally when `None`). `force` bypasses
+    /// the description shrink guard, and `user` is checked against the
+    /// assignee when only they may change the status. The flag is `false`
+    /// when the patch changed nothing.
+    async fn patch_ticket(
+        &self,
+        id: &TicketId,
+        patch: PatchTicketRequest,
+        expected_version: Option<u64>,
+        force: bool,
+        user: Option<&str>,
+    ) -> Result<(Ticket, bool), StoreError>;
+
+    /// Replaces every field of a ticket except its ID, creation time and
+    /// external reference, if the ticket is at `expected_version` (or
+    /// unconditionally when `None`).
+    async fn replace_ticket(
+        &self,
+        id: &TicketId,
+        draft: TicketDraft,
+        status: Status,
+        expected_version: Option<u64>,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError>;
+
+    /// Applies several operations all or nothing. On failure the store is
+    /// unchanged and the index of the failing operation is returned.
+    async fn apply_transaction(
+        &self,
+        operations: Vec<TicketOperation>,
+        user: Option<&str>,
+    ) -> Result<Vec<Option<Ticket>>, (usize, StoreError)>;
+
+    /// Deletes a ticket by moving it to the trash, like `soft_delete`.
+    async fn delete_ticket(&self, id: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Marks a ticket as deleted, hiding it until restored.
+    async fn soft_delete(&self, id: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Brings back a soft-deleted ticket; a no-op for live tickets.
+    async fn restore(&self, id: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Retrieves the soft-deleted tickets, the most recently deleted first.
+    async fn list_trash(&self) -> Vec<Ticket>;
+
+    /// Brings back a soft-deleted ticket; fails for live tickets.
+    async fn restore_from_trash(&self, id: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Moves a ticket to another status allowed from its current one.
+    async fn transition_status(
+        &self,
+        id: &TicketId,
+        to: Status,
+        user: Option<&str>,
+    ) -> Result<StatusTransition, StoreError>;
+
+    /// Moves a ticket to `new` only if its status is still `expected`.
+    async fn compare_and_swap_status(
+        &self,
+        id: &TicketId,
+        expected: Status,
+        new: Status,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError>;
+
+    /// Moves a ticket to another position in the backlog.
+    async fn reorder(&self, id: &TicketId, target: ReorderTarget) -> Result<Ticket, StoreError>;
+
+    /// Moves a `Done` ticket back to `InProgress`, or `ToDo` with `backlog`.
+    async fn reopen(
+        &self,
+        id: &TicketId,
+        reason: Option<String>,
+        backlog: bool,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError>;
+
+    /// Retrieves all live tickets, ordered by creation time, then ID.
+    async fn list_tickets(&self) -> Vec<Ticket>;
+
+    /// Streams every live ticket in creation order, reading each one only
+    /// when the stream reaches it.
+    async fn stream_tickets(&self) -> BoxStream<'static, Ticket>;
+
+    /// Captures every ticket and the write version as one consistent fixture.
+    async fn export_fixture(&self) -> Fixture;
+
+    /// Replaces the whole store with a fixture, validating it first.
+    async fn load_fixture(&self, fixture: Fixture) -> Result<(), StoreError>;
+
+    /// Captures every ticket, soft-deleted ones included, as one snapshot.
+    async fn export_snapshot(&self) -> Vec<Ticket>;
+
+    /// Replaces every ticket with a snapshot's, validating all of them first.
+    async fn import_snapshot(&self, tickets: Vec<Ticket>) -> Result<u64, StoreError>;
+
+    /// Serializes one page of tickets matching `filter`, ordered like
+    /// [`Self::list_tickets`], as a JSON array of ticket responses appended
+    /// to `out`, and counts the matches.
+    async fn write_page_json(
+        &self,
+        filter: &TicketFilter,
+        offset: usize,
+        limit: usize,
+        out: &mut Vec<u8>,
+    ) -> serde_json::Result<usize>;
+
+    /// Reverts the most recent undoable change to a ticket, reporting the
+    /// reverted field.
+    async fn undo_last_change(
+        &self,
+        id: &TicketId,
+        user: Option<&str>,
+    ) -> Result<(Ticket, String), StoreError>;
+
+    /// Retrieves the tickets matching every predicate of `filter`, ordered
+    /// like [`Self::list_tickets`].
+    async fn query(&self, filter: &TicketFilter) -> Vec<Ticket>;
+
+    /// Finds the live tickets titled `title`, ignoring case and surrounding
+    /// whitespace, ordered like [`Self::list_tickets`].
+    async fn find_by_title(&self, title: &str) -> Vec<TicketId>;
+
+    /// Finds tickets whose title or description contains `query`, ignoring
+    /// case, ordered like [`Self::list_tickets`].
+    async fn search(&self, query: &str) -> Vec<Ticket>;
+
+    /// Finds tickets whose title, description or comments contain `query`,
+    /// ignoring case, each with its matching comments.
+    async fn search_all(&self, query: &str) -> Vec<(Ticket, Vec<CommentMatch>)>;
+
+    /// Retrieves one page of tickets along with the total ticket count.
+    async fn list_tickets_paginated(
+        &self,
+        offset: usize,
+        limit: usize,
+        include_deleted: bool,
+    ) -> (Vec<Ticket>, usize);
+
+    /// Retrieves tickets in ID order after `after`, stopping at `deadline`.
+    /// Returns a continuation ID when the listing is incomplete.
+    async fn list_tickets_partial(
+        &self,
+        after: Option<&TicketId>,
+        deadline: Option<tokio::time::Instant>,
+        include_deleted: bool,
+    ) -> (Vec<Ticket>, Option<TicketId>);
+
+    /// Retrieves up to `limit` live tickets after `cursor`, in creation
+    /// order, and the cursor for the next page.
+    async fn list_after_cursor(
+        &self,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> (Vec<Ticket>, Option<Cursor>);
+
+    /// Retrieves the tickets the sync client hasn't seen yet.
+    async fn list_pending_sync(&self) -> Vec<Ticket>;
+
+    /// Marks a ticket as seen by the sync client.
+    async fn mark_synced(&self, id: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Adds a tag to a ticket; adding one it already has changes nothing.
+    async fn add_tag(&self, id: &TicketId, tag: TicketTag) -> Result<Ticket, StoreError>;
+
+    /// Removes a tag from a ticket, failing if the ticket doesn't have it.
+    async fn remove_tag(&self, id: &TicketId, tag: &str) -> Result<Ticket, StoreError>;
+
+    /// Records that ticket `id` blocks ticket `other`, refusing self-links,
+    /// missing targets and cycles.
+    async fn add_block(&self, id: &TicketId, other: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Removes the link saying ticket `id` blocks ticket `other`, if any.
+    async fn remove_block(&self, id: &TicketId, other: &TicketId) -> Result<Ticket, StoreError>;
+
+    /// Sets or clears a ticket's parent, refusing self-links, missing
+    /// targets and cycles.
+    async fn set_parent(
+        &self,
+        id: &TicketId,
+        parent: Option<&TicketId>,
+    ) -> Result<Ticket, StoreError>;
+
+    /// Appends a comment to a ticket.
+    async fn add_comment(&self, id: &TicketId, draft: CommentDraft) -> Result<Comment, StoreError>;
+
+    /// Appends several comments to a ticket at once, keeping their order.
+    async fn add_comments(
+        &self,
+        id: &TicketId,
+        drafts: Vec<CommentDraft>,
+    ) -> Result<Vec<Comment>, StoreError>;
+
+    /// Retrieves a ticket's comments, oldest first.
+    async fn list_comments(&self, id: &TicketId) -> Result<Vec<Comment>, StoreError>;
+
+    /// Retrieves the audit log of a ticket's field changes, oldest first.
+    async fn get_history(&self, id: &TicketId) -> Result<Vec<AuditEntry>, StoreError>;
+
+    /// Computes how long a ticket has spent in every status.
+    async fn time_in_status(&self, id: &TicketId) -> Result<Vec<TimeInStatus>, StoreError>;
+
+    /// Computes where a ticket stands against the SLA target of its status,
+    /// or `None` when SLA tracking doesn't cover it.
+    fn sla_status(&self, ticket: &Ticket) -> Option<SlaStatus>;
+
+    /// Computes lead time statistics for the `Done` tickets completed in
+    /// `[from, to)`.
+    async fn lead_time_stats(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> LeadTimeStats;
+
+    /// Counts tickets in total and for every status.
+    async fn count_by_status(&self) -> (usize, HashMap<Status, usize>);
+
+    /// Sums the story point estimates of live tickets that aren't `Done`.
+    async fn total_estimate(&self) -> u64;
+
+    /// Counts live tickets per assignee, ordered by name, with unassigned
+    /// tickets last.
+    async fn assignee_summary(&self) -> Vec<AssigneeSummary>;
+
+    /// Counts tickets for every priority and status combination.
+    async fn count_by_priority_and_status(&self) -> HashMap<Priority, HashMap<Status, usize>>;
+
+    /// Applies the same custom field changes to many tickets, returning one
+    /// result per ID in input order.
+    async fn bulk_update_custom_fields(
+        &self,
+        ids: &[TicketId],
+        set: &CustomFields,
+        unset: &[String],
+    ) -> Vec<Result<Ticket, StoreError>>;
+
+    /// Applies the same custom field changes to many tickets, all or nothing.
+    /// On failure nothing is updated and the per-ID validation results are returned.
+    async fn bulk_update_custom_fields_atomic(
+        &self,
+        ids: &[TicketId],
+        set: &CustomFields,
+        unset: &[String],
+    ) -> Result<Vec<Ticket>, Vec<Result<Ticket, StoreError>>>;
+
+    /// Moves many tickets to the same status, returning one result per ID in
+    /// input order.
+    async fn bulk_update_status(
+        &self,
+        ids: &[TicketId],
+        status: Status,
+        user: Option<&str>,
+    ) -> Vec<Result<Ticket, StoreError>>;
+
+    /// Removes every `Done` ticket not updated for at least `older_than`,
+    /// returning how many were removed.
+    async fn purge_expired(&self, older_than: Duration) -> usize;
+
+    /// Removes the tickets soft-deleted at least `older_than` ago, or all of
+    /// them when `None`, returning how many were removed.
+    async fn purge_soft_deleted(&self, older_than: Option<Duration>) -> usize;
+}
+
+#[async_trait]
+impl TicketRepository for TicketStore {
+    fn config(&self) -> &ServerConfig {
+        TicketStore::config(self)
+    }
+
+    fn version(&self) -> u64 {
+        TicketStore::version(self)
+    }
+
+    async fn is_ready(&self) -> bool {
+        TicketStore::is_ready(self).await
+    }
+
+    async fn wait_for_version(&self, min_version: u64) -> bool {
+        TicketStore::wait_for_version(self, min_version).await
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<TicketEvent> {
+        TicketStore::subscribe(self)
+    }
+
+    fn changes_since(&self, since: u64) -> Result<(Vec<ChangeRecord>, u64), StoreError> {
+        TicketStore::changes_since(self, since)
+    }
+
+    fn throttle_creation(&self, reporter: &str) -> Result<(), StoreError> {
+        TicketStore::throttle_creation(self, reporter)
+    }
+
+    fn validate_draft(&self, draft: &TicketDraft) -> Result<(), StoreError> {
+        TicketStore::validate_draft(self, draft)
+    }
+
+    async fn add_ticket(&self, draft: TicketDraft) -> Result<Ticket, StoreError> {
+        TicketStore::add_ticket(self, draft).await
+    }
+
+    fn idempotent_replay(&self, key: &str, fingerprint: u64) -> Result<Option<Ticket>, StoreError> {
+        TicketStore::idempotent_replay(self, key, fingerprint)
+    }
+
+    async fn add_ticket_idempotent(
+        &self,
+        key: &str,
+        fingerprint: u64,
+        draft: TicketDraft,
+    ) -> Result<(Ticket, bool), StoreError> {
+        TicketStore::add_ticket_idempotent(self, key, fingerprint, draft).await
+    }
+
+    async fn add_tickets(&self, drafts: Vec<TicketDraft>) -> Result<Vec<Ticket>, StoreError> {
+        TicketStore::add_tickets(self, drafts).await
+    }
+
+    async fn get_or_create_by_ref(
+        &self,
+        external_ref: &str,
+        draft: TicketDraft,
+    ) -> Result<(Ticket, bool), StoreError> {
+        TicketStore::get_or_create_by_ref(self, external_ref, draft).await
+    }
+
+    async fn get_ticket(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::get_ticket(self, id).await
+    }
+
+    async fn get_many(&self, ids: &[TicketId]) -> (Vec<Ticket>, Vec<TicketId>) {
+        TicketStore::get_many(self, ids).await
+    }
+
+    async fn patch_ticket(
+        &self,
+        id: &TicketId,
+        patch: PatchTicketRequest,
+        expected_version: Option<u64>,
+        force: bool,
+        user: Option<&str>,
+    ) -> Result<(Ticket, bool), StoreError> {
+        TicketStore::patch_ticket(self, id, patch, expected_version, force, user).await
+    }
+
+    async fn replace_ticket(
+        &self,
+        id: &TicketId,
+        draft: TicketDraft,
+        status: Status,
+        expected_version: Option<u64>,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError> {
+        TicketStore::replace_ticket(self, id, draft, status, expected_version, user).await
+    }
+
+    async fn apply_transaction(
+        &self,
+        operations: Vec<TicketOperation>,
+        user: Option<&str>,
+    ) -> Result<Vec<Option<Ticket>>, (usize, StoreError)> {
+        TicketStore::apply_transaction(self, operations, user).await
+    }
+
+    async fn delete_ticket(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::delete_ticket(self, id).await
+    }
+
+    async fn soft_delete(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::soft_delete(self, id).await
+    }
+
+    async fn restore(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::restore(self, id).await
+    }
+
+    async fn list_trash(&self) -> Vec<Ticket> {
+        TicketStore::list_trash(self).await
+    }
+
+    async fn restore_from_trash(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::restore_from_trash(self, id).await
+    }
+
+    async fn transition_status(
+        &self,
+        id: &TicketId,
+        to: Status,
+        user: Option<&str>,
+    ) -> Result<StatusTransition, StoreError> {
+        TicketStore::transition_status(self, id, to, user).await
+    }
+
+    async fn compare_and_swap_status(
+        &self,
+        id: &TicketId,
+        expected: Status,
+        new: Status,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError> {
+        TicketStore::compare_and_swap_status(self, id, expected, new, user).await
+    }
+
+    async fn reorder(&self, id: &TicketId, target: ReorderTarget) -> Result<Ticket, StoreError> {
+        TicketStore::reorder(self, id, target).await
+    }
+
+    async fn reopen(
+        &self,
+        id: &TicketId,
+        reason: Option<String>,
+        backlog: bool,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError> {
+        TicketStore::reopen(self, id, reason, backlog, user).await
+    }
+
+    async fn list_tickets(&self) -> Vec<Ticket> {
+        TicketStore::list_tickets(self).await
+    }
+
+    async fn stream_tickets(&self) -> BoxStream<'static, Ticket> {
+        TicketStore::stream_tickets(self).await
+    }
+
+    async fn export_fixture(&self) -> Fixture {
+        TicketStore::export_fixture(self).await
+    }
+
+    async fn load_fixture(&self, fixture: Fixture) -> Result<(), StoreError> {
+        TicketStore::load_fixture(self, fixture).await
+    }
+
+    async fn export_snapshot(&self) -> Vec<Ticket> {
+        TicketStore::export_snapshot(self).await
+    }
+
+    async fn import_snapshot(&self, tickets: Vec<Ticket>) -> Result<u64, StoreError> {
+        TicketStore::import_snapshot(self, tickets).await
+    }
+
+    async fn write_page_json(
+        &self,
+        filter: &TicketFilter,
+        offset: usize,
+        limit: usize,
+        out: &mut Vec<u8>,
+    ) -> serde_json::Result<usize> {
+        TicketStore::write_page_json(self, filter, offset, limit, out).await
+    }
+
+    async fn undo_last_change(
+        &self,
+        id: &TicketId,
+        user: Option<&str>,
+    ) -> Result<(Ticket, String), StoreError> {
+        TicketStore::undo_last_change(self, id, user).await
+    }
+
+    async fn query(&self, filter: &TicketFilter) -> Vec<Ticket> {
+        TicketStore::query(self, filter).await
+    }
+
+    async fn find_by_title(&self, title: &str) -> Vec<TicketId> {
+        TicketStore::find_by_title(self, title).await
+    }
+
+    async fn search(&self, query: &str) -> Vec<Ticket> {
+        TicketStore::search(self, query).await
+    }
+
+    async fn search_all(&self, query: &str) -> Vec<(Ticket, Vec<CommentMatch>)> {
+        TicketStore::search_all(self, query).await
+    }
+
+    async fn list_tickets_paginated(
+        &self,
+        offset: usize,
+        limit: usize,
+        include_deleted: bool,
+    ) -> (Vec<Ticket>, usize) {
+        TicketStore::list_tickets_paginated(self, offset, limit, include_deleted).await
+    }
+
+    async fn list_tickets_partial(
+        &self,
+        after: Option<&TicketId>,
+        deadline: Option<tokio::time::Instant>,
+        include_deleted: bool,
+    ) -> (Vec<Ticket>, Option<TicketId>) {
+        TicketStore::list_tickets_partial(self, after, deadline, include_deleted).await
+    }
+
+    async fn list_after_cursor(
+        &self,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> (Vec<Ticket>, Option<Cursor>) {
+        TicketStore::list_after_cursor(self, cursor, limit).await
+    }
+
+    async fn list_pending_sync(&self) -> Vec<Ticket> {
+        TicketStore::list_pending_sync(self).await
+    }
+
+    async fn mark_synced(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::mark_synced(self, id).await
+    }
+
+    async fn add_tag(&self, id: &TicketId, tag: TicketTag) -> Result<Ticket, StoreError> {
+        TicketStore::add_tag(self, id, tag).await
+    }
+
+    async fn remove_tag(&self, id: &TicketId, tag: &str) -> Result<Ticket, StoreError> {
+        TicketStore::remove_tag(self, id, tag).await
+    }
+
+    async fn add_block(&self, id: &TicketId, other: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::add_block(self, id, other).await
+    }
+
+    async fn remove_block(&self, id: &TicketId, other: &TicketId) -> Result<Ticket, StoreError> {
+        TicketStore::remove_block(self, id, other).await
+    }
+
+    async fn set_parent(
+        &self,
+        id: &TicketId,
+        parent: Option<&TicketId>,
+    ) -> Result<Ticket, StoreError> {
+        TicketStore::set_parent(self, id, parent).await
+    }
+
+    async fn add_comment(&self, id: &TicketId, draft: CommentDraft) -> Result<Comment, StoreError> {
+        TicketStore::add_comment(self, id, draft).await
+    }
+
+    async fn add_comments(
+        &self,
+        id: &TicketId,
+        drafts: Vec<CommentDraft>,
+    ) -> Result<Vec<Comment>, StoreError> {
+        TicketStore::add_comments(self, id, drafts).await
+    }
+
+    async fn list_comments(&self, id: &TicketId) -> Result<Vec<Comment>, StoreError> {
+        TicketStore::list_comments(self, id).await
+    }
+
+    async fn get_history(&self, id: &TicketId) -> Result<Vec<AuditEntry>, StoreError> {
+        TicketStore::get_history(self, id).await
+    }
+
+    async fn time_in_status(&self, id: &TicketId) -> Result<Vec<TimeInStatus>, StoreError> {
+        TicketStore::time_in_status(self, id).await
+    }
+
+    fn sla_status(&self, ticket: &Ticket) -> Option<SlaStatus> {
+        TicketStore::sla_status(self, ticket)
+    }
+
+    async fn lead_time_stats(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> LeadTimeStats {
+        TicketStore::lead_time_stats(self, from, to).await
+    }
+
+    async fn count_by_status(&self) -> (usize, HashMap<Status, usize>) {
+        TicketStore::count_by_status(self).await
+    }
+
+    async fn total_estimate(&self) -> u64 {
+        TicketStore::total_estimate(self).await
+    }
+
+    async fn assignee_summary(&self) -> Vec<AssigneeSummary> {
+        TicketStore::assignee_summary(self).await
+    }
+
+    async fn count_by_priority_and_status(&self) -> HashMap<Priority, HashMap<Status, usize>> {
+        TicketStore::count_by_priority_and_status(self).await
+    }
+
+    async fn bulk_update_custom_fields(
+        &self,
+        ids: &[TicketId],
+        set: &CustomFields,
+        unset: &[String],
+    ) -> Vec<Result<Ticket, StoreError>> {
+        TicketStore::bulk_update_custom_fields(self, ids, set, unset).await
+    }
+
+    async fn bulk_update_custom_fields_atomic(
+        &self,
+        ids: &[TicketId],
+        set: &CustomFields,
+        unset: &[String],
+    ) -> Result<Vec<Ticket>, Vec<Result<Ticket, StoreError>>> {
+        TicketStore::bulk_update_custom_fields_atomic(self, ids, set, unset).await
+    }
+
+    async fn bulk_update_status(
+        &self,
+        ids: &[TicketId],
+        status: Status,
+        user: Option<&str>,
+    ) -> Vec<Result<Ticket, StoreError>> {
+        TicketStore::bulk_update_status(self, ids, status, user).await
+    }
+
+    async fn purge_expired(&self, older_than: Duration) -> usize {
+        TicketStore::purge_expired(self, older_than).await
+    }
+
+    async fn purge_soft_deleted(&self, older_than: Option<Duration>) -> usize {
+        TicketStore::purge_soft_deleted(self, older_than).await
+    }
+}
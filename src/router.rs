@@ -0,0 +1,210 @@
+//! Router construction for the Ticket API.
+//!
+//! Building the [`Router`] in the library rather than in `main.rs` lets the
+//! whole application be constructed and exercised in-process, without
+//! binding a socket.
+
+use axum::{
+    error_handling::HandleErrorLayer,
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, patch, post, put},
+    Router,
+};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tower::ServiceBuilder;
+use tower_http::compression::predicate::{DefaultPredicate, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer};
+
+use crate::config::{CorsPolicy, ServerConfig};
+use crate::handlers::{
+    add_comment, add_comments, add_tag, apply_transaction, block_ticket, bulk_update_custom_fields,
+    bulk_update_status, cas_status, clear_parent, count_tickets, create_ticket, create_tickets,
+    delete_ticket, diff_tickets, export_csv, export_fixture, export_snapshot, get_ticket,
+    get_tickets, health_check, lead_time, list_assignees, list_changes, list_comments,
+    list_pending_sync, list_tickets, list_trash, load_fixture, mark_synced, metrics, openapi_json,
+    patch_ticket, purge_deleted, put_ticket, readiness_check, remove_tag, reopen_ticket,
+    reorder_ticket, restore_from_trash, restore_snapshot, restore_ticket, search_all,
+    search_tickets, set_parent, ticket_events, ticket_history, ticket_matrix, time_in_status,
+    transition_ticket, unblock_ticket, undo_ticket, upsert_ticket, validate_ticket, wait_ticket,
+    AppState,
+};
+use crate::metrics::Metrics;
+use crate::middleware::{
+    legacy_field_names, limit_body_size, rate_limit, read_your_writes, request_logging,
+    request_timed_out, require_admin, require_api_key, track_metrics, verbose_errors, RateLimiter,
+};
+use crate::store::TicketStore;
+
+/// Smallest response body, in bytes, worth compressing.
+pub const COMPRESSION_MIN_BYTES: u16 = 1024;
+
+/// Builds the application router with a fresh store using `config`.
+///
+/// The store, all routes, the API key check and body size limit on `/tickets`
+/// routes, the request timeout on all but the event stream and long poll,
+/// the request logging and metrics middleware, response compression, the
+/// CORS layer selected by `config.cors`, the rate limiter, the legacy field
+/// renaming middleware, the read-your-writes middleware and the error
+/// verbosity middleware are wired up; the returned router is ready to be
+/// served.
+///
+/// The rate limiter keys clients by IP, so serve the router with
+/// `into_make_service_with_connect_info::<SocketAddr>()`; without connect
+/// info, no client is limited. `X-Forwarded-For` is only believed from the
+/// limit's trusted proxies.
+pub fn build_router(config: ServerConfig) -> Router {
+    let repository: AppState = Arc::new(TicketStore::with_config(config.clone()));
+    build_router_with_repository(config, repository)
+}
+
+/// Builds the application router around an existing storage backend.
+///
+/// Only the router-level parts of `config` (`cors`, `legacy_fields`,
+/// `admin_token`, `api_key`, `request_logging`, `max_body_bytes`,
+/// `request_timeout`, `rate_limit`, `max_event_subscribers` and
+/// `verbose_errors`) are used here; store policies are up to the repository
+/// itself. Every router counts its own [`Metrics`].
+pub fn build_router_with_repository(config: ServerConfig, repository: AppState) -> Router {
+    let cors = match config.cors {
+        CorsPolicy::Permissive => CorsLayer::permissive(),
+        CorsPolicy::Disabled => CorsLayer::new(),
+        CorsPolicy::AllowList(allow_list) => CorsLayer::new()
+            .allow_origin(AllowOrigin::list(allow_list.origins))
+            .allow_methods(allow_list.methods)
+            .allow_headers(AllowHeaders::mirror_request())
+            .allow_credentials(allow_list.allow_credentials),
+    };
+
+    let legacy_fields = Arc::new(config.legacy_fields);
+    let admin_token = Arc::new(config.admin_token);
+    let api_key = Arc::new(config.api_key);
+    let request_metrics = Arc::new(Metrics::new());
+    let rate_limiter = Arc::new(config.rate_limit.map(RateLimiter::new));
+
+    let admin = Router::new()
+        .route("/admin/fixture", get(export_fixture))
+        .route("/admin/fixture", post(load_fixture))
+        .route("/admin/snapshot", get(export_snapshot))
+        .route("/admin/restore", post(restore_snapshot))
+        .route("/admin/purge-deleted", post(purge_deleted))
+        .route_layer(middleware::from_fn_with_state(admin_token, require_admin));
+
+    let monitoring = Router::new()
+        .route("/metrics", get(metrics))
+        .with_state((request_metrics.clone(), repository.clone()));
+
+    let tickets = Router::new()
+        .route("/tickets", post(create_ticket))
+        .route("/tickets", get(list_tickets))
+        .route("/tickets/:id", get(get_ticket))
+        .route("/tickets/:id", patch(patch_ticket))
+        .route("/tickets/:id", put(put_ticket))
+        .route("/tickets/:id", delete(delete_ticket))
+        .route("/tickets/:id/restore", post(restore_ticket))
+        .route("/tickets/trash", get(list_trash))
+        .route("/tickets/trash/:id/restore", post(restore_from_trash))
+        .route("/tickets/:id/transition", post(transition_ticket))
+        .route("/tickets/:id/cas-status", post(cas_status))
+        .route("/tickets/:id/reorder", post(reorder_ticket))
+        .route("/tickets/:id/reopen", post(reopen_ticket))
+        .route("/tickets/:id/undo", post(undo_ticket))
+        .route("/tickets/batch", post(create_tickets))
+        .route("/tickets/batch-get", post(get_tickets))
+        .route("/tickets/validate", post(validate_ticket))
+        .route("/tickets/transaction", post(apply_transaction))
+        .route("/tickets/search", get(search_tickets))
+        .route("/tickets/search-all", get(search_all))
+        .route("/tickets/export.csv", get(export_csv))
+        .route("/tickets/diff", get(diff_tickets))
+        .route("/tickets/count", get(count_tickets))
+        .route("/tickets/lead-time", get(lead_time))
+        .route("/tickets/matrix", get(ticket_matrix))
+        .route("/tickets/assignees", get(list_assignees))
+        .route("/tickets/upsert", post(upsert_ticket))
+        .route("/tickets/bulk-custom", post(bulk_update_custom_fields))
+        .route("/tickets/bulk-status", post(bulk_update_status))
+        .route("/tickets/changes", get(list_changes))
+        .route("/tickets/pending-sync", get(list_pending_sync))
+        .route("/tickets/:id/mark-synced", post(mark_synced))
+        .route("/tickets/:id/tags", post(add_tag))
+        .route("/tickets/:id/tags/:tag", delete(remove_tag))
+        .route("/tickets/:id/comments", post(add_comment))
+        .route("/tickets/:id/comments", get(list_comments))
+        .route("/tickets/:id/comments/batch", post(add_comments))
+        .route("/tickets/:id/history", get(ticket_history))
+        .route("/tickets/:id/time-in-status", get(time_in_status))
+        .route("/tickets/:id/block/:other", post(block_ticket))
+        .route("/tickets/:id/block/:other", delete(unblock_ticket))
+        .route("/tickets/:id/parent/:parent", post(set_parent))
+        .route("/tickets/:id/parent", delete(clear_parent))
+        .route_layer(middleware::from_fn_with_state(
+            api_key.clone(),
+            require_api_key,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            config.max_body_bytes,
+            limit_body_size,
+        ))
+        .layer(DefaultBodyLimit::max(config.max_body_bytes));
+
+    // The event stream stays open for as long as the client listens, and a
+    // long poll for as long as it asked to wait, so both are kept out of the
+    // request timeout
+    let subscribers = config
+        .max_event_subscribers
+        .map(|max| Arc::new(Semaphore::new(max)));
+    let event_stream = Router::new()
+        .route("/tickets/events", get(ticket_events))
+        .with_state((repository.clone(), subscribers));
+    let events = Router::new()
+        .merge(event_stream)
+        .route("/tickets/:id/wait", get(wait_ticket))
+        .route_layer(middleware::from_fn_with_state(api_key, require_api_key));
+
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/health/live", get(health_check))
+        .route("/health/ready", get(readiness_check))
+        .route("/openapi.json", get(openapi_json))
+        .merge(tickets)
+        .merge(admin)
+        .merge(monitoring)
+        .layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(request_timed_out))
+                .timeout(config.request_timeout),
+        )
+        .merge(events)
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    config.request_logging,
+                    request_logging,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    request_metrics,
+                    track_metrics,
+                ))
+                .layer(CompressionLayer::new().compress_when(
+                    DefaultPredicate::new().and(SizeAbove::new(COMPRESSION_MIN_BYTES)),
+                ))
+                .layer(cors)
+                .layer(middleware::from_fn_with_state(rate_limiter, rate_limit))
+                .layer(middleware::from_fn_with_state(
+                    legacy_fields,
+                    legacy_field_names,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    repository.clone(),
+                    read_your_writes,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    config.verbose_errors,
+                    verbose_errors,
+                )),
+        )
+        .with_state(repository)
+}
@@ -1,8 +1,23 @@
-use crate::data::{PatchTicketRequest, Status, Ticket, TicketDraft, TicketId};
-use std::collections::HashMap;
-use std::sync::Arc;
+use crate::config::ServerConfig;
+use crate::data::{
+    estimate_error, validate_custom_fields, AssigneeSummary, AuditEntry, ChangeRecord, Comment,
+    CommentDraft, CommentMatch, Cursor, CustomFields, FieldDiff, FieldError, Fixture,
+    FixtureIdempotencyKey, LeadTimeStats, PatchTicketRequest, Priority, Relationships,
+    ReorderTarget, SlaStatus, Status, StatusTransition, SyncState, Ticket, TicketAssignee,
+    TicketDescription, TicketDraft, TicketEvent, TicketFilter, TicketId, TicketOperation,
+    TicketResponseRef, TicketTag, TicketTitle, TimeInStatus,
+};
+use crate::id::{IdGenerator, UuidV4Generator};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{
+    broadcast, watch, OwnedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+use uuid::Uuid;
 
 /// Errors that can occur in the ticket store.
 /// Errors that can occur during ticket store operations.
@@ -15,8 +30,134 @@ pub enum StoreError {
     /// Returned when a field validation fails during update.
     #[error("Invalid field: {0}")]
     InvalidField(String),
+
+    /// Returned when one or more fields of a patch fail validation.
+    #[error("Validation failed: {}", field_messages(.0))]
+    ValidationFailed(Vec<FieldError>),
+
+    /// Returned when a reporter exceeds the configured creation throttle.
+    #[error("Reporter {0} has exceeded the ticket creation limit")]
+    RateLimited(String),
+
+    /// Returned when a conditional update expected a different ticket version.
+    #[error("Expected ticket version {expected}, but the current version is {actual}")]
+    VersionConflict { expected: u64, actual: u64 },
+
+    /// Returned when a ticket lacks custom fields required by its kind.
+    #[error("Tickets of kind {kind} require custom fields: {}", missing.join(", "))]
+    MissingRequiredFields { kind: String, missing: Vec<String> },
+
+    /// Returned when a patch would shrink a description more than the guard allows.
+    #[error("Patch would shrink the description from {from} to {to} characters; pass force=true to allow it")]
+    DescriptionShrink { from: usize, to: usize },
+
+    /// Returned when the change log no longer holds every change after `since`.
+    #[error("Changes after sequence {since} are no longer retained; the oldest available start is {floor}")]
+    ChangesExpired { since: u64, floor: u64 },
+
+    /// Returned when removing a tag the ticket doesn't have.
+    #[error("Ticket has no tag {0}")]
+    TagNotFound(String),
+
+    /// Returned when storing new tickets would exceed the store's capacity.
+    #[error("The store holds at most {capacity} tickets")]
+    CapacityExceeded { capacity: usize },
+
+    /// Returned when reopening a ticket that isn't `Done`.
+    #[error("Only Done tickets can be reopened, but the ticket is {}", .0.name())]
+    NotDone(Status),
+
+    /// Returned when a ticket can't move from its status to the requested one.
+    #[error("Cannot move a ticket from {} to {}; allowed: {}", .from.name(), .to.name(), allowed_names(allowed))]
+    InvalidTransition {
+        from: Status,
+        to: Status,
+        allowed: Vec<Status>,
+    },
+
+    /// Returned when an idempotency key is reused for a different request.
+    #[error("Idempotency key {0} was already used with a different request")]
+    IdempotencyKeyReused(String),
+
+    /// Returned when undoing a change for a ticket whose history has nothing to undo.
+    #[error("Ticket {0} has no change to undo")]
+    NothingToUndo(TicketId),
+
+    /// Returned when a ticket's lock isn't acquired within the lock timeout.
+    #[error("Ticket {0} is busy; gave up waiting for it after {1:?}")]
+    Timeout(TicketId, Duration),
+
+    /// Returned when the ID generator keeps handing out IDs already in use.
+    #[error("Could not generate an unused ticket ID; {0} is already taken")]
+    IdCollision(TicketId),
+
+    /// Returned when a compare-and-swap finds the ticket in another status
+    /// than expected.
+    #[error("Expected the ticket to be {}, but it is {}", .expected.name(), .actual.name())]
+    StatusMismatch { expected: Status, actual: Status },
+
+    /// Returned when someone other than the assignee changes a ticket's
+    /// status while [`ServerConfig::status_owner_only`] is set.
+    #[error("Only the assignee {assignee} may change the status of this ticket")]
+    StatusOwnerOnly { assignee: String },
 }
 
+/// Joins status names for an error message, or `none` for no statuses.
+fn field_messages(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|error| format!("{}: {}", error.field, error.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn allowed_names(statuses: &[Status]) -> String {
+    if statuses.is_empty() {
+        return "none".to_string();
+    }
+    statuses
+        .iter()
+        .map(Status::name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The retained tail of the change log.
+#[derive(Debug, Default)]
+struct ChangeLog {
+    /// Retained changes, oldest first
+    records: VecDeque<ChangeRecord>,
+    /// Sequence of the newest change no longer retained; every change after
+    /// it is in `records`
+    floor: u64,
+}
+
+/// Ticket creations remembered by idempotency key.
+#[derive(Debug, Default)]
+struct IdempotencyKeys {
+    /// The ticket created for each key, and a fingerprint of its request
+    creations: HashMap<String, (u64, Ticket)>,
+    /// When each key was first used, oldest first
+    order: VecDeque<(Instant, String)>,
+}
+
+/// State derived from past writes, restored along with the tickets by
+/// [`TicketStore::load_fixture`].
+#[derive(Default)]
+struct DerivedState {
+    history: HashMap<TicketId, Vec<AuditEntry>>,
+    changes: VecDeque<ChangeRecord>,
+    /// `None` puts the floor at the new write version
+    floor: Option<u64>,
+    idempotency_keys: IdempotencyKeys,
+}
+
+/// Number of events buffered per subscriber before it starts missing events.
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Longest time [`TicketStore::is_ready`] waits for the store lock.
+pub const READY_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// Thread-safe, in-memory store for tickets.
 ///
 /// Uses a combination of [`Arc`] and [`RwLock`] to provide safe concurrent access
@@ -26,123 +167,4483 @@ pub enum StoreError {
 pub struct TicketStore {
     /// Inner storage using nested Arc and RwLock for fine-grained locking
     tickets: Arc<RwLock<HashMap<TicketId, Arc<RwLock<Ticket>>>>>,
+    /// Policies applied by store operations
+    config: Arc<ServerConfig>,
+    /// Recent creation times per reporter, used by the creation throttle
+    creations: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+    /// Monotonic write version, bumped after every successful mutation
+    version: Arc<watch::Sender<u64>>,
+    /// Publishes a [`TicketEvent`] for every mutation
+    events: broadcast::Sender<TicketEvent>,
+    /// The most recent changes, for incremental sync
+    changes: Arc<Mutex<ChangeLog>>,
+    /// Field-level audit log per ticket, kept out of [`Ticket`] so reads
+    /// and diffs aren't weighed down by it
+    history: Arc<Mutex<HashMap<TicketId, Vec<AuditEntry>>>>,
+    /// Tickets created with an `Idempotency-Key`, for replaying retries
+    idempotency_keys: Arc<Mutex<IdempotencyKeys>>,
+    /// Source of new ticket IDs
+    id_generator: Arc<dyn IdGenerator>,
+}
+
+impl Default for TicketStore {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TicketStore {
     /// Create a new, empty TicketStore.
     pub fn new() -> Self {
+        Self::with_config(ServerConfig::default())
+    }
+
+    /// Create a new, empty TicketStore applying the given configuration.
+    ///
+    /// New tickets get random UUIDs.
+    pub fn with_config(config: ServerConfig) -> Self {
+        Self::with_id_generator(config, Arc::new(UuidV4Generator))
+    }
+
+    /// Create a new, empty TicketStore holding at most `max` tickets.
+    ///
+    /// Soft-deleted tickets count toward the limit until they are removed
+    /// for good. Other policies keep their defaults.
+    pub fn with_capacity(max: usize) -> Self {
+        Self::with_config(ServerConfig {
+            max_tickets: Some(max),
+            ..ServerConfig::default()
+        })
+    }
+
+    /// Create a new, empty TicketStore that takes new ticket IDs from `id_generator`.
+    ///
+    /// The generator may be any [`IdGenerator`], a plain
+    /// `Fn() -> TicketId` closure included. It is called exactly once per
+    /// ticket created, batches and transactions included, and never for a
+    /// creation that is rejected or rolled back before its ID is needed.
+    /// Only a generated ID that is already in the store fails the creation
+    /// with [`StoreError::IdCollision`] after the call; loading a fixture or
+    /// snapshot tells the generator which IDs it holds, see
+    /// [`IdGenerator::skip`].
+    pub fn with_id_generator(config: ServerConfig, id_generator: Arc<dyn IdGenerator>) -> Self {
         Self {
             tickets: Arc::new(RwLock::new(HashMap::new())),
+            config: Arc::new(config),
+            creations: Arc::new(Mutex::new(HashMap::new())),
+            version: Arc::new(watch::Sender::new(0)),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            changes: Arc::new(Mutex::new(ChangeLog::default())),
+            history: Arc::new(Mutex::new(HashMap::new())),
+            idempotency_keys: Arc::new(Mutex::new(IdempotencyKeys::default())),
+            id_generator,
         }
     }
 
-    /// Add a new ticket from a draft. Returns the new ticket's ID.
-    /// Adds a new ticket to the store from a draft.
+    /// Subscribes to the events of every mutation from now on.
     ///
-    /// # Arguments
-    /// * `draft` - The validated ticket draft containing title and description
+    /// A subscriber that falls more than [`EVENT_CHANNEL_CAPACITY`] events
+    /// behind misses the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<TicketEvent> {
+        self.events.subscribe()
+    }
+
+    /// Publishes an event to current subscribers, if any.
+    fn publish(&self, event: TicketEvent) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.events.send(event);
+    }
+
+    /// Returns the configuration the store applies.
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    /// Returns the store's current write version.
+    ///
+    /// The version starts at 0 and increases by one after every successful
+    /// mutation, so a read that observes version `n` reflects every write up
+    /// to and including the `n`th.
+    pub fn version(&self) -> u64 {
+        *self.version.borrow()
+    }
+
+    /// Checks that the store can serve requests.
     ///
     /// # Returns
-    /// The ID of the newly created ticket
-    pub async fn add_ticket(&self, draft: TicketDraft) -> TicketId {
-        let id = TicketId::new();
-        let ticket = Ticket {
-            id: id.clone(),
-            title: draft.title,
-            description: draft.description,
-            status: Status::ToDo,
-        };
+    /// `true` if the store lock could be taken for reading within
+    /// [`READY_TIMEOUT`], `false` if a writer held it the whole time
+    pub async fn is_ready(&self) -> bool {
+        tokio::time::timeout(READY_TIMEOUT, self.tickets.read())
+            .await
+            .is_ok()
+    }
 
-        let ticket_arc = Arc::new(RwLock::new(ticket));
-        let mut tickets = self.tickets.write().await;
-        tickets.insert(id.clone(), ticket_arc);
+    /// Waits until the store has reached `min_version`.
+    ///
+    /// Waits at most the configured `read_your_writes.max_wait`.
+    ///
+    /// # Returns
+    /// `true` if the store reached `min_version`, `false` if the wait timed out
+    pub async fn wait_for_version(&self, min_version: u64) -> bool {
+        let mut receiver = self.version.subscribe();
+        let reached = receiver.wait_for(|version| *version >= min_version);
+        let result = tokio::time::timeout(self.config.read_your_writes.max_wait, reached).await;
+        matches!(result, Ok(Ok(_)))
+    }
+
+    /// Records a successful mutation.
+    ///
+    /// Bumps the write version, appends the change to the change log under
+    /// the new version, trimming the log to the configured retention, and
+    /// publishes the event.
+    ///
+    /// # Arguments
+    /// * `event` - What happened to which ticket
+    /// * `fields` - The names of the fields an update changed
+    fn record_change(&self, event: TicketEvent, fields: Vec<String>) {
+        let mut log = self.changes.lock().unwrap();
+        self.version.send_modify(|version| *version += 1);
+        log.records.push_back(ChangeRecord {
+            sequence: self.version(),
+            event: event.clone(),
+            fields,
+            at: Utc::now(),
+        });
+        while log.records.len() > self.config.change_log_retention {
+            if let Some(dropped) = log.records.pop_front() {
+                log.floor = dropped.sequence;
+            }
+        }
+        drop(log);
 
-        id
+        self.publish(event);
     }
 
-    /// Retrieve a ticket by its ID.
-    /// Retrieves a ticket by its ID.
+    /// Drops everything the store keeps about tickets removed for good.
+    ///
+    /// Their history, the idempotency keys that created them and their
+    /// change log records go, except for `Deleted` records, which tell a
+    /// client catching up on changes that the ticket is gone. Callers hold
+    /// the outer write lock and have marked the tickets deleted with
+    /// [`tombstone`], so no concurrent write can add to them again.
+    fn forget(&self, removed: &HashSet<TicketId>) {
+        if removed.is_empty() {
+            return;
+        }
+        let mut history = self.history.lock().unwrap();
+        history.retain(|id, _| !removed.contains(id));
+        drop(history);
+
+        let mut keys = self.idempotency_keys.lock().unwrap();
+        keys.creations
+            .retain(|_, (_, ticket)| !removed.contains(&ticket.id));
+        let IdempotencyKeys { creations, order } = &mut *keys;
+        order.retain(|(_, key)| creations.contains_key(key));
+        drop(keys);
+
+        let mut log = self.changes.lock().unwrap();
+        log.records.retain(|record| {
+            matches!(record.event, TicketEvent::Deleted { .. })
+                || !removed.contains(record.event.id())
+        });
+    }
+
+    /// Records an update made by a patch, a replacement or a transaction.
+    ///
+    /// Every changed field gets an [`AuditEntry`] in the ticket's history,
+    /// then the update is recorded like any other change.
     ///
     /// # Arguments
-    /// * `id` - The ID of the ticket to retrieve
+    /// * `id` - The ID of the updated ticket
+    /// * `changes` - The changed fields, see [`field_changes`]
+    fn record_update(&self, id: &TicketId, changes: Vec<FieldDiff>) {
+        self.record_update_because(id, changes, None);
+    }
+
+    /// Records an update like [`record_update`](Self::record_update), with
+    /// `reason` kept on each of its audit entries.
+    fn record_update_because(
+        &self,
+        id: &TicketId,
+        changes: Vec<FieldDiff>,
+        reason: Option<String>,
+    ) {
+        let fields = changes.iter().map(|change| change.field.clone()).collect();
+        if !changes.is_empty() {
+            let at = Utc::now();
+            let mut history = self.history.lock().unwrap();
+            let entries = history.entry(id.clone()).or_default();
+            for change in changes {
+                entries.push(AuditEntry {
+                    field: change.field,
+                    old: change.a,
+                    new: change.b,
+                    at,
+                    reason: reason.clone(),
+                });
+            }
+        }
+        self.record_change(TicketEvent::Updated { id: id.clone() }, fields);
+    }
+
+    /// Returns the audit log of a ticket's field changes, oldest first.
+    ///
+    /// Patches, replacements and transaction updates are audited, one entry
+    /// per changed field. Soft-deleted tickets keep their history readable.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket
     ///
     /// # Returns
-    /// * `Ok(Ticket)` - The requested ticket
+    /// * `Ok(Vec<AuditEntry>)` - The ticket's history, empty if it never changed
     /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
-    pub async fn get_ticket(&self, id: &TicketId) -> Result<Ticket, StoreError> {
-        let tickets = self.tickets.read().await;
-        match tickets.get(id) {
-            Some(ticket_arc) => {
-                let ticket = ticket_arc.read().await;
-                Ok(ticket.clone())
-            }
-            None => Err(StoreError::TicketNotFound(id.clone())),
+    pub async fn get_history(&self, id: &TicketId) -> Result<Vec<AuditEntry>, StoreError> {
+        if !self.tickets.read().await.contains_key(id) {
+            return Err(StoreError::TicketNotFound(id.clone()));
         }
+        let history = self.history.lock().unwrap();
+        Ok(history.get(id).cloned().unwrap_or_default())
     }
 
-    /// Patch a ticket by its ID using the provided patch request.
-    /// Updates specific fields of an existing ticket.
+    /// Computes how long a ticket has spent in each status, from its history.
+    ///
+    /// Each status change in the history ends a stay in the status left,
+    /// the first one starting at the ticket's creation, and the current
+    /// status counts up to now. A ticket whose history holds no status
+    /// change, such as one loaded from a snapshot, is counted in its current
+    /// status since `status_changed_at`.
     ///
     /// # Arguments
-    /// * `id` - The ID of the ticket to update
-    /// * `patch` - The patch request containing optional updates to title, description, and status
+    /// * `id` - The ID of the ticket
     ///
     /// # Returns
-    /// * `Ok(Ticket)` - The updated ticket
+    /// * `Ok(Vec<TimeInStatus>)` - The time in every status, in [`Status::ALL`]
+    ///   order, zero for the statuses the ticket was never in
     /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
-    /// * `Err(StoreError::InvalidField)` - If any of the updates fail validation
-    pub async fn patch_ticket(
+    /// * `Err(StoreError::Timeout)` - If the ticket stayed locked for longer
+    ///   than the configured `lock_timeout`
+    pub async fn time_in_status(&self, id: &TicketId) -> Result<Vec<TimeInStatus>, StoreError> {
+        let ticket = self.get_ticket(id).await?;
+        let left: Vec<(DateTime<Utc>, Status)> = self
+            .history
+            .lock()
+            .unwrap()
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.field == "status")
+            .filter_map(|entry| Some((entry.at, serde_json::from_value(entry.old.clone()).ok()?)))
+            .collect();
+
+        let mut totals: HashMap<Status, chrono::Duration> = HashMap::new();
+        let mut since = if left.is_empty() {
+            ticket.status_changed_at
+        } else {
+            ticket.created_at
+        };
+        for (at, status) in left {
+            *totals.entry(status).or_default() += at - since;
+            since = at;
+        }
+        *totals.entry(ticket.status).or_default() += Utc::now() - since;
+
+        Ok(Status::ALL
+            .into_iter()
+            .map(|status| TimeInStatus {
+                status,
+                seconds: totals
+                    .get(&status)
+                    .map_or(0.0, |time| time.num_milliseconds() as f64 / 1000.0),
+            })
+            .collect())
+    }
+
+    /// Reverts the most recent change to a ticket, one field at a time.
+    ///
+    /// The newest [`AuditEntry`] of a field in [`UNDOABLE_FIELDS`] is taken
+    /// and that field is set back to its `old` value; bookkeeping such as
+    /// `status_changed_at` follows along. The revert is recorded like any
+    /// other update, with a `reason` naming the change it undid, so undoing
+    /// again redoes it. Reverting a status must be an allowed transition.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket
+    /// * `user` - Who is making the change, if known
+    ///
+    /// # Returns
+    /// * `Ok((Ticket, String))` - The updated ticket and the reverted field
+    /// * `Err(StoreError::TicketNotFound)` - If no live ticket exists with the given ID
+    /// * `Err(StoreError::NothingToUndo)` - If the ticket's history has no
+    ///   undoable change
+    /// * `Err(StoreError::InvalidTransition)` - If the ticket can't move back
+    ///   to its previous status
+    /// * `Err(StoreError::InvalidField)` - If the previous value no longer fits the field
+    /// * `Err(StoreError::StatusOwnerOnly)` - If only the assignee may change
+    ///   the status and `user` isn't them
+    pub async fn undo_last_change(
         &self,
         id: &TicketId,
-        patch: PatchTicketRequest,
-    ) -> Result<Ticket, StoreError> {
+        user: Option<&str>,
+    ) -> Result<(Ticket, String), StoreError> {
         let tickets = self.tickets.read().await;
-        match tickets.get(id) {
-            Some(ticket_arc) => {
-                let mut ticket = ticket_arc.write().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = self.write_within(ticket_arc, id).await?;
+        check_live(&ticket)?;
 
-                // Apply patches
-                if let Some(title_str) = patch.title {
-                    match crate::data::TicketTitle::new(title_str) {
-                        Ok(title) => ticket.title = title,
-                        Err(e) => return Err(StoreError::InvalidField(format!("title: {}", e))),
-                    }
-                }
+        let entry = self
+            .history
+            .lock()
+            .unwrap()
+            .get(id)
+            .and_then(|entries| {
+                entries
+                    .iter()
+                    .rev()
+                    .find(|entry| UNDOABLE_FIELDS.contains(&entry.field.as_str()))
+                    .cloned()
+            })
+            .ok_or_else(|| StoreError::NothingToUndo(id.clone()))?;
 
-                if let Some(description_str) = patch.description {
-                    match crate::data::TicketDescription::new(description_str) {
-                        Ok(description) => ticket.description = description,
-                        Err(e) => {
-                            return Err(StoreError::InvalidField(format!("description: {}", e)))
-                        }
-                    }
-                }
+        let mut reverted = if entry.field == "status" {
+            let to: Status = serde_json::from_value(entry.old.clone())
+                .map_err(|e| StoreError::InvalidField(format!("status: {}", e)))?;
+            self.check_status_owner(&ticket, Some(to), user)?;
+            let from = ticket.status;
+            if !from.can_transition_to(to) {
+                return Err(StoreError::InvalidTransition {
+                    from,
+                    to,
+                    allowed: from.allowed_transitions(),
+                });
+            }
+            let mut reverted = ticket.clone();
+            reverted.set_status(to);
+            reverted
+        } else {
+            let mut value = serde_json::to_value(&*ticket)
+                .map_err(|e| StoreError::InvalidField(format!("{}: {}", entry.field, e)))?;
+            value[&entry.field] = entry.old.clone();
+            serde_json::from_value(value)
+                .map_err(|e| StoreError::InvalidField(format!("{}: {}", entry.field, e)))?
+        };
+        reverted.sync_state = SyncState::Pending;
+        reverted.touch();
+
+        let before = std::mem::replace(&mut *ticket, reverted);
+        self.record_update_because(
+            id,
+            field_changes(&before, &ticket),
+            Some(format!(
+                "Undo of the {} change at {}",
+                entry.field,
+                entry.at.to_rfc3339()
+            )),
+        );
+        Ok((ticket.clone(), entry.field))
+    }
 
-                if let Some(status) = patch.status {
-                    ticket.status = status;
+    /// Returns the changes made after sequence `since`, oldest first.
+    ///
+    /// # Arguments
+    /// * `since` - The last sequence the client has seen, 0 for everything
+    ///
+    /// # Returns
+    /// * `Ok((Vec<ChangeRecord>, u64))` - The changes and the current sequence
+    /// * `Err(StoreError::ChangesExpired)` - If some changes after `since` have
+    ///   already been dropped from the log, so the client must resync fully
+    pub fn changes_since(&self, since: u64) -> Result<(Vec<ChangeRecord>, u64), StoreError> {
+        let log = self.changes.lock().unwrap();
+        if since < log.floor {
+            return Err(StoreError::ChangesExpired {
+                since,
+                floor: log.floor,
+            });
+        }
+        let changes = log
+            .records
+            .iter()
+            .filter(|change| change.sequence > since)
+            .cloned()
+            .collect();
+        Ok((changes, self.version()))
+    }
+
+    /// Records a ticket creation by `reporter` against the creation throttle.
+    ///
+    /// Does nothing when no throttle is configured. Reporters with no creation
+    /// left in the window are dropped from the map, so it only holds the
+    /// reporters that created tickets recently.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The reporter may create the ticket
+    /// * `Err(StoreError::RateLimited)` - If the reporter has used up its allowance
+    pub fn throttle_creation(&self, reporter: &str) -> Result<(), StoreError> {
+        let throttle = match &self.config.creation_throttle {
+            Some(throttle) => throttle,
+            None => return Ok(()),
+        };
+
+        let now = Instant::now();
+        let mut creations = self.creations.lock().unwrap();
+        creations.retain(|_, recent| {
+            while let Some(oldest) = recent.front() {
+                if now.duration_since(*oldest) < throttle.window {
+                    break;
                 }
+                recent.pop_front();
+            }
+            !recent.is_empty()
+        });
+        let recent = creations.entry(reporter.to_string()).or_default();
 
-                Ok(ticket.clone())
+        if recent.len() >= throttle.max_per_window {
+            return Err(StoreError::RateLimited(reporter.to_string()));
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+
+    /// Checks a draft against the store's custom field policies.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The draft may be stored
+    /// * `Err(StoreError::InvalidField)` - If its custom fields exceed the limits
+    /// * `Err(StoreError::MissingRequiredFields)` - If its kind requires custom
+    ///   fields it doesn't carry
+    pub fn validate_draft(&self, draft: &TicketDraft) -> Result<(), StoreError> {
+        if let Err(e) =
+            validate_custom_fields(&draft.custom_fields, &self.config.custom_field_limits)
+        {
+            return Err(StoreError::InvalidField(format!("custom_fields: {}", e)));
+        }
+        self.check_required_fields(draft.kind.as_deref(), &draft.custom_fields)
+    }
+
+    /// Checks that `fields` carries every custom field required by `kind`.
+    fn check_required_fields(
+        &self,
+        kind: Option<&str>,
+        fields: &CustomFields,
+    ) -> Result<(), StoreError> {
+        let Some(kind) = kind else {
+            return Ok(());
+        };
+        let Some(required) = self.config.required_custom_fields.get(kind) else {
+            return Ok(());
+        };
+
+        let missing: Vec<String> = required
+            .iter()
+            .filter(|key| !fields.contains_key(*key))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(StoreError::MissingRequiredFields {
+                kind: kind.to_string(),
+                missing,
+            })
+        }
+    }
+
+    /// Adds a new ticket to the store from a draft.
+    ///
+    /// When the draft's description is empty and a description template is
+    /// configured for its category, the template is used instead. Likewise a
+    /// draft without an assignee gets the category's default assignee.
+    ///
+    /// # Arguments
+    /// * `draft` - The validated ticket draft containing title and description
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The newly created ticket, as a read right after
+    ///   would return it
+    /// * `Err(StoreError::CapacityExceeded)` - If the store is full
+    /// * `Err(StoreError::IdCollision)` - If the ID generator returned an ID
+    ///   already in use
+    pub async fn add_ticket(&self, draft: TicketDraft) -> Result<Ticket, StoreError> {
+        let mut tickets = self.tickets.write().await;
+        self.check_capacity(tickets.len(), 1)?;
+
+        let id = self.unused_id(|id| tickets.contains_key(id))?;
+        let ticket = self.build_ticket(id, draft);
+        let id = ticket.id.clone();
+        tickets.insert(id.clone(), Arc::new(RwLock::new(ticket.clone())));
+        self.record_change(TicketEvent::Created { id }, Vec::new());
+
+        Ok(ticket)
+    }
+
+    /// Draws one ID from the generator, refusing it if it is `in_use`.
+    ///
+    /// # Returns
+    /// * `Ok(TicketId)` - An ID no stored ticket has
+    /// * `Err(StoreError::IdCollision)` - If the drawn ID is taken
+    fn unused_id(&self, in_use: impl Fn(&TicketId) -> bool) -> Result<TicketId, StoreError> {
+        let id = self.id_generator.generate();
+        if in_use(&id) {
+            tracing::warn!("Generated ticket ID {} is already taken", id);
+            return Err(StoreError::IdCollision(id));
+        }
+        Ok(id)
+    }
+
+    /// Checks that `adding` more tickets fit next to the `stored` ones.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The tickets fit, or the store is unbounded
+    /// * `Err(StoreError::CapacityExceeded)` - If they would exceed `max_tickets`
+    fn check_capacity(&self, stored: usize, adding: usize) -> Result<(), StoreError> {
+        match self.config.max_tickets {
+            Some(capacity) if stored + adding > capacity => {
+                Err(StoreError::CapacityExceeded { capacity })
             }
-            None => Err(StoreError::TicketNotFound(id.clone())),
+            _ => Ok(()),
         }
     }
 
-    /// List all tickets in the store.
-    /// Retrieves all tickets from the store.
+    /// Takes a ticket's read lock, waiting at most the configured `lock_timeout`.
     ///
     /// # Returns
-    /// A vector containing clones of all tickets currently in the store.
-    /// Returns an empty vector if no tickets exist.
-    pub async fn list_tickets(&self) -> Vec<Ticket> {
-        let tickets = self.tickets.read().await;
-        let mut result = Vec::new();
+    /// * `Ok(RwLockReadGuard)` - The held read lock
+    /// * `Err(StoreError::Timeout)` - If the lock wasn't free in time
+    async fn read_within<'a>(
+        &self,
+        ticket_arc: &'a RwLock<Ticket>,
+        id: &TicketId,
+    ) -> Result<RwLockReadGuard<'a, Ticket>, StoreError> {
+        let timeout = self.config.lock_timeout;
+        tokio::time::timeout(timeout, ticket_arc.read())
+            .await
+            .map_err(|_| StoreError::Timeout(id.clone(), timeout))
+    }
+
+    /// Takes a ticket's write lock, waiting at most the configured `lock_timeout`.
+    ///
+    /// # Returns
+    /// * `Ok(RwLockWriteGuard)` - The held write lock
+    /// * `Err(StoreError::Timeout)` - If the lock wasn't free in time
+    async fn write_within<'a>(
+        &self,
+        ticket_arc: &'a RwLock<Ticket>,
+        id: &TicketId,
+    ) -> Result<RwLockWriteGuard<'a, Ticket>, StoreError> {
+        let timeout = self.config.lock_timeout;
+        tokio::time::timeout(timeout, ticket_arc.write())
+            .await
+            .map_err(|_| StoreError::Timeout(id.clone(), timeout))
+    }
+
+    /// Returns the ticket an earlier creation with idempotency key `key` made.
+    ///
+    /// # Arguments
+    /// * `key` - The `Idempotency-Key` of the request
+    /// * `fingerprint` - A hash of the request, to tell retries from reuse
+    ///
+    /// # Returns
+    /// * `Ok(Some(Ticket))` - The ticket as it was created, for a retry
+    /// * `Ok(None)` - If the key is new or has expired
+    /// * `Err(StoreError::IdempotencyKeyReused)` - If the key was used for a
+    ///   request with a different fingerprint
+    pub fn idempotent_replay(
+        &self,
+        key: &str,
+        fingerprint: u64,
+    ) -> Result<Option<Ticket>, StoreError> {
+        let mut keys = self.idempotency_keys.lock().unwrap();
+        self.expire_idempotency_keys(&mut keys);
+        match keys.creations.get(key) {
+            Some((known, ticket)) if *known == fingerprint => Ok(Some(ticket.clone())),
+            Some(_) => Err(StoreError::IdempotencyKeyReused(key.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Adds a new ticket unless idempotency key `key` already created one.
+    ///
+    /// The key is checked and the ticket inserted under a single write lock,
+    /// so concurrent requests with the same key create exactly one ticket.
+    ///
+    /// # Arguments
+    /// * `key` - The `Idempotency-Key` of the request
+    /// * `fingerprint` - A hash of the request, to tell retries from reuse
+    /// * `draft` - The validated ticket draft used if the key is new
+    ///
+    /// # Returns
+    /// * `Ok((Ticket, bool))` - The ticket as created, and `true` if it was
+    ///   created by this call
+    /// * `Err(StoreError::IdempotencyKeyReused)` - If the key was used for a
+    ///   request with a different fingerprint
+    /// * `Err(StoreError::CapacityExceeded)` - If the key is new and the
+    ///   store is full
+    /// * `Err(StoreError::IdCollision)` - If the ID generator returned an ID
+    ///   already in use
+    pub async fn add_ticket_idempotent(
+        &self,
+        key: &str,
+        fingerprint: u64,
+        draft: TicketDraft,
+    ) -> Result<(Ticket, bool), StoreError> {
+        let mut tickets = self.tickets.write().await;
+        if let Some(ticket) = self.idempotent_replay(key, fingerprint)? {
+            return Ok((ticket, false));
+        }
+        self.check_capacity(tickets.len(), 1)?;
+
+        let id = self.unused_id(|id| tickets.contains_key(id))?;
+        let ticket = self.build_ticket(id, draft);
+        let id = ticket.id.clone();
+        tickets.insert(id.clone(), Arc::new(RwLock::new(ticket.clone())));
+        self.record_change(TicketEvent::Created { id }, Vec::new());
+
+        let mut keys = self.idempotency_keys.lock().unwrap();
+        keys.creations
+            .insert(key.to_string(), (fingerprint, ticket.clone()));
+        keys.order.push_back((Instant::now(), key.to_string()));
+        while keys.order.len() > self.config.idempotency.max_keys {
+            if let Some((_, oldest)) = keys.order.pop_front() {
+                keys.creations.remove(&oldest);
+            }
+        }
+        Ok((ticket, true))
+    }
+
+    /// Forgets idempotency keys older than the configured TTL.
+    fn expire_idempotency_keys(&self, keys: &mut IdempotencyKeys) {
+        let now = Instant::now();
+        while let Some((created, _)) = keys.order.front() {
+            if now.duration_since(*created) < self.config.idempotency.ttl {
+                break;
+            }
+            if let Some((_, key)) = keys.order.pop_front() {
+                keys.creations.remove(&key);
+            }
+        }
+    }
+
+    /// Adds many tickets at once, taking the write lock a single time.
+    ///
+    /// Each draft gets the same defaults as in [`Self::add_ticket`].
+    ///
+    /// # Arguments
+    /// * `drafts` - The validated ticket drafts
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Ticket>)` - The new tickets as created, in draft order
+    /// * `Err(StoreError::CapacityExceeded)` - If they don't all fit; none
+    ///   are stored
+    /// * `Err(StoreError::IdCollision)` - If the ID generator returned an ID
+    ///   already in use; none are stored
+    pub async fn add_tickets(&self, drafts: Vec<TicketDraft>) -> Result<Vec<Ticket>, StoreError> {
+        let mut tickets = self.tickets.write().await;
+        self.check_capacity(tickets.len(), drafts.len())?;
+        // Every ID is drawn before anything is stored, so a collision stores none
+        let mut new_ids = Vec::with_capacity(drafts.len());
+        let mut drawn = HashSet::with_capacity(drafts.len());
+        for _ in &drafts {
+            let id = self.unused_id(|id| tickets.contains_key(id) || drawn.contains(id))?;
+            drawn.insert(id.clone());
+            new_ids.push(id);
+        }
+
+        let mut created = Vec::with_capacity(drafts.len());
+        for (draft, id) in drafts.into_iter().zip(new_ids) {
+            let ticket = self.build_ticket(id, draft);
+            let id = ticket.id.clone();
+            tickets.insert(id.clone(), Arc::new(RwLock::new(ticket.clone())));
+            self.record_change(TicketEvent::Created { id }, Vec::new());
+            created.push(ticket);
+        }
+
+        Ok(created)
+    }
+
+    /// Returns the ticket mirrored from `external_ref`, creating it from
+    /// `draft` if there is none.
+    ///
+    /// The lookup and the insert happen under a single write lock, so
+    /// concurrent calls with the same reference create exactly one ticket.
+    /// Soft-deleted tickets don't match, so a deleted mirror is created anew.
+    ///
+    /// # Arguments
+    /// * `external_ref` - Identifier of the record in the external system
+    /// * `draft` - The validated ticket draft used if the reference is new
+    ///
+    /// # Returns
+    /// * `Ok((Ticket, bool))` - The ticket for the reference, and `true` if
+    ///   it was newly created
+    /// * `Err(StoreError::CapacityExceeded)` - If the reference is new and
+    ///   the store is full
+    /// * `Err(StoreError::IdCollision)` - If the ID generator returned an ID
+    ///   already in use
+    pub async fn get_or_create_by_ref(
+        &self,
+        external_ref: &str,
+        draft: TicketDraft,
+    ) -> Result<(Ticket, bool), StoreError> {
+        let mut tickets = self.tickets.write().await;
 
         for ticket_arc in tickets.values() {
             let ticket = ticket_arc.read().await;
-            result.push(ticket.clone());
+            if !ticket.is_deleted() && ticket.external_ref.as_deref() == Some(external_ref) {
+                return Ok((ticket.clone(), false));
+            }
         }
+        self.check_capacity(tickets.len(), 1)?;
 
-        result
+        let id = self.unused_id(|id| tickets.contains_key(id))?;
+        let mut ticket = self.build_ticket(id, draft);
+        ticket.external_ref = Some(external_ref.to_string());
+        tickets.insert(ticket.id.clone(), Arc::new(RwLock::new(ticket.clone())));
+        self.record_change(
+            TicketEvent::Created {
+                id: ticket.id.clone(),
+            },
+            Vec::new(),
+        );
+
+        Ok((ticket, true))
+    }
+
+    /// Builds a ticket with the given ID from a draft, applying the configured defaults.
+    fn build_ticket(&self, id: TicketId, draft: TicketDraft) -> Ticket {
+        let description = self.default_description(&draft);
+        let assignee = draft
+            .assignee
+            .clone()
+            .map(|assignee| assignee.0)
+            .or_else(|| {
+                draft
+                    .category
+                    .as_ref()
+                    .and_then(|category| self.config.default_assignees.get(category))
+                    .cloned()
+            });
+        let now = Utc::now();
+        Ticket {
+            id,
+            title: draft.title,
+            description,
+            status: Status::ToDo,
+            priority: draft.priority,
+            sync_state: SyncState::Pending,
+            reporter: draft.reporter,
+            category: draft.category,
+            kind: draft.kind,
+            assignee,
+            custom_fields: draft.custom_fields,
+            tags: draft.tags.into_iter().map(|tag| tag.0).collect(),
+            comments: Vec::new(),
+            relationships: Relationships::default(),
+            external_ref: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+            status_changed_at: now,
+            due_date: draft.due_date,
+            estimate: draft.estimate,
+            rank: now.timestamp_millis() as f64,
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    /// Picks the description for a new ticket, falling back to the category template.
+    fn default_description(&self, draft: &TicketDraft) -> TicketDescription {
+        if !draft.description.0.trim().is_empty() {
+            return draft.description.clone();
+        }
+        draft
+            .category
+            .as_ref()
+            .and_then(|category| self.config.description_templates.get(category))
+            .and_then(|template| {
+                TicketDescription::new(template.clone(), self.config.max_description_len, false)
+                    .ok()
+            })
+            .unwrap_or_else(|| draft.description.clone())
+    }
+
+    /// Fills an empty store with a handful of demo tickets.
+    ///
+    /// The tickets cover every status and priority, with creation and status
+    /// change times spread over the past week. Seeding a store that already
+    /// holds tickets, soft-deleted ones included, adds nothing, so running
+    /// it twice doesn't duplicate the demo tickets.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of tickets added, 0 if the store wasn't empty
+    /// * `Err(StoreError::CapacityExceeded)` - If the demo tickets don't fit
+    pub async fn seed_demo_data(&self) -> Result<usize, StoreError> {
+        let mut tickets = self.tickets.write().await;
+        if !tickets.is_empty() {
+            return Ok(0);
+        }
+        self.check_capacity(0, DEMO_TICKETS.len())?;
+
+        let now = Utc::now();
+        for demo in DEMO_TICKETS {
+            let draft = TicketDraft {
+                title: TicketTitle(demo.title.to_string()),
+                description: TicketDescription(demo.description.to_string()),
+                priority: demo.priority,
+                reporter: Some(demo.reporter.to_string()),
+                category: Some(demo.category.to_string()),
+                kind: None,
+                assignee: demo
+                    .assignee
+                    .map(|assignee| TicketAssignee(assignee.to_string())),
+                custom_fields: CustomFields::new(),
+                tags: demo
+                    .tags
+                    .iter()
+                    .map(|tag| TicketTag(tag.to_string()))
+                    .collect(),
+                due_date: None,
+                estimate: None,
+            };
+            let id = self.unused_id(|id| tickets.contains_key(id))?;
+            let mut ticket = self.build_ticket(id, draft);
+            ticket.created_at = now - chrono::Duration::hours(demo.created_hours_ago);
+            ticket.rank = ticket.created_at.timestamp_millis() as f64;
+            ticket.status = demo.status;
+            ticket.status_changed_at = now - chrono::Duration::hours(demo.changed_hours_ago);
+            ticket.updated_at = ticket.status_changed_at;
+            if demo.status == Status::Done {
+                ticket.completed_at = Some(ticket.status_changed_at);
+            }
+            if demo.status != Status::ToDo {
+                ticket.version += 1;
+            }
+
+            let id = ticket.id.clone();
+            tickets.insert(id.clone(), Arc::new(RwLock::new(ticket)));
+            self.record_change(TicketEvent::Created { id }, Vec::new());
+        }
+
+        Ok(DEMO_TICKETS.len())
+    }
+
+    /// Retrieve a ticket by its ID.
+    /// Retrieves a ticket by its ID.
+    ///
+    /// Soft-deleted tickets are returned too; callers hiding them check
+    /// [`Ticket::is_deleted`].
+    ///
+    /// The outer lock is only held to clone the ticket's handle, so a read
+    /// waiting on a ticket being written doesn't hold up inserts and removals.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to retrieve
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The requested ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    /// * `Err(StoreError::Timeout)` - If the ticket stayed locked for longer
+    ///   than the configured `lock_timeout`
+    pub async fn get_ticket(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        let ticket_arc = self
+            .tickets
+            .read()
+            .await
+            .get(id)
+            .cloned()
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let ticket = self.read_within(&ticket_arc, id).await?;
+        Ok(ticket.clone())
+    }
+
+    /// Retrieves several tickets by their IDs.
+    ///
+    /// The outer lock is taken once for the whole lookup. Soft-deleted
+    /// tickets count as missing, and an ID given twice is only looked up
+    /// once.
+    ///
+    /// # Arguments
+    /// * `ids` - The IDs of the tickets to retrieve
+    ///
+    /// # Returns
+    /// The tickets found, then the IDs that matched no live ticket, both in
+    /// the order of `ids`
+    pub async fn get_many(&self, ids: &[TicketId]) -> (Vec<Ticket>, Vec<TicketId>) {
+        let tickets = self.tickets.read().await;
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+
+        for id in ids.iter().filter(|id| seen.insert(*id)) {
+            match tickets.get(id) {
+                Some(ticket_arc) => {
+                    let ticket = ticket_arc.read().await;
+                    if ticket.is_deleted() {
+                        missing.push(id.clone());
+                    } else {
+                        found.push(ticket.clone());
+                    }
+                }
+                None => missing.push(id.clone()),
+            }
+        }
+
+        (found, missing)
+    }
+
+    /// Patch a ticket by its ID using the provided patch request.
+    /// Updates specific fields of an existing ticket.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to update
+    /// * `patch` - The patch request containing optional updates to title, description, status,
+    ///   priority and assignee
+    /// * `expected_version` - If set, the update only applies when the ticket is at this version
+    /// * `force` - Bypasses the description shrink guard
+    /// * `user` - Who is making the change, if known
+    ///
+    /// # Returns
+    /// * `Ok((Ticket, bool))` - The updated ticket, and `false` if every
+    ///   patched field already held its value, in which case nothing was
+    ///   written or recorded
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    /// * `Err(StoreError::ValidationFailed)` - If any of the updates fail
+    ///   validation, with one error per invalid field
+    /// * `Err(StoreError::InvalidTransition)` - If the status change isn't
+    ///   allowed by [`Status::can_transition_to`]
+    /// * `Err(StoreError::VersionConflict)` - If the ticket isn't at `expected_version`
+    /// * `Err(StoreError::DescriptionShrink)` - If the new description is too much
+    ///   shorter than the old one and `force` isn't set
+    /// * `Err(StoreError::StatusOwnerOnly)` - If
+    ///   [`ServerConfig::status_owner_only`] is set, the patch changes the
+    ///   status of an assigned ticket and `user` isn't its assignee
+    pub async fn patch_ticket(
+        &self,
+        id: &TicketId,
+        patch: PatchTicketRequest,
+        expected_version: Option<u64>,
+        force: bool,
+        user: Option<&str>,
+    ) -> Result<(Ticket, bool), StoreError> {
+        // Like get_ticket, only the handle is taken under the outer lock.
+        // Transactions write-lock every ticket they stage, and removals mark
+        // the ticket deleted under its lock first, so neither can lose the
+        // patch or leave it on a ticket no longer in the map.
+        let ticket_arc = self
+            .tickets
+            .read()
+            .await
+            .get(id)
+            .cloned()
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = self.write_within(&ticket_arc, id).await?;
+        check_live(&ticket)?;
+        check_version(&ticket, expected_version)?;
+        self.check_status_owner(&ticket, patch.status.flatten(), user)?;
+        let before = ticket.clone();
+        let changed = self.apply_patch(&mut ticket, patch, force)?;
+        if changed {
+            self.record_update(id, field_changes(&before, &ticket));
+        }
+
+        Ok((ticket.clone(), changed))
+    }
+
+    /// Checks that `user` may move `ticket` to the status `to`.
+    ///
+    /// Every path that changes a status goes through this. Only applies
+    /// while [`ServerConfig::status_owner_only`] is set. Leaving the status
+    /// alone (`None`), or setting it to the value it already has, always
+    /// passes, and so does any change to an unassigned ticket.
+    fn check_status_owner(
+        &self,
+        ticket: &Ticket,
+        to: Option<Status>,
+        user: Option<&str>,
+    ) -> Result<(), StoreError> {
+        if !self.config.status_owner_only {
+            return Ok(());
+        }
+        let changes_status = matches!(to, Some(status) if status != ticket.status);
+        match &ticket.assignee {
+            Some(assignee) if changes_status && user != Some(assignee.as_str()) => {
+                Err(StoreError::StatusOwnerOnly {
+                    assignee: assignee.clone(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validates a patch and applies it to a ticket, bumping its version.
+    ///
+    /// Everything is validated before anything is applied, so a rejected
+    /// patch leaves the ticket untouched. Unless `force` is set, the
+    /// description shrink guard applies. Fields already at the patched value
+    /// are skipped; when that is all of them the ticket is left as it was,
+    /// version and `updated_at` included.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Whether the patch changed anything
+    /// * `Err(StoreError)` - Why the patch was rejected
+    fn apply_patch(
+        &self,
+        ticket: &mut Ticket,
+        patch: PatchTicketRequest,
+        force: bool,
+    ) -> Result<bool, StoreError> {
+        let patch = patch
+            .validate(&self.config)
+            .map_err(StoreError::ValidationFailed)?;
+
+        if let (Some(guard), Some(description), false) = (
+            &self.config.description_shrink_guard,
+            &patch.description,
+            force,
+        ) {
+            let from = ticket.description.0.chars().count();
+            let to = description.0.chars().count();
+            let removed = from.saturating_sub(to);
+            if removed * 100 > usize::from(guard.max_shrink_percent) * from {
+                return Err(StoreError::DescriptionShrink { from, to });
+            }
+        }
+
+        if let Some(status) = patch.status {
+            if status != ticket.status && !ticket.status.can_transition_to(status) {
+                return Err(StoreError::InvalidTransition {
+                    from: ticket.status,
+                    to: status,
+                    allowed: ticket.status.allowed_transitions(),
+                });
+            }
+        }
+
+        // Apply the patches that change something
+        let mut changed = false;
+        if let Some(title) = patch.title.filter(|title| *title != ticket.title) {
+            ticket.title = title;
+            changed = true;
+        }
+
+        if let Some(description) = patch
+            .description
+            .filter(|description| *description != ticket.description)
+        {
+            ticket.description = description;
+            changed = true;
+        }
+
+        if let Some(status) = patch.status.filter(|status| *status != ticket.status) {
+            ticket.set_status(status);
+            changed = true;
+        }
+
+        if let Some(priority) = patch
+            .priority
+            .filter(|priority| *priority != ticket.priority)
+        {
+            ticket.priority = priority;
+            changed = true;
+        }
+
+        if let Some(assignee) = patch
+            .assignee
+            .filter(|assignee| *assignee != ticket.assignee)
+        {
+            ticket.assignee = assignee;
+            changed = true;
+        }
+
+        if let Some(due_date) = patch
+            .due_date
+            .filter(|due_date| *due_date != ticket.due_date)
+        {
+            ticket.due_date = due_date;
+            changed = true;
+        }
+
+        if let Some(estimate) = patch
+            .estimate
+            .filter(|estimate| *estimate != ticket.estimate)
+        {
+            ticket.estimate = estimate;
+            changed = true;
+        }
+
+        if changed {
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+        }
+        Ok(changed)
+    }
+
+    /// Applies several operations as one, all or nothing.
+    ///
+    /// The store is write-locked for the whole transaction, as is every
+    /// ticket it touches, and operations are staged on copies, so nothing is
+    /// applied unless every operation succeeds, and no other change can
+    /// interleave. Each applied operation
+    /// is recorded as its own change, in order. Deletes fill the trash like
+    /// [`TicketStore::soft_delete`].
+    ///
+    /// # Arguments
+    /// * `operations` - The operations to apply, in order
+    /// * `user` - Who is making the changes, if known; patches and transitions
+    ///   are checked as in [`TicketStore::patch_ticket`]
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Option<Ticket>>)` - The ticket after each operation, `None` for deletes
+    /// * `Err((usize, StoreError))` - The index of the first failing operation and
+    ///   why it failed; the store is unchanged
+    pub async fn apply_transaction(
+        &self,
+        operations: Vec<TicketOperation>,
+        user: Option<&str>,
+    ) -> Result<Vec<Option<Ticket>>, (usize, StoreError)> {
+        let mut tickets = self.tickets.write().await;
+        // Tickets touched so far, in their staged state
+        let mut staged: HashMap<TicketId, Ticket> = HashMap::new();
+        // Stored tickets touched so far, locked until the commit
+        let mut locked: HashMap<TicketId, OwnedRwLockWriteGuard<Ticket>> = HashMap::new();
+        let mut results = Vec::with_capacity(operations.len());
+        // Each event with the index of its operation
+        let mut events = Vec::with_capacity(operations.len());
+        // Validated creations, given their IDs only once every operation has
+        // succeeded, so a rolled-back transaction draws none
+        let mut creations: Vec<(usize, TicketDraft)> = Vec::new();
+
+        for (index, operation) in operations.into_iter().enumerate() {
+            let result = match operation {
+                TicketOperation::Create(draft) => self
+                    .check_capacity(tickets.len() + creations.len(), 1)
+                    .and_then(|()| self.validate_draft(&draft))
+                    .map(|()| {
+                        creations.push((index, draft));
+                        None
+                    }),
+                TicketOperation::Patch(id, patch, force) => {
+                    match staged_ticket(&tickets, &staged, &mut locked, &id).await {
+                        Ok(before) => {
+                            let mut ticket = before.clone();
+                            self.check_status_owner(&before, patch.status.flatten(), user)
+                                .and_then(|()| self.apply_patch(&mut ticket, patch, force))
+                                .map(|changed| {
+                                    if changed {
+                                        let changes = field_changes(&before, &ticket);
+                                        events.push((
+                                            index,
+                                            TicketEvent::Updated { id: id.clone() },
+                                            changes,
+                                        ));
+                                    }
+                                    staged.insert(id, ticket.clone());
+                                    Some(ticket)
+                                })
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                TicketOperation::Transition(id, status) => {
+                    let patch = PatchTicketRequest {
+                        status: Some(Some(status)),
+                        ..PatchTicketRequest::default()
+                    };
+                    match staged_ticket(&tickets, &staged, &mut locked, &id).await {
+                        Ok(before) => {
+                            let mut ticket = before.clone();
+                            self.check_status_owner(&before, Some(status), user)
+                                .and_then(|()| self.apply_patch(&mut ticket, patch, false))
+                                .map(|changed| {
+                                    if changed {
+                                        let changes = field_changes(&before, &ticket);
+                                        events.push((
+                                            index,
+                                            TicketEvent::Updated { id: id.clone() },
+                                            changes,
+                                        ));
+                                    }
+                                    staged.insert(id, ticket.clone());
+                                    Some(ticket)
+                                })
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                TicketOperation::Delete(id) => staged_ticket(&tickets, &staged, &mut locked, &id)
+                    .await
+                    .map(|mut ticket| {
+                        ticket.deleted_at = Some(Utc::now());
+                        ticket.sync_state = SyncState::Pending;
+                        ticket.touch();
+                        events.push((index, TicketEvent::Deleted { id: id.clone() }, Vec::new()));
+                        staged.insert(id, ticket);
+                        None
+                    }),
+            };
+            match result {
+                Ok(ticket) => results.push(ticket),
+                Err(e) => return Err((index, e)),
+            }
+        }
+
+        for (index, draft) in creations {
+            let id = self
+                .unused_id(|id| tickets.contains_key(id) || staged.contains_key(id))
+                .map_err(|e| (index, e))?;
+            let ticket = self.build_ticket(id, draft);
+            events.push((
+                index,
+                TicketEvent::Created {
+                    id: ticket.id.clone(),
+                },
+                Vec::new(),
+            ));
+            staged.insert(ticket.id.clone(), ticket.clone());
+            results[index] = Some(ticket);
+        }
+        events.sort_by_key(|(index, _, _)| *index);
+
+        for (id, ticket) in staged {
+            match locked.get_mut(&id) {
+                Some(stored) => **stored = ticket,
+                None => {
+                    tickets.insert(id, Arc::new(RwLock::new(ticket)));
+                }
+            }
+        }
+        for (_, event, changes) in events {
+            match event {
+                TicketEvent::Updated { id } => self.record_update(&id, changes),
+                event => self.record_change(event, Vec::new()),
+            }
+        }
+        drop(locked);
+        self.trim_trash(&mut tickets);
+
+        Ok(results)
+    }
+
+    /// Replaces every field of an existing ticket.
+    ///
+    /// Unlike [`TicketStore::patch_ticket`], fields missing from the draft are
+    /// cleared to their defaults rather than left untouched, including custom
+    /// fields. The ID, creation time, external reference and comments are
+    /// kept, and the version moves on from the current one.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to replace
+    /// * `draft` - The validated ticket draft with the new contents
+    /// * `status` - The ticket's new status
+    /// * `expected_version` - If set, the update only applies when the ticket is at this version
+    /// * `user` - Who is making the change, if known
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The replaced ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    /// * `Err(StoreError::VersionConflict)` - If the ticket isn't at `expected_version`
+    /// * `Err(StoreError::InvalidTransition)` - If the ticket can't move to
+    ///   `status`, along with the statuses it can move to
+    /// * `Err(StoreError::Timeout)` - If the ticket stayed locked for longer
+    ///   than the configured lock timeout
+    /// * `Err(StoreError::StatusOwnerOnly)` - If only the assignee may change
+    ///   the status and `user` isn't them
+    pub async fn replace_ticket(
+        &self,
+        id: &TicketId,
+        draft: TicketDraft,
+        status: Status,
+        expected_version: Option<u64>,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        match tickets.get(id) {
+            Some(ticket_arc) => {
+                let mut ticket = self.write_within(ticket_arc, id).await?;
+                check_live(&ticket)?;
+                check_version(&ticket, expected_version)?;
+                self.check_status_owner(&ticket, Some(status), user)?;
+                if status != ticket.status && !ticket.status.can_transition_to(status) {
+                    return Err(StoreError::InvalidTransition {
+                        from: ticket.status,
+                        to: status,
+                        allowed: ticket.status.allowed_transitions(),
+                    });
+                }
+
+                let mut replacement = self.build_ticket(ticket.id.clone(), draft);
+                replacement.created_at = ticket.created_at;
+                replacement.external_ref = ticket.external_ref.clone();
+                replacement.status = ticket.status;
+                replacement.completed_at = ticket.completed_at;
+                replacement.status_changed_at = ticket.status_changed_at;
+                replacement.comments = ticket.comments.clone();
+                replacement.relationships = ticket.relationships.clone();
+                replacement.rank = ticket.rank;
+                replacement.set_status(status);
+                replacement.version = ticket.version + 1;
+                let before = std::mem::replace(&mut *ticket, replacement);
+                self.record_update(id, field_changes(&before, &ticket));
+
+                Ok(ticket.clone())
+            }
+            None => Err(StoreError::TicketNotFound(id.clone())),
+        }
+    }
+
+    /// Deletes a ticket by moving it to the trash.
+    ///
+    /// The same as [`TicketStore::soft_delete`]: the ticket can be brought
+    /// back until the trash policy drops it. Use
+    /// [`TicketStore::purge_ticket`] to remove a ticket for good.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to delete
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The ticket, now in the trash
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID,
+    ///   or it is already deleted
+    pub async fn delete_ticket(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        self.soft_delete(id).await
+    }
+
+    /// Removes a ticket from the store for good, bypassing the trash.
+    ///
+    /// Its history, idempotency key and change records go with it. Nothing
+    /// in the HTTP API reaches this; deletes go to the trash, see
+    /// [`TicketStore::delete_ticket`].
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to remove, live or in the trash
+    ///
+    /// # Returns
+    /// * `Ok(())` - The ticket was removed
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    pub async fn purge_ticket(&self, id: &TicketId) -> Result<(), StoreError> {
+        let mut tickets = self.tickets.write().await;
+        match tickets.remove(id) {
+            Some(ticket_arc) => {
+                tombstone(&mut *ticket_arc.write().await);
+                self.forget(&HashSet::from([id.clone()]));
+                self.record_change(TicketEvent::Deleted { id: id.clone() }, Vec::new());
+                Ok(())
+            }
+            None => Err(StoreError::TicketNotFound(id.clone())),
+        }
+    }
+
+    /// Removes every `Done` ticket not updated for at least `older_than`.
+    ///
+    /// Tickets in any other status are kept however old they are. The outer
+    /// write lock is held throughout, so a ticket can't be reopened between
+    /// being checked and removed. Publishes a `Deleted` event for each removed
+    /// ticket that wasn't already soft-deleted. The removed tickets' history,
+    /// idempotency keys and earlier change records are dropped with them.
+    ///
+    /// # Arguments
+    /// * `older_than` - How long a `Done` ticket must have gone without updates
+    ///
+    /// # Returns
+    /// The number of tickets removed.
+    pub async fn purge_expired(&self, older_than: Duration) -> usize {
+        let cutoff = chrono::Duration::from_std(older_than)
+            .ok()
+            .and_then(|age| Utc::now().checked_sub_signed(age));
+        let Some(cutoff) = cutoff else {
+            return 0;
+        };
+
+        let mut tickets = self.tickets.write().await;
+        let mut expired = Vec::new();
+        for (id, ticket_arc) in tickets.iter() {
+            let mut ticket = ticket_arc.write().await;
+            if ticket.status == Status::Done && ticket.updated_at <= cutoff {
+                expired.push((id.clone(), ticket.is_deleted()));
+                tombstone(&mut ticket);
+            }
+        }
+
+        for (id, _) in &expired {
+            tickets.remove(id);
+        }
+        self.forget(&expired.iter().map(|(id, _)| id.clone()).collect());
+        for (id, deleted) in &expired {
+            if !deleted {
+                self.record_change(TicketEvent::Deleted { id: id.clone() }, Vec::new());
+            }
+        }
+        expired.len()
+    }
+
+    /// Permanently removes soft-deleted tickets.
+    ///
+    /// Live tickets are never touched. The outer write lock is taken once and
+    /// the map is swept in a single pass; a ticket whose own lock is held at
+    /// that moment is kept for the next purge. No events are published, as
+    /// the tickets already left readers' view when they were deleted. The
+    /// removed tickets' history, idempotency keys and change records, except
+    /// for their deletion, are dropped with them.
+    ///
+    /// # Arguments
+    /// * `older_than` - How long ago a ticket must have been deleted, or
+    ///   `None` to remove every soft-deleted ticket
+    ///
+    /// # Returns
+    /// The number of tickets removed.
+    pub async fn purge_soft_deleted(&self, older_than: Option<Duration>) -> usize {
+        let cutoff = match older_than {
+            Some(age) => {
+                let cutoff = chrono::Duration::from_std(age)
+                    .ok()
+                    .and_then(|age| Utc::now().checked_sub_signed(age));
+                let Some(cutoff) = cutoff else {
+                    return 0;
+                };
+                cutoff
+            }
+            None => Utc::now(),
+        };
+
+        let mut tickets = self.tickets.write().await;
+        let mut removed = HashSet::new();
+        tickets.retain(|id, ticket_arc| {
+            let keep = match ticket_arc.try_read() {
+                Ok(ticket) => ticket
+                    .deleted_at
+                    .is_none_or(|deleted_at| deleted_at > cutoff),
+                Err(_) => true,
+            };
+            if !keep {
+                removed.insert(id.clone());
+            }
+            keep
+        });
+        self.forget(&removed);
+        removed.len()
+    }
+
+    /// Marks a ticket as deleted, hiding it from reads until restored.
+    ///
+    /// The ticket is kept, so it can be brought back with
+    /// [`TicketStore::restore`], and becomes pending sync so the sync client
+    /// learns of the deletion. Publishes a `Deleted` event. With a
+    /// [`ServerConfig::trash`] policy, a trash already holding `max_tickets`
+    /// loses its longest-deleted tickets for good to make room.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to delete
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The ticket, now soft-deleted
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID,
+    ///   or it is already deleted
+    pub async fn soft_delete(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+
+        ticket.deleted_at = Some(Utc::now());
+        ticket.sync_state = SyncState::Pending;
+        ticket.touch();
+        self.record_change(TicketEvent::Deleted { id: id.clone() }, Vec::new());
+        let deleted = ticket.clone();
+        drop(ticket);
+        drop(tickets);
+
+        if self.config.trash.is_some() {
+            self.trim_trash(&mut *self.tickets.write().await);
+        }
+        Ok(deleted)
+    }
+
+    /// Permanently removes the longest-deleted tickets beyond the
+    /// [`ServerConfig::trash`] policy's `max_tickets`.
+    ///
+    /// Callers hold the outer write lock. Like
+    /// [`TicketStore::purge_soft_deleted`], a ticket whose own lock is held
+    /// is neither counted nor removed, and no events are published.
+    fn trim_trash(&self, tickets: &mut HashMap<TicketId, Arc<RwLock<Ticket>>>) {
+        let Some(trash) = &self.config.trash else {
+            return;
+        };
+        let mut trashed: Vec<(DateTime<Utc>, TicketId)> = tickets
+            .iter()
+            .filter_map(|(id, ticket_arc)| {
+                let deleted_at = ticket_arc.try_read().ok()?.deleted_at?;
+                Some((deleted_at, id.clone()))
+            })
+            .collect();
+        if trashed.len() <= trash.max_tickets {
+            return;
+        }
+
+        trashed.sort();
+        let overflow = trashed.len() - trash.max_tickets;
+        let removed: HashSet<TicketId> = trashed
+            .into_iter()
+            .take(overflow)
+            .map(|(_, id)| id)
+            .collect();
+        tickets.retain(|id, _| !removed.contains(id));
+        self.forget(&removed);
+    }
+
+    /// Retrieves the tickets in the trash, that is the soft-deleted ones.
+    ///
+    /// # Returns
+    /// A vector containing clones of every soft-deleted ticket, the most
+    /// recently deleted first.
+    pub async fn list_trash(&self) -> Vec<Ticket> {
+        let tickets = self.tickets.read().await;
+        let mut result = Vec::new();
+
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if ticket.is_deleted() {
+                result.push(ticket.clone());
+            }
+        }
+
+        result.sort_by_key(|ticket| std::cmp::Reverse(ticket.deleted_at));
+        result
+    }
+
+    /// Takes a ticket out of the trash.
+    ///
+    /// Works like [`TicketStore::restore`], except that a live ticket isn't
+    /// in the trash to begin with.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to restore
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The live ticket
+    /// * `Err(StoreError::TicketNotFound)` - If the trash holds no ticket with
+    ///   the given ID
+    pub async fn restore_from_trash(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        self.restore_deleted(id, true).await
+    }
+
+    /// Brings back a soft-deleted ticket.
+    ///
+    /// Restoring a ticket that isn't deleted changes nothing. Otherwise the
+    /// ticket becomes pending sync and a `Restored` event is published.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to restore
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The live ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    pub async fn restore(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        self.restore_deleted(id, false).await
+    }
+
+    /// Brings back a soft-deleted ticket, failing for live ones when
+    /// `deleted_only` is set.
+    async fn restore_deleted(
+        &self,
+        id: &TicketId,
+        deleted_only: bool,
+    ) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        if deleted_only && !ticket.is_deleted() {
+            return Err(StoreError::TicketNotFound(id.clone()));
+        }
+
+        if ticket.deleted_at.take().is_some() {
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+            self.record_change(
+                TicketEvent::Restored { id: id.clone() },
+                vec!["deleted_at".to_string(), "sync_state".to_string()],
+            );
+        }
+        Ok(ticket.clone())
+    }
+
+    /// Moves a ticket to another status, reporting where it moved from.
+    ///
+    /// The move must be allowed by [`Status::can_transition_to`]. Moving a
+    /// ticket to the status it is already in is a no-op that succeeds, with
+    /// `from` and `to` equal, and records nothing.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to move
+    /// * `to` - The status to move it to
+    /// * `user` - Who is making the change, if known
+    ///
+    /// # Returns
+    /// * `Ok(StatusTransition)` - The previous and new status, and the ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no live ticket exists with the given ID
+    /// * `Err(StoreError::InvalidTransition)` - If the ticket can't move to
+    ///   `to`, along with the statuses it can move to
+    /// * `Err(StoreError::StatusOwnerOnly)` - If only the assignee may change
+    ///   the status and `user` isn't them
+    pub async fn transition_status(
+        &self,
+        id: &TicketId,
+        to: Status,
+        user: Option<&str>,
+    ) -> Result<StatusTransition, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+        self.check_status_owner(&ticket, Some(to), user)?;
+
+        let from = ticket.status;
+        if from != to {
+            if !from.can_transition_to(to) {
+                return Err(StoreError::InvalidTransition {
+                    from,
+                    to,
+                    allowed: from.allowed_transitions(),
+                });
+            }
+            let before = ticket.clone();
+            ticket.set_status(to);
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+            self.record_update(id, field_changes(&before, &ticket));
+        }
+
+        Ok(StatusTransition {
+            from,
+            to,
+            ticket: ticket.clone(),
+        })
+    }
+
+    /// Moves a ticket to another status, but only if it is in `expected`.
+    ///
+    /// The status is compared and written under the ticket's write lock, so
+    /// of several swaps from the same status only the first one applies; the
+    /// others see its result and fail. The move must still be allowed by
+    /// [`Status::can_transition_to`], and swapping to the current status is
+    /// a no-op that succeeds.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to move
+    /// * `expected` - The status the ticket must be in
+    /// * `new` - The status to move it to
+    /// * `user` - Who is making the change, if known
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The updated ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no live ticket exists with the given ID
+    /// * `Err(StoreError::StatusMismatch)` - If the ticket isn't in `expected`,
+    ///   along with the status it is in
+    /// * `Err(StoreError::InvalidTransition)` - If the ticket can't move to `new`
+    /// * `Err(StoreError::StatusOwnerOnly)` - If only the assignee may change
+    ///   the status and `user` isn't them
+    ///
+    /// # Examples
+    ///
+    /// Two clients racing to pick up the same ticket:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use ticket_api::{Status, StoreError, TicketDraft, TicketStore};
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let store = Arc::new(TicketStore::new());
+    /// let draft = TicketDraft::builder().title("Fix login").build().unwrap();
+    /// let id = store.add_ticket(draft).await.unwrap().id;
+    ///
+    /// let attempts = [Status::InProgress, Status::Cancelled].map(|new| {
+    ///     let (store, id) = (store.clone(), id.clone());
+    ///     tokio::spawn(async move {
+    ///         store.compare_and_swap_status(&id, Status::ToDo, new, None).await
+    ///     })
+    /// });
+    /// let mut results = Vec::new();
+    /// for attempt in attempts {
+    ///     results.push(attempt.await.unwrap());
+    /// }
+    ///
+    /// let winner = results.iter().find_map(|result| result.as_ref().ok()).unwrap();
+    /// assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+    /// assert!(results.iter().any(|result| matches!(
+    ///     result,
+    ///     Err(StoreError::StatusMismatch { expected: Status::ToDo, actual })
+    ///         if *actual == winner.status
+    /// )));
+    /// # });
+    /// ```
+    pub async fn compare_and_swap_status(
+        &self,
+        id: &TicketId,
+        expected: Status,
+        new: Status,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+
+        let actual = ticket.status;
+        if actual != expected {
+            return Err(StoreError::StatusMismatch { expected, actual });
+        }
+        self.check_status_owner(&ticket, Some(new), user)?;
+        if actual != new {
+            if !actual.can_transition_to(new) {
+                return Err(StoreError::InvalidTransition {
+                    from: actual,
+                    to: new,
+                    allowed: actual.allowed_transitions(),
+                });
+            }
+            let before = ticket.clone();
+            ticket.set_status(new);
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+            self.record_update(id, field_changes(&before, &ticket));
+        }
+
+        Ok(ticket.clone())
+    }
+
+    /// Moves a ticket to another position in the backlog.
+    ///
+    /// Tickets are ordered by [`Ticket::rank`]. The moved ticket is given a
+    /// rank halfway between its new neighbors, so only it changes. When the
+    /// neighbors' ranks are too close to fit another in between, every live
+    /// ticket is renumbered instead, keeping their order. Each ticket whose
+    /// rank changes that way is updated like the moved one: its version is
+    /// bumped, it's marked pending sync, and the change is audited and
+    /// published. A ticket already in the requested position is left as it
+    /// is. Soft-deleted tickets don't take part in the ordering.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to move
+    /// * `target` - Where to move it
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The ticket with its new rank
+    /// * `Err(StoreError::TicketNotFound)` - If the ticket, or the ticket to
+    ///   move next to, isn't a live ticket
+    /// * `Err(StoreError::InvalidField)` - If the ticket is to move next to
+    ///   itself
+    pub async fn reorder(
+        &self,
+        id: &TicketId,
+        target: ReorderTarget,
+    ) -> Result<Ticket, StoreError> {
+        // Exclusive, so no rank changes while the neighbors are picked
+        let tickets = self.tickets.write().await;
+        let ticket_arc = live_ticket(&tickets, id).await?;
+
+        let mut others: Vec<(f64, DateTime<Utc>, TicketId)> = Vec::with_capacity(tickets.len());
+        for (other_id, other_arc) in tickets.iter() {
+            let other = other_arc.read().await;
+            if other_id != id && !other.is_deleted() {
+                others.push((other.rank, other.created_at, other_id.clone()));
+            }
+        }
+        others.sort_by(|a, b| a.0.total_cmp(&b.0).then((a.1, &a.2).cmp(&(b.1, &b.2))));
+
+        let anchor_index = |anchor: &TicketId, field: &str| {
+            if anchor == id {
+                return Err(StoreError::InvalidField(format!(
+                    "{}: a ticket can't be moved next to itself",
+                    field
+                )));
+            }
+            others
+                .iter()
+                .position(|(_, _, other_id)| other_id == anchor)
+                .ok_or_else(|| StoreError::TicketNotFound(anchor.clone()))
+        };
+        let position = match &target {
+            ReorderTarget::After(anchor) => anchor_index(anchor, "after")? + 1,
+            ReorderTarget::Before(anchor) => anchor_index(anchor, "before")?,
+            ReorderTarget::Start => 0,
+            ReorderTarget::End => others.len(),
+        };
+        let lower = position.checked_sub(1).map(|index| others[index].0);
+        let upper = others.get(position).map(|(rank, _, _)| *rank);
+        let fits = |rank: f64| {
+            lower.is_none_or(|lower| lower < rank) && upper.is_none_or(|upper| rank < upper)
+        };
+
+        let mut ticket = ticket_arc.write().await;
+        if fits(ticket.rank) {
+            return Ok(ticket.clone());
+        }
+
+        let before = ticket.clone();
+        let rank = match (lower, upper) {
+            (Some(lower), Some(upper)) => lower + (upper - lower) / 2.0,
+            (Some(lower), None) => lower + 1.0,
+            (None, Some(upper)) => upper - 1.0,
+            (None, None) => ticket.rank,
+        };
+        ticket.rank = if fits(rank) {
+            rank
+        } else {
+            // Out of room between the neighbors: space every ticket one apart
+            for (index, (rank, _, other_id)) in others.iter().enumerate() {
+                let slot = if index < position { index } else { index + 1 };
+                let slot = slot as f64 + 1.0;
+                if *rank == slot {
+                    continue;
+                }
+                let mut other = tickets[other_id].write().await;
+                let other_before = other.clone();
+                other.rank = slot;
+                other.sync_state = SyncState::Pending;
+                other.touch();
+                self.record_update(other_id, field_changes(&other_before, &other));
+            }
+            position as f64 + 1.0
+        };
+        ticket.sync_state = SyncState::Pending;
+        ticket.touch();
+        self.record_update(id, field_changes(&before, &ticket));
+
+        Ok(ticket.clone())
+    }
+
+    /// Reopens a `Done` ticket.
+    ///
+    /// The ticket moves back to `InProgress`, or to `ToDo` with `backlog`.
+    /// This is the one way out of `Done` into `ToDo`, which a status patch
+    /// can't do. The status change is audited along with `reason`.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to reopen
+    /// * `reason` - Why the ticket is reopened, if given
+    /// * `backlog` - Whether to move the ticket to `ToDo` rather than `InProgress`
+    /// * `user` - Who is making the change, if known
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The reopened ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no live ticket exists with the given ID
+    /// * `Err(StoreError::NotDone)` - If the ticket isn't `Done`
+    /// * `Err(StoreError::StatusOwnerOnly)` - If only the assignee may change
+    ///   the status and `user` isn't them
+    pub async fn reopen(
+        &self,
+        id: &TicketId,
+        reason: Option<String>,
+        backlog: bool,
+        user: Option<&str>,
+    ) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+        if ticket.status != Status::Done {
+            return Err(StoreError::NotDone(ticket.status));
+        }
+        let to = if backlog {
+            Status::ToDo
+        } else {
+            Status::InProgress
+        };
+        self.check_status_owner(&ticket, Some(to), user)?;
+
+        let before = ticket.clone();
+        ticket.set_status(to);
+        ticket.sync_state = SyncState::Pending;
+        ticket.touch();
+        self.record_update_because(id, field_changes(&before, &ticket), reason);
+        Ok(ticket.clone())
+    }
+
+    /// List all tickets in the store.
+    /// Retrieves all tickets from the store.
+    ///
+    /// The ticket handles are collected and the outer lock released before
+    /// the per-ticket locks are read, so a slow read doesn't hold up inserts
+    /// and removals. When `list_read_concurrency` is configured, the tickets
+    /// are read concurrently, up to the configured limit. Since no lock
+    /// covers the whole read, a transaction committing meanwhile may show up
+    /// in some tickets and not yet in others.
+    ///
+    /// # Returns
+    /// A vector containing clones of all live tickets currently in the store,
+    /// ordered by creation time, then ID. Soft-deleted tickets are left out.
+    /// Returns an empty vector if no tickets exist.
+    pub async fn list_tickets(&self) -> Vec<Ticket> {
+        let handles: Vec<Arc<RwLock<Ticket>>> =
+            self.tickets.read().await.values().cloned().collect();
+        let mut result = match self.config.list_read_concurrency {
+            Some(concurrency) => {
+                stream::iter(handles)
+                    .map(|ticket_arc| async move { ticket_arc.read().await.clone() })
+                    .buffer_unordered(concurrency.max(1))
+                    .collect::<Vec<Ticket>>()
+                    .await
+            }
+            None => {
+                let mut result = Vec::with_capacity(handles.len());
+
+                for ticket_arc in handles {
+                    let ticket = ticket_arc.read().await;
+                    result.push(ticket.clone());
+                }
+
+                result
+            }
+        };
+
+        result.retain(|ticket| !ticket.is_deleted());
+        result.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        result
+    }
+
+    /// Streams every live ticket, ordered by creation time, then ID.
+    ///
+    /// Only the ticket handles are collected up front; each ticket is read
+    /// and cloned when the stream reaches it, so a large store is never
+    /// copied at once. A ticket changed mid-stream appears as it is when
+    /// read, and one deleted mid-stream still appears.
+    ///
+    /// # Returns
+    /// A stream of the tickets that were live when it was created
+    pub async fn stream_tickets(&self) -> BoxStream<'static, Ticket> {
+        let tickets = self.tickets.read().await;
+        let mut keyed = Vec::with_capacity(tickets.len());
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if !ticket.is_deleted() {
+                keyed.push((ticket.created_at, ticket.id.clone(), Arc::clone(ticket_arc)));
+            }
+        }
+        keyed.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+        stream::iter(keyed)
+            .then(|(_, _, ticket_arc)| async move { ticket_arc.read().await.clone() })
+            .boxed()
+    }
+
+    /// Captures the whole store as a [`Fixture`].
+    ///
+    /// The outer write lock and every ticket's lock are held while reading,
+    /// so no mutation can slip in between the tickets, their history, the
+    /// change log and the write version.
+    ///
+    /// # Returns
+    /// Every ticket, soft-deleted ones included, ordered by creation time,
+    /// then ID, with the current write version, every ticket's history, the
+    /// retained change log and the live idempotency keys
+    pub async fn export_fixture(&self) -> Fixture {
+        let tickets = self.tickets.write().await;
+        let mut guards = Vec::with_capacity(tickets.len());
+        for ticket_arc in tickets.values() {
+            guards.push(ticket_arc.read().await);
+        }
+
+        let store_version = self.version();
+        let mut result: Vec<Ticket> = guards.iter().map(|ticket| (**ticket).clone()).collect();
+        result.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        let history = self
+            .history
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, entries)| (id.clone(), entries.clone()))
+            .collect();
+        let log = self.changes.lock().unwrap();
+        let (changes, changes_floor) = (log.records.iter().cloned().collect(), Some(log.floor));
+        drop(log);
+
+        let mut keys = self.idempotency_keys.lock().unwrap();
+        self.expire_idempotency_keys(&mut keys);
+        let now = Instant::now();
+        let idempotency_keys = keys
+            .order
+            .iter()
+            .filter_map(|(created, key)| {
+                let (fingerprint, ticket) = keys.creations.get(key)?;
+                Some(FixtureIdempotencyKey {
+                    key: key.clone(),
+                    fingerprint: *fingerprint,
+                    age_seconds: now.duration_since(*created).as_secs(),
+                    ticket: ticket.clone(),
+                })
+            })
+            .collect();
+
+        Fixture {
+            store_version,
+            tickets: result,
+            history,
+            changes,
+            changes_floor,
+            idempotency_keys,
+        }
+    }
+
+    /// Replaces the whole store with the contents of a [`Fixture`].
+    ///
+    /// Every ticket is validated before anything is replaced, so a rejected
+    /// fixture leaves the store untouched. The write version, history,
+    /// change log and idempotency keys are restored to the fixture's, so
+    /// undo, incremental sync and retried creations carry on as they would
+    /// have in the captured store. An idempotency key keeps its age, and one
+    /// that has expired since the capture is dropped. No events are
+    /// published, since a load replaces the store rather than changing
+    /// individual tickets.
+    ///
+    /// # Arguments
+    /// * `fixture` - The fixture to load
+    ///
+    /// # Returns
+    /// * `Ok(())` - The store now holds exactly the fixture's tickets
+    /// * `Err(StoreError::InvalidField)` - If a ticket fails validation, two
+    ///   tickets share an ID, the history names a ticket the fixture doesn't
+    ///   have, or the change log isn't ordered between the floor and the
+    ///   write version
+    pub async fn load_fixture(&self, fixture: Fixture) -> Result<(), StoreError> {
+        let loaded = self.validate_tickets(fixture.tickets)?;
+        if let Some(id) = fixture.history.keys().find(|id| !loaded.contains_key(id)) {
+            return Err(StoreError::InvalidField(format!(
+                "history: no ticket with ID {}",
+                id.0
+            )));
+        }
+        let floor = fixture.changes_floor.unwrap_or(fixture.store_version);
+        let mut last = floor;
+        for record in &fixture.changes {
+            if record.sequence <= last || record.sequence > fixture.store_version {
+                return Err(StoreError::InvalidField(format!(
+                    "changes: sequence {} must come after {} and be at most the store version {}",
+                    record.sequence, last, fixture.store_version
+                )));
+            }
+            last = record.sequence;
+        }
+
+        let mut idempotency_keys = IdempotencyKeys::default();
+        let now = Instant::now();
+        for entry in fixture.idempotency_keys {
+            let age = Duration::from_secs(entry.age_seconds);
+            if age >= self.config.idempotency.ttl
+                || idempotency_keys.creations.contains_key(&entry.key)
+            {
+                continue;
+            }
+            let created = now.checked_sub(age).unwrap_or(now);
+            idempotency_keys
+                .creations
+                .insert(entry.key.clone(), (entry.fingerprint, entry.ticket));
+            idempotency_keys.order.push_back((created, entry.key));
+        }
+        while idempotency_keys.order.len() > self.config.idempotency.max_keys {
+            if let Some((_, oldest)) = idempotency_keys.order.pop_front() {
+                idempotency_keys.creations.remove(&oldest);
+            }
+        }
+
+        let derived = DerivedState {
+            history: fixture.history.into_iter().collect(),
+            changes: fixture.changes.into(),
+            floor: Some(floor),
+            idempotency_keys,
+        };
+        self.replace_tickets(loaded, |_| fixture.store_version, derived)
+            .await;
+        Ok(())
+    }
+
+    /// Captures every ticket for a backup.
+    ///
+    /// The outer write lock is held while reading, as for
+    /// [`TicketStore::export_fixture`], so the snapshot is consistent.
+    ///
+    /// # Returns
+    /// Every ticket, soft-deleted ones included, ordered by creation time,
+    /// then ID
+    pub async fn export_snapshot(&self) -> Vec<Ticket> {
+        self.export_fixture().await.tickets
+    }
+
+    /// Replaces every ticket with those of a snapshot, all or nothing.
+    ///
+    /// Every ticket is validated before the swap, so a rejected snapshot
+    /// leaves the store untouched. Unlike [`TicketStore::load_fixture`], the
+    /// write version isn't reset: the restore counts as one more write. No
+    /// events are published. History and idempotency keys are forgotten and
+    /// the change log is emptied, so clients syncing from before the restore
+    /// get [`StoreError::ChangesExpired`] and must resync fully.
+    ///
+    /// # Arguments
+    /// * `tickets` - The tickets of the snapshot
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The write version after the restore
+    /// * `Err(StoreError::InvalidField)` - If a ticket fails validation or two
+    ///   tickets share an ID
+    pub async fn import_snapshot(&self, tickets: Vec<Ticket>) -> Result<u64, StoreError> {
+        let loaded = self.validate_tickets(tickets)?;
+        Ok(self
+            .replace_tickets(loaded, |current| current + 1, DerivedState::default())
+            .await)
+    }
+
+    /// Validates loaded tickets and indexes them by ID, rejecting duplicates.
+    fn validate_tickets(
+        &self,
+        tickets: Vec<Ticket>,
+    ) -> Result<HashMap<TicketId, Arc<RwLock<Ticket>>>, StoreError> {
+        let mut loaded = HashMap::with_capacity(tickets.len());
+
+        for ticket in tickets {
+            self.validate_fixture_ticket(&ticket)
+                .map_err(|e| StoreError::InvalidField(format!("ticket {}: {}", ticket.id.0, e)))?;
+            if loaded.contains_key(&ticket.id) {
+                return Err(StoreError::InvalidField(format!(
+                    "ticket {}: duplicate id",
+                    ticket.id.0
+                )));
+            }
+            loaded.insert(ticket.id.clone(), Arc::new(RwLock::new(ticket)));
+        }
+        Ok(loaded)
+    }
+
+    /// Swaps in `loaded` as the store's tickets in one step.
+    ///
+    /// The ID generator is told every loaded ID. History, the change log and
+    /// idempotency keys are replaced by `derived`, and the write version becomes `version` applied to the
+    /// current one, all under the outer write lock.
+    ///
+    /// # Returns
+    /// The new write version
+    async fn replace_tickets(
+        &self,
+        loaded: HashMap<TicketId, Arc<RwLock<Ticket>>>,
+        version: impl FnOnce(u64) -> u64,
+        derived: DerivedState,
+    ) -> u64 {
+        let mut tickets = self.tickets.write().await;
+        for id in loaded.keys() {
+            self.id_generator.skip(id);
+        }
+        for ticket_arc in std::mem::replace(&mut *tickets, loaded).values() {
+            tombstone(&mut *ticket_arc.write().await);
+        }
+        *self.history.lock().unwrap() = derived.history;
+        *self.idempotency_keys.lock().unwrap() = derived.idempotency_keys;
+        let version = version(self.version());
+        let mut log = self.changes.lock().unwrap();
+        log.records = derived.changes;
+        log.floor = derived.floor.unwrap_or(version);
+        self.version.send_replace(version);
+        version
+    }
+
+    /// Re-runs the field validation a ticket would have passed on creation.
+    fn validate_fixture_ticket(&self, ticket: &Ticket) -> Result<(), String> {
+        crate::data::TicketTitle::new(ticket.title.0.clone(), self.config.max_title_len)
+            .map_err(|e| format!("title: {}", e))?;
+        TicketDescription::new(
+            ticket.description.0.clone(),
+            self.config.max_description_len,
+            self.config.require_description,
+        )
+        .map_err(|e| format!("description: {}", e))?;
+        if let Some(assignee) = &ticket.assignee {
+            crate::data::TicketAssignee::new(assignee.clone())
+                .map_err(|e| format!("assignee: {}", e))?;
+        }
+        if let Some(message) = ticket
+            .estimate
+            .and_then(|estimate| estimate_error(estimate, &self.config.estimate_range))
+        {
+            return Err(format!("estimate: {}", message));
+        }
+        for tag in &ticket.tags {
+            if TicketTag::new(tag.clone())
+                .map_err(|e| format!("tags: {}", e))?
+                .0
+                != *tag
+            {
+                return Err(format!("tags: {} is not lowercase", tag));
+            }
+        }
+        validate_custom_fields(&ticket.custom_fields, &self.config.custom_field_limits)
+            .map_err(|e| format!("custom_fields: {}", e))
+    }
+
+    /// Retrieves the tickets matching every predicate of `filter`.
+    ///
+    /// Everything is read in a single pass under the read lock, and only
+    /// matching tickets are cloned.
+    ///
+    /// # Arguments
+    /// * `filter` - The predicates a ticket must satisfy
+    ///
+    /// # Returns
+    /// Clones of the matching tickets, ordered by creation time, then ID.
+    pub async fn query(&self, filter: &TicketFilter) -> Vec<Ticket> {
+        let tickets = self.tickets.read().await;
+        let mut result = Vec::new();
+
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if filter.matches(&ticket) {
+                result.push(ticket.clone());
+            }
+        }
+
+        result.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        result
+    }
+
+    /// Finds the live tickets titled `title`, ignoring case and surrounding
+    /// whitespace; see [`TicketTitle::normalize`].
+    ///
+    /// # Arguments
+    /// * `title` - The title to look for
+    ///
+    /// # Returns
+    /// The IDs of the matching tickets, ordered by creation time, then ID.
+    pub async fn find_by_title(&self, title: &str) -> Vec<TicketId> {
+        let title = TicketTitle::normalize(title);
+        let tickets = self.tickets.read().await;
+        let mut matching = Vec::new();
+
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if !ticket.is_deleted() && TicketTitle::normalize(&ticket.title.0) == title {
+                matching.push((ticket.created_at, ticket.id.clone()));
+            }
+        }
+
+        matching.sort();
+        matching.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Finds tickets whose title or description contains `query`.
+    ///
+    /// Matching is a case-insensitive substring match. Soft-deleted tickets
+    /// never match.
+    ///
+    /// # Arguments
+    /// * `query` - The text to look for
+    ///
+    /// # Returns
+    /// Clones of the matching tickets, ordered by creation time, then ID.
+    pub async fn search(&self, query: &str) -> Vec<Ticket> {
+        let query = query.to_lowercase();
+        let tickets = self.tickets.read().await;
+        let mut result = Vec::new();
+
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if !ticket.is_deleted()
+                && (ticket.title.0.to_lowercase().contains(&query)
+                    || ticket.description.0.to_lowercase().contains(&query))
+            {
+                result.push(ticket.clone());
+            }
+        }
+
+        result.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        result
+    }
+
+    /// Finds tickets whose title, description or comments contain `query`.
+    ///
+    /// Matching ignores case like [`TicketStore::search`]. Every ticket and
+    /// its comments are scanned under the store's read lock. Soft-deleted
+    /// tickets never match.
+    ///
+    /// # Arguments
+    /// * `query` - The text to look for
+    ///
+    /// # Returns
+    /// Clones of the matching tickets, ordered by creation time, then ID,
+    /// each with its matching comments, oldest first, and empty when only
+    /// the title or description matched.
+    pub async fn search_all(&self, query: &str) -> Vec<(Ticket, Vec<CommentMatch>)> {
+        let lowercase = query.to_lowercase();
+        let tickets = self.tickets.read().await;
+        let mut result = Vec::new();
+
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if ticket.is_deleted() {
+                continue;
+            }
+            let comments: Vec<CommentMatch> = ticket
+                .comments
+                .iter()
+                .filter_map(|comment| CommentMatch::find(comment, query))
+                .collect();
+            if !comments.is_empty()
+                || ticket.title.0.to_lowercase().contains(&lowercase)
+                || ticket.description.0.to_lowercase().contains(&lowercase)
+            {
+                result.push((ticket.clone(), comments));
+            }
+        }
+
+        result.sort_by(|(a, _), (b, _)| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        result
+    }
+
+    /// Retrieves one page of tickets, ordered by creation time, then ID.
+    ///
+    /// Only the tickets on the requested page are cloned.
+    ///
+    /// # Arguments
+    /// * `offset` - Number of tickets to skip
+    /// * `limit` - Maximum number of tickets to return
+    /// * `include_deleted` - Whether soft-deleted tickets are listed and counted
+    ///
+    /// # Returns
+    /// The tickets on the page, and the total number of tickets listed
+    pub async fn list_tickets_paginated(
+        &self,
+        offset: usize,
+        limit: usize,
+        include_deleted: bool,
+    ) -> (Vec<Ticket>, usize) {
+        let tickets = self.tickets.read().await;
+        let mut keyed = Vec::with_capacity(tickets.len());
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if !include_deleted && ticket.is_deleted() {
+                continue;
+            }
+            keyed.push((ticket.created_at, ticket.id.clone(), ticket_arc));
+        }
+        keyed.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+        let total = keyed.len();
+        let mut page = Vec::new();
+        for (_, _, ticket_arc) in keyed.into_iter().skip(offset).take(limit) {
+            page.push(ticket_arc.read().await.clone());
+        }
+
+        (page, total)
+    }
+
+    /// Serializes one page of tickets matching `filter`, ordered by creation
+    /// time, then ID, as a JSON array of
+    /// [`TicketResponse`](crate::data::TicketResponse)s appended to `out`.
+    ///
+    /// No ticket is cloned: each is serialized in place under its read lock,
+    /// one at a time. The array is byte for byte what serializing the
+    /// cloned tickets' responses as a [`serde_json::Value`] gives, keys in
+    /// order. Tickets are read after the store's
+    /// lock is released, so they show as they are then.
+    ///
+    /// # Arguments
+    /// * `filter` - The predicates a ticket must satisfy
+    /// * `offset` - Number of matching tickets to skip
+    /// * `limit` - Maximum number of tickets to serialize
+    /// * `out` - The buffer to append the array to
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The total number of matching tickets
+    /// * `Err(serde_json::Error)` - If a ticket failed to serialize
+    pub async fn write_page_json(
+        &self,
+        filter: &TicketFilter,
+        offset: usize,
+        limit: usize,
+        out: &mut Vec<u8>,
+    ) -> serde_json::Result<usize> {
+        let (handles, total) = self.page_handles(filter, offset, limit).await;
+        out.push(b'[');
+        for (index, handle) in handles.iter().enumerate() {
+            if index > 0 {
+                out.push(b',');
+            }
+            let ticket = handle.read().await;
+            serde_json::to_writer(&mut *out, &TicketResponseRef::from(&*ticket))?;
+        }
+        out.push(b']');
+        Ok(total)
+    }
+
+    /// Retrieves the handles of one page of tickets matching `filter`,
+    /// ordered by creation time, then ID, and the number of matches.
+    async fn page_handles(
+        &self,
+        filter: &TicketFilter,
+        offset: usize,
+        limit: usize,
+    ) -> (Vec<Arc<RwLock<Ticket>>>, usize) {
+        let tickets = self.tickets.read().await;
+        let mut keyed = Vec::new();
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if filter.matches(&ticket) {
+                keyed.push((ticket.created_at, ticket.id.clone(), ticket_arc.clone()));
+            }
+        }
+        keyed.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+        let total = keyed.len();
+        let page = keyed
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(_, _, ticket_arc)| ticket_arc)
+            .collect();
+        (page, total)
+    }
+
+    /// Retrieves a page of live tickets after a cursor, in creation order, then ID.
+    ///
+    /// Unlike offset paging, tickets created or deleted between pages don't
+    /// shift the ones after the cursor, so no ticket is skipped or repeated.
+    /// New tickets sort last and show up on a later page. Only the tickets
+    /// on the page are cloned.
+    ///
+    /// # Arguments
+    /// * `cursor` - The position to continue after, `None` for the first page
+    /// * `limit` - Maximum number of tickets to return
+    ///
+    /// # Returns
+    /// The page, and the cursor for the next page (`None` when this page
+    /// reached the end). A `limit` of 0 returns no tickets and, when there
+    /// are more, `cursor` itself.
+    pub async fn list_after_cursor(
+        &self,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> (Vec<Ticket>, Option<Cursor>) {
+        let handles: Vec<Arc<RwLock<Ticket>>> =
+            self.tickets.read().await.values().cloned().collect();
+        let mut keyed = Vec::new();
+        for ticket_arc in handles {
+            let ticket = ticket_arc.read().await;
+            if !ticket.is_deleted()
+                && cursor
+                    .as_ref()
+                    .is_none_or(|cursor| cursor.precedes(&ticket))
+            {
+                keyed.push((ticket.created_at, ticket.id.clone(), ticket_arc.clone()));
+            }
+        }
+
+        // Only the page, and one more ticket to tell if there is a next
+        // page, need to be in order
+        let order = |a: &(DateTime<Utc>, TicketId, _), b: &(DateTime<Utc>, TicketId, _)| {
+            (a.0, &a.1).cmp(&(b.0, &b.1))
+        };
+        let wanted = limit.saturating_add(1);
+        if keyed.len() > wanted {
+            keyed.select_nth_unstable_by(wanted, order);
+            keyed.truncate(wanted);
+        }
+        keyed.sort_by(order);
+
+        let more = keyed.len() > limit;
+        let mut page = Vec::with_capacity(limit.min(keyed.len()));
+        for (_, _, ticket_arc) in keyed.into_iter().take(limit) {
+            page.push(ticket_arc.read().await.clone());
+        }
+        let next = if more {
+            page.last().map(Cursor::after).or(cursor)
+        } else {
+            None
+        };
+        (page, next)
+    }
+
+    /// Retrieves tickets in ID order, stopping early if a deadline passes.
+    ///
+    /// The outer lock is only held while collecting the ticket handles, so a
+    /// slow, contended read doesn't block writers to the map.
+    ///
+    /// # Arguments
+    /// * `after` - Only tickets with an ID greater than this are returned
+    /// * `deadline` - Optional point in time after which no more tickets are read
+    /// * `include_deleted` - Whether soft-deleted tickets are returned
+    ///
+    /// # Returns
+    /// The tickets read, and the ID to continue after if the deadline cut
+    /// the listing short (`None` once every ticket has been returned). At
+    /// least one ticket is read per call, so resuming always makes progress.
+    pub async fn list_tickets_partial(
+        &self,
+        after: Option<&TicketId>,
+        deadline: Option<tokio::time::Instant>,
+        include_deleted: bool,
+    ) -> (Vec<Ticket>, Option<TicketId>) {
+        let mut handles: Vec<(TicketId, Arc<RwLock<Ticket>>)> = self
+            .tickets
+            .read()
+            .await
+            .iter()
+            .filter(|(id, _)| after.is_none_or(|after| *id > after))
+            .map(|(id, ticket_arc)| (id.clone(), ticket_arc.clone()))
+            .collect();
+        handles.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let total = handles.len();
+        let mut result = Vec::new();
+        let mut read = 0;
+        let mut last_read = None;
+        for (id, ticket_arc) in handles {
+            // The first ticket is always read so every call makes progress
+            let ticket = match deadline {
+                Some(deadline) if read > 0 => {
+                    if tokio::time::Instant::now() >= deadline {
+                        break;
+                    }
+                    match tokio::time::timeout_at(deadline, ticket_arc.read()).await {
+                        Ok(ticket) => ticket.clone(),
+                        Err(_) => break,
+                    }
+                }
+                _ => ticket_arc.read().await.clone(),
+            };
+            read += 1;
+            last_read = Some(id);
+            if include_deleted || !ticket.is_deleted() {
+                result.push(ticket);
+            }
+        }
+
+        let continuation = if read < total { last_read } else { None };
+
+        (result, continuation)
+    }
+
+    /// Retrieves all tickets with changes not yet pulled by the sync client.
+    ///
+    /// Soft-deleted tickets are included, with their `deleted_at`, so the
+    /// client can drop them too.
+    ///
+    /// # Returns
+    /// A vector containing clones of all tickets in the `Pending` sync state.
+    pub async fn list_pending_sync(&self) -> Vec<Ticket> {
+        let tickets = self.tickets.read().await;
+        let mut result = Vec::new();
+
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if ticket.sync_state == SyncState::Pending {
+                result.push(ticket.clone());
+            }
+        }
+
+        result
+    }
+
+    /// Marks a ticket as synced with the offline client.
+    ///
+    /// Soft-deleted tickets can be marked too, once the client has dropped
+    /// them. Only bookkeeping changes: the version, `updated_at` and the
+    /// change log are left alone and no event is published, so marking a
+    /// ticket doesn't look like an edit to other clients.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to mark as synced
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The ticket in its `Synced` state
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    pub async fn mark_synced(&self, id: &TicketId) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        match tickets.get(id) {
+            Some(ticket_arc) => {
+                let mut ticket = ticket_arc.write().await;
+                ticket.sync_state = SyncState::Synced;
+                Ok(ticket.clone())
+            }
+            None => Err(StoreError::TicketNotFound(id.clone())),
+        }
+    }
+
+    /// Records that one ticket blocks another.
+    ///
+    /// Linking a ticket it already blocks changes nothing. The link is
+    /// refused if `other` already blocks `id`, directly or through a chain
+    /// of tickets, since neither could then ever proceed.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the blocking ticket
+    /// * `other` - The ID of the ticket it blocks
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The blocking ticket with the link
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with ID `id`
+    /// * `Err(StoreError::InvalidField)` - If `other` doesn't exist, is `id`
+    ///   itself or the link would close a cycle
+    pub async fn add_block(&self, id: &TicketId, other: &TicketId) -> Result<Ticket, StoreError> {
+        // The map stays write-locked so no concurrent link can close a cycle
+        let tickets = self.tickets.write().await;
+        let ticket_arc = live_ticket(&tickets, id).await?;
+        check_link_target(&tickets, id, other).await?;
+        if reaches(&tickets, other, id, |ticket| {
+            ticket.relationships.blocks.clone()
+        })
+        .await
+        {
+            return Err(StoreError::InvalidField(format!(
+                "Ticket {} already blocks {}, directly or indirectly",
+                other, id
+            )));
+        }
+
+        let mut ticket = ticket_arc.write().await;
+        if !ticket.relationships.blocks.contains(other) {
+            let before = ticket.clone();
+            ticket.relationships.blocks.push(other.clone());
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+            self.record_update(id, field_changes(&before, &ticket));
+        }
+        Ok(ticket.clone())
+    }
+
+    /// Removes a blocking link between two tickets.
+    ///
+    /// Removing a link that doesn't exist changes nothing.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the blocking ticket
+    /// * `other` - The ID of the ticket it blocks
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The formerly blocking ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with ID `id`
+    pub async fn remove_block(
+        &self,
+        id: &TicketId,
+        other: &TicketId,
+    ) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = live_ticket(&tickets, id).await?;
+        let mut ticket = ticket_arc.write().await;
+
+        if ticket.relationships.blocks.contains(other) {
+            let before = ticket.clone();
+            ticket
+                .relationships
+                .blocks
+                .retain(|blocked| blocked != other);
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+            self.record_update(id, field_changes(&before, &ticket));
+        }
+        Ok(ticket.clone())
+    }
+
+    /// Sets or clears the parent of a ticket.
+    ///
+    /// A ticket can't become the parent of one of its own ancestors.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the child ticket
+    /// * `parent` - The ID of the new parent, or `None` to clear it
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The child ticket with its new parent
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with ID `id`
+    /// * `Err(StoreError::InvalidField)` - If `parent` doesn't exist, is `id`
+    ///   itself or descends from `id`
+    pub async fn set_parent(
+        &self,
+        id: &TicketId,
+        parent: Option<&TicketId>,
+    ) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.write().await;
+        let ticket_arc = live_ticket(&tickets, id).await?;
+        if let Some(parent) = parent {
+            check_link_target(&tickets, id, parent).await?;
+            if reaches(&tickets, parent, id, |ticket| {
+                ticket.relationships.parent.clone().into_iter().collect()
+            })
+            .await
+            {
+                return Err(StoreError::InvalidField(format!(
+                    "Ticket {} descends from {} and can't become its parent",
+                    parent, id
+                )));
+            }
+        }
+
+        let mut ticket = ticket_arc.write().await;
+        if ticket.relationships.parent.as_ref() != parent {
+            let before = ticket.clone();
+            ticket.relationships.parent = parent.cloned();
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+            self.record_update(id, field_changes(&before, &ticket));
+        }
+        Ok(ticket.clone())
+    }
+
+    /// Adds a tag to a ticket.
+    ///
+    /// Adding a tag the ticket already has changes nothing.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to tag
+    /// * `tag` - The validated tag to add
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The ticket with the tag
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    pub async fn add_tag(&self, id: &TicketId, tag: TicketTag) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+
+        let before = ticket.clone();
+        if ticket.add_tag(tag) {
+            ticket.sync_state = SyncState::Pending;
+            ticket.touch();
+            self.record_update(id, field_changes(&before, &ticket));
+        }
+        Ok(ticket.clone())
+    }
+
+    /// Removes a tag from a ticket.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to untag
+    /// * `tag` - The tag to remove, matched ignoring case
+    ///
+    /// # Returns
+    /// * `Ok(Ticket)` - The ticket without the tag
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    /// * `Err(StoreError::TagNotFound)` - If the ticket doesn't have the tag
+    pub async fn remove_tag(&self, id: &TicketId, tag: &str) -> Result<Ticket, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+
+        let tag = TicketTag::normalize(tag);
+        let Some(position) = ticket.tags.iter().position(|existing| *existing == tag) else {
+            return Err(StoreError::TagNotFound(tag));
+        };
+        let before = ticket.clone();
+        ticket.tags.remove(position);
+        ticket.sync_state = SyncState::Pending;
+        ticket.touch();
+        self.record_update(id, field_changes(&before, &ticket));
+        Ok(ticket.clone())
+    }
+
+    /// Appends a comment to a ticket.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to comment on
+    /// * `draft` - The validated comment
+    ///
+    /// # Returns
+    /// * `Ok(Comment)` - The stored comment, with its ID and timestamp
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    pub async fn add_comment(
+        &self,
+        id: &TicketId,
+        draft: CommentDraft,
+    ) -> Result<Comment, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+
+        let comment = Comment {
+            id: Uuid::new_v4(),
+            author: draft.author,
+            body: draft.body.0,
+            created_at: draft.created_at.unwrap_or_else(Utc::now),
+        };
+        ticket.comments.push(comment.clone());
+        ticket.sync_state = SyncState::Pending;
+        ticket.touch();
+        self.record_change(
+            TicketEvent::Updated { id: id.clone() },
+            vec!["comments".to_string(), "sync_state".to_string()],
+        );
+        Ok(comment)
+    }
+
+    /// Appends several comments to a ticket at once, keeping their order.
+    ///
+    /// Comments without a `created_at` are stamped with the current time.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket to comment on
+    /// * `drafts` - The validated comments, in the order to append them
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Comment>)` - The stored comments, in input order
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    pub async fn add_comments(
+        &self,
+        id: &TicketId,
+        drafts: Vec<CommentDraft>,
+    ) -> Result<Vec<Comment>, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let mut ticket = ticket_arc.write().await;
+        check_live(&ticket)?;
+        if drafts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let now = Utc::now();
+        let comments: Vec<Comment> = drafts
+            .into_iter()
+            .map(|draft| Comment {
+                id: Uuid::new_v4(),
+                author: draft.author,
+                body: draft.body.0,
+                created_at: draft.created_at.unwrap_or(now),
+            })
+            .collect();
+        ticket.comments.extend(comments.iter().cloned());
+        ticket.sync_state = SyncState::Pending;
+        ticket.touch();
+        self.record_change(
+            TicketEvent::Updated { id: id.clone() },
+            vec!["comments".to_string(), "sync_state".to_string()],
+        );
+        Ok(comments)
+    }
+
+    /// Retrieves a ticket's comments.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the ticket
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Comment>)` - The comments, oldest first
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the given ID
+    pub async fn list_comments(&self, id: &TicketId) -> Result<Vec<Comment>, StoreError> {
+        let tickets = self.tickets.read().await;
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        let ticket = ticket_arc.read().await;
+        check_live(&ticket)?;
+        Ok(ticket.comments.clone())
+    }
+
+    /// Computes where a ticket stands against the SLA target of its status.
+    ///
+    /// # Arguments
+    /// * `ticket` - The ticket to check
+    ///
+    /// # Returns
+    /// * `Some(SlaStatus)` - The deadline and remaining time in the current status
+    /// * `None` - If SLA tracking is off or the ticket's status has no target
+    pub fn sla_status(&self, ticket: &Ticket) -> Option<SlaStatus> {
+        let target = self.config.sla.as_ref()?.targets.get(&ticket.status)?;
+        let target = chrono::Duration::from_std(*target).ok()?;
+        Some(SlaStatus::compute(
+            ticket.status_changed_at,
+            target,
+            Utc::now(),
+        ))
+    }
+
+    /// Computes lead time statistics for the tickets completed in a window.
+    ///
+    /// Tickets that aren't `Done` and soft-deleted tickets are excluded. All tickets are read in a
+    /// single pass under the read lock.
+    ///
+    /// # Arguments
+    /// * `from` - Start of the window, inclusive; unbounded when `None`
+    /// * `to` - End of the window, exclusive; unbounded when `None`
+    ///
+    /// # Returns
+    /// Statistics over the lead times of the tickets completed in the window
+    pub async fn lead_time_stats(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> LeadTimeStats {
+        let tickets = self.tickets.read().await;
+        let mut lead_times = Vec::new();
+
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            let Some(completed_at) = ticket.completed_at else {
+                continue;
+            };
+            if ticket.status != Status::Done
+                || ticket.is_deleted()
+                || from.is_some_and(|from| completed_at < from)
+                || to.is_some_and(|to| completed_at >= to)
+            {
+                continue;
+            }
+            let lead_time = completed_at - ticket.created_at;
+            lead_times.push(lead_time.num_milliseconds() as f64 / 1000.0);
+        }
+
+        LeadTimeStats::from_seconds(lead_times)
+    }
+
+    /// Counts tickets in total and per status.
+    ///
+    /// Everything is counted under a single read lock, so the breakdown always
+    /// adds up to the total. Soft-deleted tickets aren't counted.
+    ///
+    /// # Returns
+    /// The total number of live tickets, and a map from every [`Status`] to the
+    /// number of tickets in it. Statuses without tickets count as zero.
+    pub async fn count_by_status(&self) -> (usize, HashMap<Status, usize>) {
+        let mut by_status: HashMap<Status, usize> =
+            Status::ALL.into_iter().map(|status| (status, 0)).collect();
+
+        let tickets = self.tickets.read().await;
+        let mut total = 0;
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if !ticket.is_deleted() {
+                *by_status.entry(ticket.status).or_insert(0) += 1;
+                total += 1;
+            }
+        }
+
+        (total, by_status)
+    }
+
+    /// Counts live tickets per assignee, in total and per status.
+    ///
+    /// Everything is counted under a single read lock, so the summaries are
+    /// consistent with each other. Soft-deleted tickets aren't counted.
+    ///
+    /// # Returns
+    /// One summary per distinct assignee, ordered by name, followed by one
+    /// with a `None` assignee for the unassigned tickets, if there are any
+    pub async fn assignee_summary(&self) -> Vec<AssigneeSummary> {
+        let mut assigned: BTreeMap<String, AssigneeSummary> = BTreeMap::new();
+        let mut unassigned = AssigneeSummary::new(None);
+
+        let tickets = self.tickets.read().await;
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if ticket.is_deleted() {
+                continue;
+            }
+            let summary = match &ticket.assignee {
+                Some(name) => assigned
+                    .entry(name.clone())
+                    .or_insert_with(|| AssigneeSummary::new(Some(name.clone()))),
+                None => &mut unassigned,
+            };
+            summary.count(ticket.status);
+        }
+
+        let mut summaries: Vec<AssigneeSummary> = assigned.into_values().collect();
+        if unassigned.total > 0 {
+            summaries.push(unassigned);
+        }
+        summaries
+    }
+
+    /// Sums the story point estimates of live tickets that aren't `Done`.
+    ///
+    /// Tickets without an estimate count as zero.
+    pub async fn total_estimate(&self) -> u64 {
+        let tickets = self.tickets.read().await;
+        let mut total = 0;
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if !ticket.is_deleted() && ticket.status != Status::Done {
+                total += u64::from(ticket.estimate.unwrap_or(0));
+            }
+        }
+        total
+    }
+
+    /// Counts live tickets by priority and status in a single pass.
+    ///
+    /// # Returns
+    /// A map from every [`Priority`] to a map from every [`Status`] to the
+    /// number of matching tickets. Combinations without tickets count as zero.
+    pub async fn count_by_priority_and_status(&self) -> HashMap<Priority, HashMap<Status, usize>> {
+        let mut matrix: HashMap<Priority, HashMap<Status, usize>> = Priority::ALL
+            .into_iter()
+            .map(|priority| {
+                let row = Status::ALL.into_iter().map(|status| (status, 0)).collect();
+                (priority, row)
+            })
+            .collect();
+
+        let tickets = self.tickets.read().await;
+        for ticket_arc in tickets.values() {
+            let ticket = ticket_arc.read().await;
+            if ticket.is_deleted() {
+                continue;
+            }
+            if let Some(count) = matrix
+                .get_mut(&ticket.priority)
+                .and_then(|row| row.get_mut(&ticket.status))
+            {
+                *count += 1;
+            }
+        }
+
+        matrix
+    }
+
+    /// Applies the same custom field changes to many tickets.
+    ///
+    /// The outer lock is taken once for the whole operation. Each ticket is
+    /// updated independently, so a failure on one ticket doesn't affect the others.
+    ///
+    /// # Arguments
+    /// * `ids` - The IDs of the tickets to update
+    /// * `set` - Custom fields to add or overwrite on each ticket
+    /// * `unset` - Custom field names to remove from each ticket
+    ///
+    /// # Returns
+    /// One result per ID, in input order:
+    /// * `Ok(Ticket)` - The updated ticket
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the ID
+    /// * `Err(StoreError::InvalidField)` - If the result exceeds the custom field limits
+    /// * `Err(StoreError::MissingRequiredFields)` - If the result lacks a field
+    ///   required by the ticket's kind
+    pub async fn bulk_update_custom_fields(
+        &self,
+        ids: &[TicketId],
+        set: &CustomFields,
+        unset: &[String],
+    ) -> Vec<Result<Ticket, StoreError>> {
+        let tickets = self.tickets.read().await;
+        let mut results = Vec::with_capacity(ids.len());
+        let mut changes = Vec::new();
+
+        for id in ids {
+            let Some(ticket_arc) = tickets.get(id) else {
+                results.push(Err(StoreError::TicketNotFound(id.clone())));
+                continue;
+            };
+            let mut ticket = ticket_arc.write().await;
+            if let Err(e) = check_live(&ticket) {
+                results.push(Err(e));
+                continue;
+            }
+
+            match self.updated_custom_fields(&ticket, set, unset) {
+                Ok(fields) => {
+                    let before = ticket.clone();
+                    ticket.custom_fields = fields;
+                    ticket.sync_state = SyncState::Pending;
+                    ticket.touch();
+                    changes.push((id, field_changes(&before, &ticket)));
+                    results.push(Ok(ticket.clone()));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+
+        for (id, changes) in changes {
+            self.record_update(id, changes);
+        }
+
+        results
+    }
+
+    /// Applies the same custom field changes to many tickets, all or nothing.
+    ///
+    /// Unlike [`TicketStore::bulk_update_custom_fields`], the outer write lock
+    /// is held for the whole operation, so no ticket can be created, patched
+    /// or deleted mid-batch. Every update is validated first and applied only
+    /// if all of them pass. The price is that every other store operation
+    /// waits until the batch is done, so this suits small batches that need
+    /// consistency, such as an atomic reprioritization, rather than large
+    /// backfills.
+    ///
+    /// # Arguments
+    /// * `ids` - The IDs of the tickets to update
+    /// * `set` - Custom fields to add or overwrite on each ticket
+    /// * `unset` - Custom field names to remove from each ticket
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Ticket>)` - Every ticket was updated, in input order
+    /// * `Err(Vec<Result<Ticket, StoreError>>)` - Nothing was updated. Holds
+    ///   one validation result per ID, in input order, like
+    ///   [`TicketStore::bulk_update_custom_fields`] would have returned.
+    pub async fn bulk_update_custom_fields_atomic(
+        &self,
+        ids: &[TicketId],
+        set: &CustomFields,
+        unset: &[String],
+    ) -> Result<Vec<Ticket>, Vec<Result<Ticket, StoreError>>> {
+        let tickets = self.tickets.write().await;
+        let mut planned = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let Some(ticket_arc) = tickets.get(id) else {
+                planned.push(Err(StoreError::TicketNotFound(id.clone())));
+                continue;
+            };
+            let ticket = ticket_arc.read().await;
+            if let Err(e) = check_live(&ticket) {
+                planned.push(Err(e));
+                continue;
+            }
+
+            planned.push(
+                self.updated_custom_fields(&ticket, set, unset)
+                    .map(|fields| Ticket {
+                        custom_fields: fields,
+                        sync_state: SyncState::Pending,
+                        version: ticket.version + 1,
+                        updated_at: Utc::now(),
+                        ..ticket.clone()
+                    }),
+            );
+        }
+
+        if planned.iter().any(Result::is_err) {
+            return Err(planned);
+        }
+
+        let mut updated = Vec::with_capacity(planned.len());
+        for ticket in planned.into_iter().flatten() {
+            if let Some(ticket_arc) = tickets.get(&ticket.id) {
+                let before = std::mem::replace(&mut *ticket_arc.write().await, ticket.clone());
+                self.record_update(&ticket.id, field_changes(&before, &ticket));
+            }
+            updated.push(ticket);
+        }
+
+        Ok(updated)
+    }
+
+    /// Moves many tickets to the same status.
+    ///
+    /// Like [`TicketStore::bulk_update_custom_fields`], the outer lock is
+    /// taken once and each ticket is updated independently, so a ticket
+    /// that can't make the transition doesn't hold back the others. A ticket
+    /// already in `status` is left untouched and counts as a success.
+    ///
+    /// # Arguments
+    /// * `ids` - The IDs of the tickets to update
+    /// * `status` - The status to move every ticket to
+    /// * `user` - Who is making the change, if known
+    ///
+    /// # Returns
+    /// One result per ID, in input order:
+    /// * `Ok(Ticket)` - The ticket, now in `status`
+    /// * `Err(StoreError::TicketNotFound)` - If no ticket exists with the ID
+    /// * `Err(StoreError::InvalidTransition)` - If the ticket can't move to
+    ///   `status`, along with the statuses it can move to
+    /// * `Err(StoreError::StatusOwnerOnly)` - If only the assignee may change
+    ///   the status and `user` isn't them
+    pub async fn bulk_update_status(
+        &self,
+        ids: &[TicketId],
+        status: Status,
+        user: Option<&str>,
+    ) -> Vec<Result<Ticket, StoreError>> {
+        let tickets = self.tickets.read().await;
+        let mut results = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let Some(ticket_arc) = tickets.get(id) else {
+                results.push(Err(StoreError::TicketNotFound(id.clone())));
+                continue;
+            };
+            let mut ticket = ticket_arc.write().await;
+            if let Err(e) = check_live(&ticket)
+                .and_then(|()| self.check_status_owner(&ticket, Some(status), user))
+            {
+                results.push(Err(e));
+                continue;
+            }
+            let from = ticket.status;
+            if from != status && !from.can_transition_to(status) {
+                results.push(Err(StoreError::InvalidTransition {
+                    from,
+                    to: status,
+                    allowed: from.allowed_transitions(),
+                }));
+                continue;
+            }
+            let patch = PatchTicketRequest {
+                status: Some(Some(status)),
+                ..PatchTicketRequest::default()
+            };
+            let before = ticket.clone();
+            let result = self.apply_patch(&mut ticket, patch, false);
+            match result {
+                Ok(changed) => {
+                    if changed {
+                        self.record_update(id, field_changes(&before, &ticket));
+                    }
+                    results.push(Ok(ticket.clone()));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+
+        results
+    }
+
+    /// Computes a ticket's custom fields after a bulk change, validating the result.
+    fn updated_custom_fields(
+        &self,
+        ticket: &Ticket,
+        set: &CustomFields,
+        unset: &[String],
+    ) -> Result<CustomFields, StoreError> {
+        let mut fields = ticket.custom_fields.clone();
+        fields.extend(set.clone());
+        for key in unset {
+            fields.remove(key);
+        }
+
+        if let Err(e) = validate_custom_fields(&fields, &self.config.custom_field_limits) {
+            return Err(StoreError::InvalidField(format!("custom_fields: {}", e)));
+        }
+        self.check_required_fields(ticket.kind.as_deref(), &fields)?;
+
+        Ok(fields)
+    }
+}
+
+/// Ticket fields [`TicketStore::undo_last_change`] reverts.
+///
+/// Fields that only follow other changes, such as `completed_at`, and
+/// links, which must stay consistent across tickets, are left out.
+pub const UNDOABLE_FIELDS: &[&str] = &[
+    "title",
+    "description",
+    "status",
+    "priority",
+    "assignee",
+    "reporter",
+    "category",
+    "kind",
+    "custom_fields",
+    "tags",
+    "external_ref",
+    "due_date",
+    "estimate",
+    "rank",
+];
+
+/// Fails with [`StoreError::VersionConflict`] unless `ticket` is at `expected`.
+///
+/// Passes unconditionally when `expected` is `None`.
+fn check_version(ticket: &Ticket, expected: Option<u64>) -> Result<(), StoreError> {
+    match expected {
+        Some(expected) if expected != ticket.version => Err(StoreError::VersionConflict {
+            expected,
+            actual: ticket.version,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Fails with [`StoreError::TicketNotFound`] if `ticket` is soft-deleted.
+fn check_live(ticket: &Ticket) -> Result<(), StoreError> {
+    if ticket.is_deleted() {
+        return Err(StoreError::TicketNotFound(ticket.id.clone()));
+    }
+    Ok(())
+}
+
+/// Returns the lock of a ticket that exists and isn't soft-deleted.
+async fn live_ticket<'a>(
+    tickets: &'a HashMap<TicketId, Arc<RwLock<Ticket>>>,
+    id: &TicketId,
+) -> Result<&'a Arc<RwLock<Ticket>>, StoreError> {
+    let ticket_arc = tickets
+        .get(id)
+        .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+    check_live(&*ticket_arc.read().await)?;
+    Ok(ticket_arc)
+}
+
+/// Checks that ticket `id` may link to ticket `target`: the target must be
+/// another, live ticket.
+async fn check_link_target(
+    tickets: &HashMap<TicketId, Arc<RwLock<Ticket>>>,
+    id: &TicketId,
+    target: &TicketId,
+) -> Result<(), StoreError> {
+    if id == target {
+        return Err(StoreError::InvalidField(
+            "A ticket can't be linked to itself".to_string(),
+        ));
+    }
+    if live_ticket(tickets, target).await.is_err() {
+        return Err(StoreError::InvalidField(format!(
+            "Linked ticket {} not found",
+            target
+        )));
+    }
+    Ok(())
+}
+
+/// Returns true if ticket `to` can be reached from ticket `from` by
+/// following the links `next` returns.
+async fn reaches(
+    tickets: &HashMap<TicketId, Arc<RwLock<Ticket>>>,
+    from: &TicketId,
+    to: &TicketId,
+    next: impl Fn(&Ticket) -> Vec<TicketId>,
+) -> bool {
+    let mut pending = vec![from.clone()];
+    let mut seen = HashSet::new();
+    while let Some(current) = pending.pop() {
+        if &current == to {
+            return true;
+        }
+        if !seen.insert(current.clone()) {
+            continue;
+        }
+        if let Some(ticket_arc) = tickets.get(&current) {
+            pending.extend(next(&*ticket_arc.read().await));
+        }
+    }
+    false
+}
+
+/// A ticket added by [`TicketStore::seed_demo_data`].
+struct DemoTicket {
+    title: &'static str,
+    description: &'static str,
+    status: Status,
+    priority: Priority,
+    reporter: &'static str,
+    category: &'static str,
+    assignee: Option<&'static str>,
+    tags: &'static [&'static str],
+    /// How long ago the ticket was created
+    created_hours_ago: i64,
+    /// How long ago the ticket entered its status; at most `created_hours_ago`
+    changed_hours_ago: i64,
+}
+
+/// The demo tickets, oldest first.
+const DEMO_TICKETS: &[DemoTicket] = &[
+    DemoTicket {
+        title: "Login page times out on slow connections",
+        description: "Users on mobile data report the login form spinning for over a minute before failing. The auth request has no client-side timeout.",
+        status: Status::Done,
+        priority: Priority::Critical,
+        reporter: "maria",
+        category: "bug",
+        assignee: Some("dev-team"),
+        tags: &["auth", "mobile"],
+        created_hours_ago: 164,
+        changed_hours_ago: 120,
+    },
+    DemoTicket {
+        title: "Add CSV export to the reports screen",
+        description: "Finance wants to pull the monthly report into a spreadsheet without copying it by hand.",
+        status: Status::InProgress,
+        priority: Priority::Medium,
+        reporter: "tom",
+        category: "feature",
+        assignee: Some("ana"),
+        tags: &["reports"],
+        created_hours_ago: 150,
+        changed_hours_ago: 30,
+    },
+    DemoTicket {
+        title: "Upgrade the database driver",
+        description: "The current driver version is out of support. Check the changelog for breaking changes before bumping it.",
+        status: Status::ToDo,
+        priority: Priority::Low,
+        reporter: "sam",
+        category: "maintenance",
+        assignee: None,
+        tags: &["dependencies"],
+        created_hours_ago: 130,
+        changed_hours_ago: 130,
+    },
+    DemoTicket {
+        title: "Dark mode for the dashboard",
+        description: "Several users asked for a dark theme. Dropped for now in favour of accessibility fixes.",
+        status: Status::Cancelled,
+        priority: Priority::Low,
+        reporter: "lee",
+        category: "feature",
+        assignee: None,
+        tags: &["ui"],
+        created_hours_ago: 110,
+        changed_hours_ago: 70,
+    },
+    DemoTicket {
+        title: "Password reset emails are not delivered",
+        description: "Reset emails bounce for addresses on some providers. The sender domain may be missing an SPF record.",
+        status: Status::InProgress,
+        priority: Priority::High,
+        reporter: "maria",
+        category: "bug",
+        assignee: Some("dev-team"),
+        tags: &["auth", "email"],
+        created_hours_ago: 90,
+        changed_hours_ago: 20,
+    },
+    DemoTicket {
+        title: "Document the release process",
+        description: "Write down the steps for tagging, building and deploying a release so anyone on the team can do it.",
+        status: Status::Done,
+        priority: Priority::Medium,
+        reporter: "sam",
+        category: "docs",
+        assignee: Some("sam"),
+        tags: &["docs"],
+        created_hours_ago: 72,
+        changed_hours_ago: 8,
+    },
+    DemoTicket {
+        title: "Checkout fails with an expired session",
+        description: "Customers whose session expires on the payment page lose their cart. They should be sent to log in and come back to their cart.",
+        status: Status::ToDo,
+        priority: Priority::Critical,
+        reporter: "tom",
+        category: "bug",
+        assignee: None,
+        tags: &["checkout"],
+        created_hours_ago: 26,
+        changed_hours_ago: 26,
+    },
+    DemoTicket {
+        title: "Cache the product catalogue",
+        description: "The catalogue is read on every page load but changes a few times a day. A short-lived cache would cut most of the database load.",
+        status: Status::ToDo,
+        priority: Priority::High,
+        reporter: "ana",
+        category: "performance",
+        assignee: Some("ana"),
+        tags: &["performance"],
+        created_hours_ago: 4,
+        changed_hours_ago: 4,
+    },
+];
+
+/// Marks a ticket that is leaving the map for good as deleted.
+///
+/// Writers holding its handle without the outer lock, such as
+/// [`TicketStore::patch_ticket`], then find it gone instead of updating a
+/// ticket no longer in the store.
+fn tombstone(ticket: &mut Ticket) {
+    ticket.deleted_at.get_or_insert_with(Utc::now);
+}
+
+/// Returns the current state of a ticket within a transaction: its staged
+/// copy if the transaction touched it, else the stored ticket.
+///
+/// A stored ticket is write-locked the first time it is read, and stays
+/// locked in `locked` until the transaction is done, so a writer that
+/// doesn't take the outer lock can't change it in between.
+async fn staged_ticket(
+    tickets: &HashMap<TicketId, Arc<RwLock<Ticket>>>,
+    staged: &HashMap<TicketId, Ticket>,
+    locked: &mut HashMap<TicketId, OwnedRwLockWriteGuard<Ticket>>,
+    id: &TicketId,
+) -> Result<Ticket, StoreError> {
+    if let Some(ticket) = staged.get(id) {
+        check_live(ticket)?;
+        return Ok(ticket.clone());
+    }
+    if !locked.contains_key(id) {
+        let ticket_arc = tickets
+            .get(id)
+            .ok_or_else(|| StoreError::TicketNotFound(id.clone()))?;
+        locked.insert(id.clone(), ticket_arc.clone().write_owned().await);
+    }
+    let ticket = &locked[id];
+    check_live(ticket)?;
+    Ok((**ticket).clone())
+}
+
+/// Lists the fields that differ between a ticket before and after an update,
+/// with their old (`a`) and new (`b`) values.
+///
+/// The `version` and `updated_at`, which every update bumps, are left out.
+fn field_changes(before: &Ticket, after: &Ticket) -> Vec<FieldDiff> {
+    before
+        .diff(after)
+        .into_iter()
+        .filter(|diff| diff.field != "version" && diff.field != "updated_at")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CreationThrottle, DescriptionShrinkGuard, TrashPolicy};
+    use crate::id::SequentialIdGenerator;
+
+    fn draft(title: &str) -> TicketDraft {
+        TicketDraft::builder().title(title).build().unwrap()
+    }
+
+    fn sequential_store() -> TicketStore {
+        TicketStore::with_id_generator(
+            ServerConfig::default(),
+            Arc::new(SequentialIdGenerator::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn generated_ids_skip_tickets_already_stored() {
+        let source = sequential_store();
+        for title in ["one", "two", "three"] {
+            source.add_ticket(draft(title)).await.unwrap();
+        }
+        let snapshot = source.export_snapshot().await;
+
+        // A fresh sequence restarts at 1, which the snapshot already holds
+        let store = sequential_store();
+        store.import_snapshot(snapshot).await.unwrap();
+        let ticket = store.add_ticket(draft("four")).await.unwrap();
+        let created = store
+            .add_tickets(vec![draft("five"), draft("six")])
+            .await
+            .unwrap();
+
+        assert_eq!(ticket.id.0.as_u128(), 4);
+        assert_eq!(
+            created
+                .iter()
+                .map(|ticket| ticket.id.0.as_u128())
+                .collect::<Vec<_>>(),
+            [5, 6]
+        );
+        assert_eq!(store.list_tickets().await.len(), 6);
+        let first = store.get_ticket(&TicketId(uuid::Uuid::from_u128(1))).await;
+        assert_eq!(first.unwrap().title.0, "one");
+    }
+
+    #[tokio::test]
+    async fn a_generator_repeating_itself_is_refused() {
+        let id = TicketId(uuid::Uuid::from_u128(7));
+        let fixed = id.clone();
+        let store = TicketStore::with_id_generator(
+            ServerConfig::default(),
+            Arc::new(move || fixed.clone()),
+        );
+
+        store.add_ticket(draft("first")).await.unwrap();
+        let second = store.add_ticket(draft("second")).await;
+
+        assert!(matches!(second, Err(StoreError::IdCollision(taken)) if taken == id));
+        assert_eq!(store.get_ticket(&id).await.unwrap().title.0, "first");
+    }
+
+    #[tokio::test]
+    async fn replace_follows_the_transition_rules() {
+        let store = sequential_store();
+        let id = store.add_ticket(draft("todo")).await.unwrap().id;
+
+        let skipped = store
+            .replace_ticket(&id, draft("done"), Status::Done, None, None)
+            .await;
+        assert!(matches!(
+            skipped,
+            Err(StoreError::InvalidTransition {
+                from: Status::ToDo,
+                to: Status::Done,
+                ..
+            })
+        ));
+        assert_eq!(store.get_ticket(&id).await.unwrap().title.0, "todo");
+
+        store
+            .replace_ticket(&id, draft("started"), Status::InProgress, None, None)
+            .await
+            .unwrap();
+        store
+            .transition_status(&id, Status::Done, None)
+            .await
+            .unwrap();
+        let reset = store
+            .replace_ticket(&id, draft("reset"), Status::ToDo, None, None)
+            .await;
+        assert!(matches!(reset, Err(StoreError::InvalidTransition { .. })));
+
+        let kept = store
+            .replace_ticket(&id, draft("renamed"), Status::Done, None, None)
+            .await
+            .unwrap();
+        assert_eq!(kept.status, Status::Done);
+        assert_eq!(kept.title.0, "renamed");
+    }
+
+    #[tokio::test]
+    async fn bulk_status_reports_invalid_transitions_per_ticket() {
+        let store = sequential_store();
+        let todo = store.add_ticket(draft("todo")).await.unwrap().id;
+        let started = store.add_ticket(draft("started")).await.unwrap().id;
+        store
+            .transition_status(&started, Status::InProgress, None)
+            .await
+            .unwrap();
+
+        let results = store
+            .bulk_update_status(&[todo.clone(), started.clone()], Status::Done, None)
+            .await;
+
+        assert!(matches!(
+            &results[0],
+            Err(StoreError::InvalidTransition {
+                from: Status::ToDo,
+                to: Status::Done,
+                ..
+            })
+        ));
+        assert_eq!(results[1].as_ref().unwrap().status, Status::Done);
+        assert_eq!(store.get_ticket(&todo).await.unwrap().status, Status::ToDo);
+    }
+
+    fn owned(status_owner_only: bool) -> TicketStore {
+        TicketStore::with_config(ServerConfig {
+            status_owner_only,
+            ..ServerConfig::default()
+        })
+    }
+
+    fn is_owner_only(result: Result<impl std::fmt::Debug, StoreError>) -> bool {
+        matches!(result, Err(StoreError::StatusOwnerOnly { assignee }) if assignee == "ana")
+    }
+
+    #[tokio::test]
+    async fn only_the_assignee_changes_the_status_on_every_path() {
+        let store = owned(true);
+        let assigned = TicketDraft::builder()
+            .title("owned")
+            .assignee("ana")
+            .build()
+            .unwrap();
+        let id = store.add_ticket(assigned).await.unwrap().id;
+        let bob = Some("bob");
+        let start = || PatchTicketRequest {
+            status: Some(Some(Status::InProgress)),
+            ..PatchTicketRequest::default()
+        };
+
+        assert!(is_owner_only(
+            store.transition_status(&id, Status::InProgress, bob).await
+        ));
+        assert!(is_owner_only(
+            store.transition_status(&id, Status::InProgress, None).await
+        ));
+        assert!(is_owner_only(
+            store
+                .compare_and_swap_status(&id, Status::ToDo, Status::InProgress, bob)
+                .await
+        ));
+        assert!(is_owner_only(
+            store
+                .replace_ticket(&id, draft("owned"), Status::InProgress, None, bob)
+                .await
+        ));
+        assert!(is_owner_only(
+            store.patch_ticket(&id, start(), None, false, bob).await
+        ));
+        let bulk = store
+            .bulk_update_status(std::slice::from_ref(&id), Status::InProgress, bob)
+            .await;
+        assert!(is_owner_only(bulk.into_iter().next().unwrap()));
+        for op in [
+            TicketOperation::Transition(id.clone(), Status::InProgress),
+            TicketOperation::Patch(id.clone(), start(), false),
+        ] {
+            let result = store.apply_transaction(vec![op], bob).await;
+            assert!(matches!(
+                result,
+                Err((0, StoreError::StatusOwnerOnly { .. }))
+            ));
+        }
+        assert_eq!(store.get_ticket(&id).await.unwrap().status, Status::ToDo);
+
+        // Anyone may edit the other fields, and the assignee the status
+        let rename = PatchTicketRequest {
+            title: Some(Some("renamed".to_string())),
+            ..PatchTicketRequest::default()
+        };
+        store
+            .patch_ticket(&id, rename, None, false, bob)
+            .await
+            .unwrap();
+        store
+            .transition_status(&id, Status::InProgress, Some("ana"))
+            .await
+            .unwrap();
+        assert!(is_owner_only(store.undo_last_change(&id, bob).await));
+
+        store
+            .transition_status(&id, Status::Done, Some("ana"))
+            .await
+            .unwrap();
+        assert!(is_owner_only(store.reopen(&id, None, false, bob).await));
+        let reopened = store.reopen(&id, None, false, Some("ana")).await.unwrap();
+        assert_eq!(reopened.status, Status::InProgress);
+    }
+
+    #[tokio::test]
+    async fn status_changes_are_open_without_owner_only() {
+        let store = owned(false);
+        let assigned = TicketDraft::builder()
+            .title("owned")
+            .assignee("ana")
+            .build()
+            .unwrap();
+        let id = store.add_ticket(assigned).await.unwrap().id;
+
+        let moved = store
+            .transition_status(&id, Status::InProgress, Some("bob"))
+            .await
+            .unwrap();
+        assert_eq!(moved.to, Status::InProgress);
+    }
+
+    #[tokio::test]
+    async fn tag_and_custom_field_changes_can_be_undone() {
+        let store = sequential_store();
+        let id = store.add_ticket(draft("tagged")).await.unwrap().id;
+
+        store
+            .add_tag(&id, TicketTag::new("urgent".to_string()).unwrap())
+            .await
+            .unwrap();
+        let (ticket, field) = store.undo_last_change(&id, None).await.unwrap();
+        assert_eq!(field, "tags");
+        assert!(ticket.tags.is_empty());
+
+        let set = CustomFields::from([("team".to_string(), serde_json::json!("core"))]);
+        store
+            .bulk_update_custom_fields(std::slice::from_ref(&id), &set, &[])
+            .await;
+        store
+            .bulk_update_custom_fields_atomic(
+                std::slice::from_ref(&id),
+                &CustomFields::new(),
+                &["team".to_string()],
+            )
+            .await
+            .unwrap();
+        let history = store.get_history(&id).await.unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .filter(|entry| entry.field == "custom_fields")
+                .count(),
+            2
+        );
+
+        let (ticket, field) = store.undo_last_change(&id, None).await.unwrap();
+        assert_eq!(field, "custom_fields");
+        assert_eq!(ticket.custom_fields, set);
+    }
+
+    fn remembers(store: &TicketStore, id: &TicketId) -> bool {
+        store.history.lock().unwrap().contains_key(id)
+            || store
+                .idempotency_keys
+                .lock()
+                .unwrap()
+                .creations
+                .values()
+                .any(|(_, ticket)| ticket.id == *id)
+            || store.changes.lock().unwrap().records.iter().any(|record| {
+                record.event.id() == id && !matches!(record.event, TicketEvent::Deleted { .. })
+            })
+    }
+
+    #[tokio::test]
+    async fn purges_forget_the_removed_tickets() {
+        let store = sequential_store();
+        let (gone, _) = store
+            .add_ticket_idempotent("key-1", 1, draft("gone"))
+            .await
+            .unwrap();
+        let kept = store.add_ticket(draft("kept")).await.unwrap();
+        for id in [&gone.id, &kept.id] {
+            store
+                .transition_status(id, Status::InProgress, None)
+                .await
+                .unwrap();
+        }
+        store.soft_delete(&gone.id).await.unwrap();
+        assert!(remembers(&store, &gone.id));
+
+        assert_eq!(store.purge_soft_deleted(None).await, 1);
+        assert!(!remembers(&store, &gone.id));
+        assert!(remembers(&store, &kept.id));
+        let (replayed, created) = store
+            .add_ticket_idempotent("key-1", 1, draft("gone"))
+            .await
+            .unwrap();
+        assert!(created);
+        assert_ne!(replayed.id, gone.id);
+
+        store
+            .transition_status(&kept.id, Status::Done, None)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(store.purge_expired(Duration::from_millis(1)).await, 1);
+        assert!(!remembers(&store, &kept.id));
+        let log = store.changes.lock().unwrap();
+        assert!(log.records.iter().any(|record| {
+            record.event
+                == TicketEvent::Deleted {
+                    id: kept.id.clone(),
+                }
+        }));
+    }
+
+    fn retitle(title: &str) -> PatchTicketRequest {
+        PatchTicketRequest {
+            title: Some(Some(title.to_string())),
+            ..PatchTicketRequest::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn a_patch_waiting_on_its_ticket_doesnt_hold_up_the_map() {
+        let store = sequential_store();
+        let id = store.add_ticket(draft("busy")).await.unwrap().id;
+        let ticket_arc = store.tickets.read().await[&id].clone();
+        let held = ticket_arc.write_owned().await;
+
+        let patch = tokio::spawn({
+            let (store, id) = (store.clone(), id.clone());
+            async move {
+                store
+                    .patch_ticket(&id, retitle("patched"), None, false, None)
+                    .await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let created =
+            tokio::time::timeout(Duration::from_secs(1), store.add_ticket(draft("other"))).await;
+        assert!(created.expect("add_ticket waited on the patch").is_ok());
+
+        drop(held);
+        let (patched, changed) = patch.await.unwrap().unwrap();
+        assert!(changed);
+        assert_eq!(patched.title.0, "patched");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_reads_and_writes_finish_without_losing_updates() {
+        let store = sequential_store();
+        let a = store.add_ticket(draft("a")).await.unwrap();
+        let b = store.add_ticket(draft("b")).await.unwrap();
+
+        let mut tasks = Vec::new();
+        for round in 0..50 {
+            let (store, a, b) = (store.clone(), a.id.clone(), b.id.clone());
+            tasks.push(tokio::spawn(async move {
+                let mut updates_to_a = 0;
+                let title = format!("patch {}", round);
+                if store
+                    .patch_ticket(&a, retitle(&title), None, false, None)
+                    .await
+                    .is_ok()
+                {
+                    updates_to_a += 1;
+                }
+                store.get_ticket(&a).await.unwrap();
+                store.list_tickets().await;
+                let operations = vec![
+                    TicketOperation::Patch(a.clone(), retitle(&format!("tx {}", round)), false),
+                    TicketOperation::Patch(b, retitle(&format!("tx {}", round)), false),
+                ];
+                if store.apply_transaction(operations, None).await.is_ok() {
+                    updates_to_a += 1;
+                }
+                let extra = store.add_ticket(draft("extra")).await.unwrap();
+                store.delete_ticket(&extra.id).await.unwrap();
+                updates_to_a
+            }));
+        }
+
+        let all = async {
+            let mut updates_to_a = 0;
+            for task in tasks {
+                updates_to_a += task.await.unwrap();
+            }
+            updates_to_a
+        };
+        let updates_to_a = tokio::time::timeout(Duration::from_secs(10), all)
+            .await
+            .expect("concurrent store operations deadlocked");
+
+        assert_eq!(updates_to_a, 100);
+        let a = store.get_ticket(&a.id).await.unwrap();
+        assert_eq!(a.version, 1 + updates_to_a);
+        assert_eq!(store.list_tickets().await.len(), 2);
+        assert_eq!(store.list_trash().await.len(), 50);
+    }
+
+    #[tokio::test]
+    async fn deleted_tickets_go_to_the_trash_until_purged() {
+        let store = sequential_store();
+        let id = store.add_ticket(draft("doomed")).await.unwrap().id;
+
+        let deleted = store.delete_ticket(&id).await.unwrap();
+        assert!(deleted.is_deleted());
+        assert_eq!(store.list_trash().await[0].id, id);
+        store.restore_from_trash(&id).await.unwrap();
+        assert!(!store.get_ticket(&id).await.unwrap().is_deleted());
+
+        store.purge_ticket(&id).await.unwrap();
+        assert!(store.get_ticket(&id).await.is_err());
+        assert!(matches!(
+            store.restore_from_trash(&id).await,
+            Err(StoreError::TicketNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_loaded_fixture_carries_on_where_the_capture_left_off() {
+        let source = sequential_store();
+        let (created, _) = source
+            .add_ticket_idempotent("key-1", 7, draft("original"))
+            .await
+            .unwrap();
+        source
+            .patch_ticket(&created.id, retitle("renamed"), None, false, None)
+            .await
+            .unwrap();
+        let fixture = source.export_fixture().await;
+        let json = serde_json::to_string(&fixture).unwrap();
+
+        let store = sequential_store();
+        store.add_ticket(draft("replaced")).await.unwrap();
+        store
+            .load_fixture(serde_json::from_str(&json).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(store.version(), source.version());
+        assert_eq!(
+            store.get_history(&created.id).await.unwrap(),
+            source.get_history(&created.id).await.unwrap()
+        );
+        assert_eq!(
+            store.changes_since(0).unwrap(),
+            source.changes_since(0).unwrap()
+        );
+        let (replayed, fresh) = store
+            .add_ticket_idempotent("key-1", 7, draft("original"))
+            .await
+            .unwrap();
+        assert!(!fresh);
+        assert_eq!(replayed, created);
+        let (undone, field) = store.undo_last_change(&created.id, None).await.unwrap();
+        assert_eq!(field, "title");
+        assert_eq!(undone.title.0, "original");
+    }
+
+    #[tokio::test]
+    async fn a_fixture_with_history_for_a_missing_ticket_is_refused() {
+        let store = sequential_store();
+        let mut fixture = store.export_fixture().await;
+        fixture
+            .history
+            .insert(TicketId(uuid::Uuid::from_u128(9)), Vec::new());
+
+        let loaded = store.load_fixture(fixture).await;
+        assert!(matches!(loaded, Err(StoreError::InvalidField(_))));
+    }
+
+    #[tokio::test]
+    async fn the_creation_throttle_forgets_idle_reporters() {
+        let store = TicketStore::with_config(ServerConfig {
+            creation_throttle: Some(CreationThrottle::new(1, Duration::from_millis(20))),
+            ..ServerConfig::default()
+        });
+        for reporter in 0..100 {
+            store.throttle_creation(&reporter.to_string()).unwrap();
+        }
+        assert_eq!(store.creations.lock().unwrap().len(), 100);
+        assert!(store.throttle_creation("0").is_err());
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        store.throttle_creation("new").unwrap();
+        assert_eq!(store.creations.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn deletions_are_synced_and_marking_is_not_an_edit() {
+        let store = sequential_store();
+        let id = store.add_ticket(draft("synced")).await.unwrap().id;
+
+        let marked = store.mark_synced(&id).await.unwrap();
+        assert_eq!(marked.sync_state, SyncState::Synced);
+        assert_eq!(marked.version, 1);
+        let version = store.version();
+        store.mark_synced(&id).await.unwrap();
+        assert_eq!(store.version(), version);
+
+        store.soft_delete(&id).await.unwrap();
+        let pending = store.list_pending_sync().await;
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].deleted_at.is_some());
+
+        store.mark_synced(&id).await.unwrap();
+        assert!(store.list_pending_sync().await.is_empty());
+        let restored = store.restore(&id).await.unwrap();
+        assert_eq!(restored.sync_state, SyncState::Pending);
+    }
+
+    #[tokio::test]
+    async fn patches_clear_null_fields_and_keep_omitted_ones() {
+        let store = sequential_store();
+        let due_date = Utc::now() + chrono::Duration::days(3);
+        let draft = TicketDraft::builder()
+            .title("merge")
+            .description("to be cleared")
+            .assignee("ana")
+            .due_date(due_date)
+            .estimate(5)
+            .build()
+            .unwrap();
+        let id = store.add_ticket(draft).await.unwrap().id;
+
+        let patch = |body| serde_json::from_value::<PatchTicketRequest>(body).unwrap();
+        let (ticket, changed) = store
+            .patch_ticket(
+                &id,
+                patch(serde_json::json!({
+                    "assignee": null,
+                    "description": null,
+                    "due_date": null,
+                    "estimate": null,
+                })),
+                None,
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(changed);
+        assert_eq!(ticket.assignee, None);
+        assert_eq!(ticket.description.0, "");
+        assert_eq!(ticket.due_date, None);
+        assert_eq!(ticket.estimate, None);
+        assert_eq!(ticket.title.0, "merge");
+        assert_eq!(ticket.priority, Priority::default());
+
+        let (ticket, _) = store
+            .patch_ticket(
+                &id,
+                patch(serde_json::json!({ "assignee": "bo", "estimate": 8 })),
+                None,
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ticket.assignee.as_deref(), Some("bo"));
+        assert_eq!(ticket.estimate, Some(8));
+        assert_eq!(ticket.due_date, None);
+        assert_eq!(ticket.title.0, "merge");
+
+        let (ticket, changed) = store
+            .patch_ticket(&id, patch(serde_json::json!({})), None, false, None)
+            .await
+            .unwrap();
+        assert!(!changed);
+        assert_eq!(ticket.assignee.as_deref(), Some("bo"));
+    }
+
+    #[tokio::test]
+    async fn a_held_ticket_lock_times_out_instead_of_hanging() {
+        let config = ServerConfig {
+            lock_timeout: Duration::from_millis(20),
+            ..ServerConfig::default()
+        };
+        let store = TicketStore::with_config(config);
+        let id = store.add_ticket(draft("held")).await.unwrap().id;
+        let ticket_arc = store.tickets.read().await[&id].clone();
+        let held = ticket_arc.write_owned().await;
+
+        let read = tokio::time::timeout(Duration::from_secs(1), store.get_ticket(&id))
+            .await
+            .expect("get_ticket hung on the held lock");
+        assert!(matches!(read, Err(StoreError::Timeout(..))));
+        let write = tokio::time::timeout(
+            Duration::from_secs(1),
+            store.patch_ticket(&id, retitle("late"), None, false, None),
+        )
+        .await
+        .expect("patch_ticket hung on the held lock");
+        let error = write.unwrap_err();
+        assert!(matches!(error, StoreError::Timeout(..)));
+        assert_eq!(
+            crate::error::ApiError::from(error).status,
+            axum::http::StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        drop(held);
+        assert_eq!(store.get_ticket(&id).await.unwrap().title.0, "held");
+    }
+
+    #[tokio::test]
+    async fn the_shrink_guard_allows_up_to_its_limit_unless_forced() {
+        let describe = |length: usize| PatchTicketRequest {
+            description: Some(Some("x".repeat(length))),
+            ..PatchTicketRequest::default()
+        };
+        let unguarded = sequential_store();
+        let id = unguarded.add_ticket(draft("open")).await.unwrap().id;
+        unguarded
+            .patch_ticket(&id, describe(100), None, false, None)
+            .await
+            .unwrap();
+        unguarded
+            .patch_ticket(&id, describe(0), None, false, None)
+            .await
+            .unwrap();
+
+        let config = ServerConfig {
+            description_shrink_guard: Some(DescriptionShrinkGuard {
+                max_shrink_percent: 80,
+            }),
+            ..ServerConfig::default()
+        };
+        let store = TicketStore::with_config(config);
+        let id = store.add_ticket(draft("guarded")).await.unwrap().id;
+        store
+            .patch_ticket(&id, describe(100), None, false, None)
+            .await
+            .unwrap();
+
+        // 81 of 100 characters removed
+        let error = store
+            .patch_ticket(&id, describe(19), None, false, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            StoreError::DescriptionShrink { from: 100, to: 19 }
+        ));
+        assert_eq!(
+            crate::error::ApiError::from(error).status,
+            axum::http::StatusCode::CONFLICT
+        );
+        assert_eq!(
+            store.get_ticket(&id).await.unwrap().description.0.len(),
+            100
+        );
+
+        // Exactly 80 removed is still allowed
+        let (ticket, _) = store
+            .patch_ticket(&id, describe(20), None, false, None)
+            .await
+            .unwrap();
+        assert_eq!(ticket.description.0.len(), 20);
+
+        let (ticket, _) = store
+            .patch_ticket(&id, describe(0), None, true, None)
+            .await
+            .unwrap();
+        assert_eq!(ticket.description.0, "");
+    }
+
+    #[tokio::test]
+    async fn the_trash_keeps_the_most_recent_deletions() {
+        let config = ServerConfig {
+            trash: Some(TrashPolicy {
+                max_tickets: 2,
+                ..TrashPolicy::default()
+            }),
+            ..ServerConfig::default()
+        };
+        let store =
+            TicketStore::with_id_generator(config, Arc::new(SequentialIdGenerator::default()));
+        let mut ids = Vec::new();
+        for title in ["one", "two", "three", "live"] {
+            ids.push(store.add_ticket(draft(title)).await.unwrap().id);
+        }
+        store.soft_delete(&ids[0]).await.unwrap();
+        store.soft_delete(&ids[1]).await.unwrap();
+        store
+            .apply_transaction(vec![TicketOperation::Delete(ids[2].clone())], None)
+            .await
+            .unwrap();
+
+        let trash: Vec<TicketId> = store
+            .list_trash()
+            .await
+            .into_iter()
+            .map(|ticket| ticket.id)
+            .collect();
+        assert_eq!(trash, [ids[2].clone(), ids[1].clone()]);
+        assert!(!store.tickets.read().await.contains_key(&ids[0]));
+        assert!(!remembers(&store, &ids[0]));
+
+        assert!(matches!(
+            store.restore_from_trash(&ids[3]).await,
+            Err(StoreError::TicketNotFound(_))
+        ));
+        let restored = store.restore_from_trash(&ids[1]).await.unwrap();
+        assert!(!restored.is_deleted());
+        assert_eq!(store.list_trash().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn time_in_status_adds_up_each_stay() {
+        let store = sequential_store();
+        let id = store.add_ticket(draft("timed")).await.unwrap().id;
+        for status in [Status::InProgress, Status::Done] {
+            store.transition_status(&id, status, None).await.unwrap();
+        }
+        // Move the ticket's timeline back to whole hours
+        let now = Utc::now();
+        let hours_ago = |hours| now - chrono::Duration::hours(hours);
+        store.tickets.read().await[&id].write().await.created_at = hours_ago(10);
+        for (entry, at) in store
+            .history
+            .lock()
+            .unwrap()
+            .get_mut(&id)
+            .unwrap()
+            .iter_mut()
+            .filter(|entry| entry.field == "status")
+            .zip([hours_ago(8), hours_ago(3)])
+        {
+            entry.at = at;
+        }
+
+        let report = store.time_in_status(&id).await.unwrap();
+        let hours: Vec<(Status, f64)> = report
+            .iter()
+            .map(|time| (time.status, (time.seconds / 3600.0).round()))
+            .collect();
+        assert_eq!(
+            hours,
+            [
+                (Status::ToDo, 2.0),
+                (Status::InProgress, 5.0),
+                (Status::Done, 3.0),
+                (Status::Cancelled, 0.0)
+            ]
+        );
+    }
+
+    /// The live tickets of `store`, in backlog order.
+    async fn backlog(store: &TicketStore) -> Vec<Ticket> {
+        let mut tickets = store.list_tickets().await;
+        tickets.sort_by(|a, b| a.rank.total_cmp(&b.rank));
+        tickets
+    }
+
+    #[tokio::test]
+    async fn reorder_moves_to_the_start_and_end_changing_only_the_moved_ticket() {
+        let store = sequential_store();
+        let mut ids = Vec::new();
+        for title in ["a", "b", "c"] {
+            ids.push(store.add_ticket(draft(title)).await.unwrap().id);
+        }
+
+        store.reorder(&ids[2], ReorderTarget::Start).await.unwrap();
+        store.reorder(&ids[0], ReorderTarget::End).await.unwrap();
+
+        let titles: Vec<String> = backlog(&store)
+            .await
+            .into_iter()
+            .map(|ticket| ticket.title.0)
+            .collect();
+        assert_eq!(titles, ["c", "b", "a"]);
+        assert_eq!(store.get_ticket(&ids[1]).await.unwrap().version, 1);
+        assert_eq!(store.get_ticket(&ids[2]).await.unwrap().version, 2);
+    }
+
+    #[tokio::test]
+    async fn renumbering_the_backlog_updates_every_renumbered_ticket() {
+        let store = sequential_store();
+        let mut ids = Vec::new();
+        for title in ["anchor", "b", "c"] {
+            let id = store.add_ticket(draft(title)).await.unwrap().id;
+            store.mark_synced(&id).await.unwrap();
+            ids.push(id);
+        }
+
+        // Each move halves the gap after the anchor until there's no room left
+        for round in 0..200 {
+            let moved = &ids[1 + round % 2];
+            store
+                .reorder(moved, ReorderTarget::After(ids[0].clone()))
+                .await
+                .unwrap();
+        }
+
+        let anchor = store.get_ticket(&ids[0]).await.unwrap();
+        assert!(anchor.version > 1, "the anchor was never renumbered");
+        assert_eq!(anchor.sync_state, SyncState::Pending);
+        let history = store.get_history(&ids[0]).await.unwrap();
+        assert!(history.iter().any(|entry| entry.field == "rank"));
+        let (changes, _) = store.changes_since(0).unwrap();
+        assert!(changes.iter().any(|change| {
+            change.event == TicketEvent::Updated { id: ids[0].clone() }
+                && change.fields.contains(&"rank".to_string())
+        }));
+        let order: Vec<TicketId> = backlog(&store).await.into_iter().map(|t| t.id).collect();
+        assert_eq!(order[0], ids[0]);
+    }
+
+    /// Hands out sequential IDs, counting how often it is called.
+    #[derive(Default)]
+    struct CountingGenerator {
+        calls: std::sync::atomic::AtomicUsize,
+        ids: SequentialIdGenerator,
+    }
+
+    impl IdGenerator for CountingGenerator {
+        fn generate(&self) -> TicketId {
+            self.calls
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            self.ids.generate()
+        }
+    }
+
+    #[tokio::test]
+    async fn the_id_generator_is_called_once_per_created_ticket() {
+        let generator = Arc::new(CountingGenerator::default());
+        let config = ServerConfig {
+            max_tickets: Some(6),
+            ..ServerConfig::default()
+        };
+        let store = TicketStore::with_id_generator(config, generator.clone());
+        let calls = || generator.calls.load(std::sync::atomic::Ordering::Relaxed);
+
+        let first = store.add_ticket(draft("one")).await.unwrap();
+        assert_eq!(calls(), 1);
+        store
+            .add_tickets(vec![draft("two"), draft("three"), draft("four")])
+            .await
+            .unwrap();
+        assert_eq!(calls(), 4);
+        let too_many = store
+            .add_tickets(vec![draft("five"), draft("six"), draft("seven")])
+            .await;
+        assert!(matches!(too_many, Err(StoreError::CapacityExceeded { .. })));
+        assert_eq!(calls(), 4);
+
+        let missing = TicketId(uuid::Uuid::from_u128(99));
+        let rolled_back = store
+            .apply_transaction(
+                vec![
+                    TicketOperation::Create(draft("five")),
+                    TicketOperation::Delete(missing),
+                ],
+                None,
+            )
+            .await;
+        assert!(matches!(
+            rolled_back,
+            Err((1, StoreError::TicketNotFound(_)))
+        ));
+        assert_eq!(calls(), 4);
+
+        let results = store
+            .apply_transaction(
+                vec![
+                    TicketOperation::Create(draft("five")),
+                    TicketOperation::Patch(first.id.clone(), retitle("first"), false),
+                    TicketOperation::Create(draft("six")),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(calls(), 6);
+        let titles: Vec<String> = results
+            .into_iter()
+            .map(|ticket| ticket.unwrap().title.0)
+            .collect();
+        assert_eq!(titles, ["five", "first", "six"]);
+        assert_eq!(store.list_tickets().await.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn a_page_written_in_place_matches_the_cloned_responses() {
+        let store = sequential_store();
+        for title in ["one", "two", "three"] {
+            store.add_ticket(draft(title)).await.unwrap();
+        }
+
+        let mut written = Vec::new();
+        let total = store
+            .write_page_json(&TicketFilter::default(), 1, 5, &mut written)
+            .await
+            .unwrap();
+        let (page, _) = store.list_tickets_paginated(1, 5, false).await;
+        let responses: Vec<crate::data::TicketResponse> =
+            page.into_iter().map(Into::into).collect();
+
+        assert_eq!(total, 3);
+        assert_eq!(
+            written,
+            serde_json::to_vec(&serde_json::json!(responses)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn cursor_pages_neither_skip_nor_repeat_when_tickets_come_and_go() {
+        let store = sequential_store();
+        let mut ids = Vec::new();
+        for n in 0..5 {
+            ids.push(
+                store
+                    .add_ticket(draft(&format!("t{}", n)))
+                    .await
+                    .unwrap()
+                    .id,
+            );
+        }
+        let titles = |page: &[Ticket]| -> Vec<String> {
+            page.iter().map(|ticket| ticket.title.0.clone()).collect()
+        };
+
+        let (first, cursor) = store.list_after_cursor(None, 2).await;
+        assert_eq!(titles(&first), ["t0", "t1"]);
+
+        // One new ticket, one seen and one unseen ticket deleted
+        store.add_ticket(draft("t5")).await.unwrap();
+        store.soft_delete(&ids[0]).await.unwrap();
+        store.soft_delete(&ids[2]).await.unwrap();
+
+        let (second, cursor) = store.list_after_cursor(cursor, 2).await;
+        assert_eq!(titles(&second), ["t3", "t4"]);
+        let (stalled, same) = store.list_after_cursor(cursor.clone(), 0).await;
+        assert!(stalled.is_empty());
+        assert_eq!(same, cursor);
+        let (third, cursor) = store.list_after_cursor(cursor, 2).await;
+        assert_eq!(titles(&third), ["t5"]);
+        assert_eq!(cursor, None);
     }
 }
@@ -0,0 +1,129 @@
+//! Webhook notifications for ticket events.
+//!
+//! [`spawn_webhooks`] subscribes to the store's events and POSTs each one to
+//! every configured URL from a background task, so request handlers never
+//! wait on a webhook. A failed delivery is retried with exponential backoff
+//! a bounded number of times, then logged and dropped.
+//!
+//! Each delivery is a JSON object holding the event, when it was dispatched
+//! and the ticket as it is at that moment (`null` once it has been removed):
+//!
+//! ```json
+//! { "type": "Updated", "id": "...", "at": "...", "ticket": { ... } }
+//! ```
+//!
+//! Deliveries run concurrently, so a receiver may see events out of order;
+//! `at` and the ticket's `version` tell them apart.
+
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+use crate::config::WebhookConfig;
+use crate::data::{TicketEvent, TicketResponse};
+use crate::handlers::AppState;
+
+/// Starts delivering the store's events to the configured webhooks.
+///
+/// # Arguments
+/// * `config` - The webhook URLs and retry policy
+/// * `store` - The store whose events are delivered
+///
+/// # Returns
+/// The background task, or `None` when no URL is configured and there is
+/// nothing to deliver.
+pub fn spawn_webhooks(config: WebhookConfig, store: AppState) -> Option<JoinHandle<()>> {
+    if config.urls.is_empty() {
+        return None;
+    }
+
+    let client = match reqwest::Client::builder().timeout(config.timeout).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("Webhooks disabled, could not build the HTTP client: {}", e);
+            return None;
+        }
+    };
+    let config = Arc::new(config);
+    let mut receiver = store.subscribe();
+
+    Some(tokio::spawn(async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Webhooks lagged, skipping {} events", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            let payload = Arc::new(event_payload(&store, &event).await);
+            for url in &config.urls {
+                tokio::spawn(deliver(
+                    client.clone(),
+                    config.clone(),
+                    url.clone(),
+                    payload.clone(),
+                ));
+            }
+        }
+    }))
+}
+
+/// Builds the JSON body delivered for `event`.
+async fn event_payload(store: &AppState, event: &TicketEvent) -> Value {
+    let id = match event {
+        TicketEvent::Created { id }
+        | TicketEvent::Updated { id }
+        | TicketEvent::Deleted { id }
+        | TicketEvent::Restored { id } => id,
+    };
+    let ticket = store.get_ticket(id).await.ok().map(TicketResponse::from);
+
+    let mut payload = json!(event);
+    payload["at"] = json!(Utc::now());
+    payload["ticket"] = json!(ticket);
+    payload
+}
+
+/// POSTs `payload` to `url`, retrying failures up to `max_attempts` in all.
+///
+/// Any response other than a 2xx counts as a failure. The wait between
+/// attempts starts at `initial_backoff` and doubles each time.
+async fn deliver(
+    client: reqwest::Client,
+    config: Arc<WebhookConfig>,
+    url: String,
+    payload: Arc<Value>,
+) {
+    let mut backoff = config.initial_backoff;
+    for attempt in 1..=config.max_attempts.max(1) {
+        let failure = match client.post(&url).json(&*payload).send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => format!("status {}", response.status()),
+            Err(e) => e.to_string(),
+        };
+
+        if attempt < config.max_attempts {
+            tracing::warn!(
+                "Webhook delivery to {} failed ({}), attempt {} of {}",
+                url,
+                failure,
+                attempt,
+                config.max_attempts
+            );
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        } else {
+            tracing::error!(
+                "Webhook delivery to {} failed ({}), giving up after {} attempts",
+                url,
+                failure,
+                attempt
+            );
+        }
+    }
+}
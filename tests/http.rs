@@ -0,0 +1,675 @@
+//! Exercises the HTTP API through the router, without binding a socket.
+
+use axum::body::{to_bytes, Body};
+use axum::extract::ConnectInfo;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::Router;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::time::Duration;
+use ticket_api::{build_router, RateLimit, ServerConfig, COMPRESSION_MIN_BYTES, MAX_BATCH_SIZE};
+use tower::ServiceExt;
+
+const NIL_ID: &str = "00000000-0000-0000-0000-000000000000";
+
+/// Sends one request through a clone of `router`.
+///
+/// # Returns
+/// The response's status, headers and body, the body parsed as JSON (or
+/// `Value::Null` when empty).
+async fn send(
+    router: &Router,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, header::HeaderMap, Value) {
+    let request = Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let (parts, body) = response.into_parts();
+    let bytes = to_bytes(body, usize::MAX).await.unwrap();
+    let body = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (parts.status, parts.headers, body)
+}
+
+#[tokio::test]
+async fn the_nil_uuid_is_rejected_as_an_invalid_id() {
+    let router = build_router(ServerConfig::default());
+    let uri = format!("/tickets/{}", NIL_ID);
+
+    for (method, body) in [
+        (Method::GET, None),
+        (Method::PATCH, Some(json!({ "title": "nil" }))),
+        (Method::DELETE, None),
+    ] {
+        let (status, _, body) = send(&router, method.clone(), &uri, body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", method);
+        assert_eq!(body["error"]["code"], "INVALID_ID", "{}", method);
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("nil UUID"));
+    }
+}
+
+#[tokio::test]
+async fn the_tag_filter_matches_regardless_of_case() {
+    let router = build_router(ServerConfig::default());
+    for (title, tags) in [("tagged", json!(["bug"])), ("other", json!(["feature"]))] {
+        let body = json!({ "title": title, "description": "", "tags": tags });
+        let (status, _, _) = send(&router, Method::POST, "/tickets", Some(body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    for query in ["Bug", "BUG", "bug"] {
+        let uri = format!("/tickets?tag={}", query);
+        let (status, _, body) = send(&router, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        let titles: Vec<&str> = body["tickets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ticket| ticket["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, ["tagged"], "{}", query);
+    }
+}
+
+#[tokio::test]
+async fn batches_are_capped_at_max_batch_size() {
+    let router = build_router(ServerConfig::default());
+    let batch = |len: usize| {
+        let items: Vec<Value> = (0..len)
+            .map(|i| json!({ "title": format!("batch {}", i), "description": "" }))
+            .collect();
+        Some(Value::from(items))
+    };
+
+    let (status, _, body) = send(
+        &router,
+        Method::POST,
+        "/tickets/batch",
+        batch(MAX_BATCH_SIZE + 1),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["error"]["code"], "BATCH_TOO_LARGE");
+    let (_, _, body) = send(&router, Method::GET, "/tickets/count", None).await;
+    assert_eq!(body["total"], 0);
+
+    let (status, _, body) = send(
+        &router,
+        Method::POST,
+        "/tickets/batch",
+        batch(MAX_BATCH_SIZE),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["tickets"].as_array().unwrap().len(), MAX_BATCH_SIZE);
+    assert_eq!(body["errors"], json!([]));
+}
+
+#[tokio::test]
+async fn list_pages_link_to_their_neighbours() {
+    // Nested, so the links must keep the prefix the router itself never sees
+    let router = Router::new().nest("/api", build_router(ServerConfig::default()));
+    for i in 0..5 {
+        let body = json!({ "title": format!("page {}", i), "description": "" });
+        send(&router, Method::POST, "/api/tickets", Some(body)).await;
+    }
+    let link = |offset: usize, rel: &str| {
+        format!(
+            "</api/tickets?priority=Medium&limit=2&offset={}>; rel=\"{}\"",
+            offset, rel
+        )
+    };
+
+    for (offset, expected) in [
+        (0, vec![link(0, "first"), link(2, "next"), link(4, "last")]),
+        (
+            2,
+            vec![
+                link(0, "first"),
+                link(0, "prev"),
+                link(4, "next"),
+                link(4, "last"),
+            ],
+        ),
+        (4, vec![link(0, "first"), link(2, "prev"), link(4, "last")]),
+    ] {
+        let uri = format!("/api/tickets?offset={}&priority=Medium&limit=2", offset);
+        let (status, headers, _) = send(&router, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            headers[header::LINK],
+            expected.join(", "),
+            "offset {}",
+            offset
+        );
+    }
+}
+
+#[tokio::test]
+async fn the_event_stream_only_sends_matching_events() {
+    let router = build_router(ServerConfig::default());
+    let (status, _, body) = send(&router, Method::GET, "/tickets/events?status=Nope", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_STATUS_FILTER");
+
+    let request = Request::get("/tickets/events?assignee=alice")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let mut events = response.into_body().into_data_stream();
+
+    let mut ids = Vec::new();
+    for assignee in ["bob", "alice"] {
+        let body = json!({ "title": assignee, "description": "", "assignee": assignee });
+        let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+        ids.push(ticket["id"].as_str().unwrap().to_string());
+    }
+    let uri = format!("/tickets/{}", ids[1]);
+    send(&router, Method::DELETE, &uri, None).await;
+
+    for expected in ["Created", "Deleted"] {
+        let frame = tokio::time::timeout(Duration::from_secs(1), events.next())
+            .await
+            .expect("no event arrived")
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        let data: Value = serde_json::from_str(frame.trim().trim_start_matches("data:")).unwrap();
+        assert_eq!(data["type"], expected);
+        assert_eq!(data["id"], ids[1]);
+    }
+}
+
+#[tokio::test]
+async fn event_subscribers_are_capped_until_one_disconnects() {
+    let config = ServerConfig {
+        max_event_subscribers: Some(1),
+        ..ServerConfig::default()
+    };
+    let router = build_router(config);
+    let subscribe = || async {
+        let request = Request::get("/tickets/events").body(Body::empty()).unwrap();
+        router.clone().oneshot(request).await.unwrap()
+    };
+
+    let first = subscribe().await;
+    assert_eq!(first.status(), StatusCode::OK);
+    let rejected = subscribe().await;
+    assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(rejected.headers()[header::RETRY_AFTER], "5");
+
+    drop(first);
+    assert_eq!(subscribe().await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn verbose_errors_add_the_store_error_as_source() {
+    let uri = "/tickets/67e55044-10b1-426f-9247-bb680e5fe0c8";
+    let (status, _, compact) = send(
+        &build_router(ServerConfig::default()),
+        Method::GET,
+        uri,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        compact["error"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        ["code", "message"]
+    );
+
+    let config = ServerConfig {
+        verbose_errors: true,
+        ..ServerConfig::default()
+    };
+    let (status, _, verbose) = send(&build_router(config), Method::GET, uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(verbose["error"]["code"], compact["error"]["code"]);
+    let source = &verbose["error"]["source"];
+    assert_eq!(source["error"], compact["error"]["message"]);
+    assert!(source["debug"]
+        .as_str()
+        .unwrap()
+        .starts_with("TicketNotFound("));
+}
+
+#[tokio::test]
+async fn batch_get_projects_each_ticket_onto_fields() {
+    let router = build_router(ServerConfig::default());
+    let mut ids = Vec::new();
+    for title in ["first", "second"] {
+        let body = json!({ "title": title, "description": "" });
+        let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+        ids.push(ticket["id"].clone());
+    }
+
+    let body = Some(json!({ "ids": ids }));
+    let uri = "/tickets/batch-get?fields=title,status";
+    let (status, _, fetched) = send(&router, Method::POST, uri, body.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    for id in &ids {
+        let ticket = fetched["tickets"][id.as_str().unwrap()]
+            .as_object()
+            .unwrap();
+        let mut keys: Vec<&String> = ticket.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["id", "status", "title"]);
+    }
+
+    let uri = "/tickets/batch-get?fields=title,colour";
+    let (status, _, fetched) = send(&router, Method::POST, uri, body).await;
+    assert_eq!(status, StatusCode::OK);
+    for id in &ids {
+        let ticket = fetched["tickets"][id.as_str().unwrap()]
+            .as_object()
+            .unwrap();
+        let mut keys: Vec<&String> = ticket.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["id", "title"]);
+    }
+}
+
+#[tokio::test]
+async fn search_all_matches_comments_and_highlights_them() {
+    let router = build_router(ServerConfig::default());
+    let mut ids = Vec::new();
+    for title in ["Login fails", "Slow export"] {
+        let body = json!({ "title": title, "description": "" });
+        let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+        ids.push(ticket["id"].as_str().unwrap().to_string());
+    }
+    let uri = format!("/tickets/{}/comments", ids[1]);
+    let body = json!({ "author": "ana", "body": "Started after the LOGIN change" });
+    send(&router, Method::POST, &uri, Some(body)).await;
+
+    let (status, _, found) = send(&router, Method::GET, "/tickets/search-all?q=login", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let results = found["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ticket"]["id"], ids[0]);
+    assert_eq!(results[0]["comments"], json!([]));
+    assert_eq!(results[1]["ticket"]["id"], ids[1]);
+    assert_eq!(
+        results[1]["comments"][0]["snippet"],
+        "Started after the <mark>LOGIN</mark> change"
+    );
+
+    let (status, _, _) = send(&router, Method::GET, "/tickets/search-all?q=%20", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn comment_batches_keep_order_and_timestamps_and_report_bad_items() {
+    let router = build_router(ServerConfig::default());
+    let body = json!({ "title": "Imported", "description": "" });
+    let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+    let uri = format!("/tickets/{}/comments/batch", ticket["id"].as_str().unwrap());
+
+    let items = json!([
+        { "author": "ana", "body": "first", "created_at": "2020-01-01T10:00:00Z" },
+        { "author": "ana", "body": "" },
+        { "author": "bo", "body": "second" },
+        { "author": "bo", "body": "later", "created_at": "2999-01-01T00:00:00Z" },
+    ]);
+    let (status, _, added) = send(&router, Method::POST, &uri, Some(items)).await;
+    assert_eq!(status, StatusCode::OK);
+    let comments = added["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0]["body"], "first");
+    assert_eq!(comments[0]["created_at"], "2020-01-01T10:00:00Z");
+    assert_eq!(comments[1]["body"], "second");
+    let rejected: Vec<&Value> = added["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| &error["index"])
+        .collect();
+    assert_eq!(rejected, [&json!(1), &json!(3)]);
+
+    let listed = uri.trim_end_matches("/batch");
+    let (_, _, listed) = send(&router, Method::GET, listed, None).await;
+    assert_eq!(listed["comments"], added["comments"]);
+}
+
+/// Sends `GET /tickets` as if from `peer`, naming `forwarded_for` in
+/// `X-Forwarded-For`.
+async fn get_from(router: &Router, peer: &str, forwarded_for: &str) -> StatusCode {
+    let peer: SocketAddr = peer.parse().unwrap();
+    let request = Request::builder()
+        .uri("/tickets")
+        .header("x-forwarded-for", forwarded_for)
+        .extension(ConnectInfo(peer))
+        .body(Body::empty())
+        .unwrap();
+    router.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn the_rate_limit_ignores_forwarded_for_from_untrusted_peers() {
+    let config = ServerConfig {
+        rate_limit: Some(RateLimit::per_minute(2)),
+        ..ServerConfig::default()
+    };
+    let router = build_router(config);
+
+    assert_eq!(
+        get_from(&router, "10.0.0.1:4000", "1.1.1.1").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_from(&router, "10.0.0.1:4001", "2.2.2.2").await,
+        StatusCode::OK
+    );
+    let spoofed = get_from(&router, "10.0.0.1:4002", "3.3.3.3").await;
+    assert_eq!(spoofed, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        get_from(&router, "10.0.0.2:4000", "3.3.3.3").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn the_rate_limit_believes_forwarded_for_from_trusted_proxies() {
+    let mut limit = RateLimit::per_minute(1);
+    limit.trusted_proxies = vec!["10.0.0.9".parse().unwrap()];
+    let config = ServerConfig {
+        rate_limit: Some(limit),
+        ..ServerConfig::default()
+    };
+    let router = build_router(config);
+    let proxy = "10.0.0.9:4000";
+
+    assert_eq!(get_from(&router, proxy, "1.1.1.1").await, StatusCode::OK);
+    // The client can prepend anything; the proxy's own entry comes last
+    let prepended = get_from(&router, proxy, "9.9.9.9, 1.1.1.1").await;
+    assert_eq!(prepended, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(get_from(&router, proxy, "2.2.2.2").await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn cursor_pages_need_a_limit_of_at_least_one() {
+    let router = build_router(ServerConfig::default());
+    let body = json!({ "title": "only", "description": "" });
+    send(&router, Method::POST, "/tickets", Some(body)).await;
+
+    let (status, _, error) = send(&router, Method::GET, "/tickets?cursor=&limit=0", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"]["code"], "INVALID_LIMIT");
+
+    let (status, _, page) = send(&router, Method::GET, "/tickets?cursor=&limit=1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["tickets"].as_array().unwrap().len(), 1);
+    assert_eq!(page["next_cursor"], Value::Null);
+}
+
+/// Sends `body` to `POST /tickets` of `router`, declaring its length unless
+/// `chunked`, in which case it is streamed in two parts of unknown size.
+async fn post_raw(router: &Router, body: String, chunked: bool) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/tickets")
+        .header(header::CONTENT_TYPE, "application/json");
+    let request = if chunked {
+        let (head, tail) = body.split_at(body.len() / 2);
+        let parts = vec![
+            Ok::<_, std::io::Error>(head.to_string()),
+            Ok(tail.to_string()),
+        ];
+        request.body(Body::from_stream(futures::stream::iter(parts)))
+    } else {
+        request
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+    }
+    .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn bodies_up_to_the_limit_are_accepted_and_larger_ones_refused_unread() {
+    let limit = 256;
+    let config = ServerConfig {
+        max_body_bytes: limit,
+        ..ServerConfig::default()
+    };
+    let router = build_router(config);
+    let envelope = r#"{"title":"Sized","description":""}"#.len();
+    let exact = format!(
+        r#"{{"title":"Sized","description":"{}"}}"#,
+        "d".repeat(limit - envelope)
+    );
+    assert_eq!(exact.len(), limit);
+
+    for chunked in [false, true] {
+        let (status, _) = post_raw(&router, exact.clone(), chunked).await;
+        assert_eq!(status, StatusCode::CREATED, "chunked: {}", chunked);
+
+        // Not even JSON, so a 413 means it was refused before parsing
+        let (status, error) = post_raw(&router, "x".repeat(limit + 1), chunked).await;
+        assert_eq!(
+            status,
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "chunked: {}",
+            chunked
+        );
+        assert_eq!(
+            error["error"]["code"], "PAYLOAD_TOO_LARGE",
+            "chunked: {}",
+            chunked
+        );
+    }
+}
+
+/// Sends `GET uri` accepting gzip when `gzip` is set.
+///
+/// # Returns
+/// The response's `Content-Encoding`, if any, and its body length
+async fn get_encoded(router: &Router, uri: &str, gzip: bool) -> (Option<String>, usize) {
+    let mut request = Request::builder().uri(uri);
+    if gzip {
+        request = request.header(header::ACCEPT_ENCODING, "gzip");
+    }
+    let response = router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let encoding = response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (encoding, bytes.len())
+}
+
+#[tokio::test]
+async fn only_responses_above_the_threshold_are_compressed() {
+    let router = build_router(ServerConfig::default());
+
+    let (encoding, length) = get_encoded(&router, "/tickets", true).await;
+    assert_eq!(encoding, None);
+    assert!(length < usize::from(COMPRESSION_MIN_BYTES));
+    assert_eq!(get_encoded(&router, "/health", true).await.0, None);
+
+    for n in 0..20 {
+        let body = json!({ "title": format!("Ticket {}", n), "description": "Worth compressing" });
+        send(&router, Method::POST, "/tickets", Some(body)).await;
+    }
+    let (encoding, _) = get_encoded(&router, "/tickets", true).await;
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    let (encoding, length) = get_encoded(&router, "/tickets", false).await;
+    assert_eq!(encoding, None);
+    assert!(length > usize::from(COMPRESSION_MIN_BYTES));
+    assert_eq!(get_encoded(&router, "/health", true).await.0, None);
+}
+
+#[tokio::test]
+async fn a_disallowed_move_gets_the_same_error_on_every_endpoint() {
+    let router = build_router(ServerConfig::default());
+    let body = json!({ "title": "workflow", "description": "" });
+    let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+    let uri = format!("/tickets/{}", ticket["id"].as_str().unwrap());
+
+    for (method, uri, body) in [
+        (Method::PATCH, uri.clone(), json!({ "status": "Done" })),
+        (
+            Method::PUT,
+            uri.clone(),
+            json!({ "title": "workflow", "description": "", "status": "Done" }),
+        ),
+        (
+            Method::POST,
+            format!("{}/transition", uri),
+            json!({ "to": "Done" }),
+        ),
+        (
+            Method::POST,
+            format!("{}/cas-status", uri),
+            json!({ "expected": "ToDo", "new": "Done" }),
+        ),
+    ] {
+        let (status, _, error) = send(&router, method.clone(), &uri, Some(body)).await;
+        assert_eq!(status, StatusCode::CONFLICT, "{} {}", method, uri);
+        let error = &error["error"];
+        assert_eq!(error["code"], "INVALID_TRANSITION", "{} {}", method, uri);
+        assert_eq!(error["from"], "ToDo");
+        assert_eq!(error["to"], "Done");
+        assert_eq!(error["allowed"], json!(["InProgress", "Cancelled"]));
+    }
+}
+
+#[tokio::test]
+async fn a_patch_reports_every_invalid_field() {
+    let router = build_router(ServerConfig::default());
+    let body = json!({ "title": "valid", "description": "" });
+    let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+    let uri = format!("/tickets/{}", ticket["id"].as_str().unwrap());
+
+    let patch = json!({ "title": "", "priority": null });
+    let (status, _, error) = send(&router, Method::PATCH, &uri, Some(patch)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"]["code"], "VALIDATION_FAILED");
+    let fields: Vec<&str> = error["error"]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["title", "priority"]);
+}
+
+#[tokio::test]
+async fn bulk_failures_carry_the_single_ticket_error_code() {
+    let router = build_router(ServerConfig::default());
+    let mut ids = Vec::new();
+    for title in ["waiting", "started", "deleted"] {
+        let body = json!({ "title": title, "description": "" });
+        let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+        ids.push(ticket["id"].as_str().unwrap().to_string());
+    }
+    let uri = format!("/tickets/{}/transition", ids[1]);
+    send(
+        &router,
+        Method::POST,
+        &uri,
+        Some(json!({ "to": "InProgress" })),
+    )
+    .await;
+    let uri = format!("/tickets/{}", ids[2]);
+    send(&router, Method::DELETE, &uri, None).await;
+
+    let body = json!({ "ids": ids, "status": "Done" });
+    let (status, _, bulk) = send(&router, Method::POST, "/tickets/bulk-status", Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+    let results = &bulk["results"];
+    assert_eq!(results[0]["outcome"], "invalid_transition");
+    assert_eq!(results[0]["code"], "INVALID_TRANSITION");
+    assert_eq!(results[0]["allowed"], json!(["InProgress", "Cancelled"]));
+    assert_eq!(results[1]["outcome"], "updated");
+    assert_eq!(results[2]["outcome"], "not_found");
+    assert_eq!(results[2]["code"], "TICKET_NOT_FOUND");
+
+    let body = json!({ "ids": [ids[0], ids[2]], "set": { "team": "core" } });
+    let (_, _, bulk) = send(&router, Method::POST, "/tickets/bulk-custom", Some(body)).await;
+    assert_eq!(bulk["results"][0]["outcome"], "updated");
+    assert_eq!(bulk["results"][1]["outcome"], "not_found");
+    assert_eq!(bulk["results"][1]["code"], "TICKET_NOT_FOUND");
+}
+
+/// GETs `uri` with an optional `Accept` header.
+///
+/// # Returns
+/// The response's status, `Content-Type` and body as text.
+async fn get_accepting(
+    router: &Router,
+    uri: &str,
+    accept: Option<&str>,
+) -> (StatusCode, Option<String>, String) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(accept) = accept {
+        request = request.header(header::ACCEPT, accept);
+    }
+    let request = request.body(Body::empty()).unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn unservable_accept_headers_get_406_and_wildcards_get_json() {
+    let router = build_router(ServerConfig::default());
+    let body = json!({ "title": "negotiated", "description": "" });
+    let (_, _, ticket) = send(&router, Method::POST, "/tickets", Some(body)).await;
+    let one = format!("/tickets/{}", ticket["id"].as_str().unwrap());
+
+    for uri in ["/tickets", one.as_str()] {
+        let (status, _, body) = get_accepting(&router, uri, Some("application/xml")).await;
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE, "{}", uri);
+        let error: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(error["error"]["code"], "NOT_ACCEPTABLE", "{}", uri);
+        assert_eq!(error["error"]["accept"], "application/xml", "{}", uri);
+
+        for accept in [Some("*/*"), None] {
+            let (status, content_type, body) = get_accepting(&router, uri, accept).await;
+            assert_eq!(status, StatusCode::OK, "{} {:?}", uri, accept);
+            assert_eq!(content_type.as_deref(), Some("application/json"));
+            assert!(serde_json::from_str::<Value>(&body).is_ok());
+        }
+    }
+}